@@ -0,0 +1,43 @@
+use criterion::{criterion_group, criterion_main, Bencher, Criterion};
+
+use pyo3::prelude::*;
+#[cfg(not(any(Py_LIMITED_API, PyPy)))]
+use pyo3::types::PyMemoryView;
+use pyo3::types::{PyBytes, PySlice};
+
+const LEN: usize = 1_000_000;
+
+fn slice_bytes(b: &mut Bencher) {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let bytes = PyBytes::new(py, &vec![0; LEN]);
+    let half = PySlice::new(py, 0, (LEN / 2) as isize, 1);
+    // Copies half of the data on every iteration
+    b.iter(|| {
+        let _pool = unsafe { py.new_pool() };
+        bytes.get_item(half).unwrap();
+    });
+}
+
+#[cfg(not(any(Py_LIMITED_API, PyPy)))]
+fn slice_memoryview(b: &mut Bencher) {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let bytes = PyBytes::new(py, &vec![0; LEN]);
+    let view = PyMemoryView::from_buffer(py, bytes).unwrap();
+    let half = PySlice::new(py, 0, (LEN / 2) as isize, 1);
+    // Creates a new view of the same memory on every iteration
+    b.iter(|| {
+        let _pool = unsafe { py.new_pool() };
+        view.get_item(half).unwrap();
+    });
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    c.bench_function("slice_bytes", slice_bytes);
+    #[cfg(not(any(Py_LIMITED_API, PyPy)))]
+    c.bench_function("slice_memoryview", slice_memoryview);
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
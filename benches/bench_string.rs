@@ -0,0 +1,45 @@
+use criterion::{criterion_group, criterion_main, Bencher, Criterion};
+
+use pyo3::prelude::*;
+use pyo3::types::PyList;
+
+const LEN: usize = 100_000;
+
+fn short_strings(py: Python) -> &PyList {
+    PyList::new(py, (0..LEN).map(|i| format!("string {}", i)))
+}
+
+fn extract_string(b: &mut Bencher) {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let list = short_strings(py);
+    let mut total = 0;
+    b.iter(|| {
+        for item in list.iter() {
+            let s: String = item.extract().unwrap();
+            total += s.len();
+        }
+    });
+}
+
+fn extract_str(b: &mut Bencher) {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let list = short_strings(py);
+    let mut total = 0;
+    b.iter(|| {
+        for item in list.iter() {
+            // Borrows the UTF-8 buffer cached by the Python string instead of allocating
+            let s: &str = item.extract().unwrap();
+            total += s.len();
+        }
+    });
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    c.bench_function("extract_string", extract_string);
+    c.bench_function("extract_str", extract_str);
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -128,6 +128,11 @@ fn emit_cargo_configuration(interpreter_config: &InterpreterConfig) -> Result<()
             if let Some(base_prefix) = &interpreter_config.base_prefix {
                 println!("cargo:rustc-link-search=native={}\\libs", base_prefix);
             }
+            // Cargo adds native search paths to PATH for `cargo run` and `cargo test`, which lets
+            // Conda's pythonXY.dll and its dependencies be found at runtime.
+            for path in interpreter_config.conda_link_search_paths(&target_os) {
+                println!("cargo:rustc-link-search=native={}", path);
+            }
         }
         (true, "macos") => {
             // with extension module on macos some extra linker arguments are needed
@@ -141,6 +146,9 @@ fn emit_cargo_configuration(interpreter_config: &InterpreterConfig) -> Result<()
             if let Some(libdir) = &interpreter_config.libdir {
                 println!("cargo:rustc-link-search=native={}", libdir);
             }
+            for path in interpreter_config.conda_link_search_paths(&target_os) {
+                println!("cargo:rustc-link-search=native={}", path);
+            }
             if interpreter_config.implementation == PythonImplementation::PyPy {
                 // PyPy 7.3.4 changed LIBDIR to point to base_prefix/lib as a regression, so need
                 // to hard-code /bin search path too: https://foss.heptapod.net/pypy/pypy/-/issues/3442
@@ -189,6 +197,7 @@ fn configure_pyo3() -> Result<()> {
     let interpreter_config = pyo3_build_config::get();
     ensure_python_version(&interpreter_config)?;
     ensure_target_architecture(&interpreter_config)?;
+    interpreter_config.ensure_gil_enabled()?;
     emit_cargo_configuration(&interpreter_config)?;
     interpreter_config.emit_pyo3_cfgs();
 
@@ -197,6 +206,11 @@ fn configure_pyo3() -> Result<()> {
         println!("cargo:rustc-cfg=min_const_generics");
     }
 
+    // Enable `#[diagnostic::on_unimplemented]` on Rust 1.78 and greater
+    if rustc_minor_version().unwrap_or(0) >= 78 {
+        println!("cargo:rustc-cfg=diagnostic_namespace");
+    }
+
     Ok(())
 }
 
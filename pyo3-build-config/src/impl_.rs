@@ -63,10 +63,14 @@ pub struct InterpreterConfig {
     pub abi3: bool,
     pub ld_version: Option<String>,
     pub base_prefix: Option<String>,
+    /// The prefix of the Conda environment containing the interpreter, if any.
+    pub conda_prefix: Option<String>,
     pub executable: Option<String>,
     pub calcsize_pointer: Option<u32>,
     pub implementation: PythonImplementation,
     pub build_flags: BuildFlags,
+    /// Whether the interpreter is a free-threaded build (PEP 703), which can run without the GIL.
+    pub free_threaded: bool,
 }
 
 impl InterpreterConfig {
@@ -99,6 +103,33 @@ impl InterpreterConfig {
     pub fn is_pypy(&self) -> bool {
         self.implementation == PythonImplementation::PyPy
     }
+
+    /// Returns an error if the interpreter is a free-threaded build.
+    pub fn ensure_gil_enabled(&self) -> Result<()> {
+        // Free-threaded builds use a different object layout and reference counting scheme, which
+        // PyO3's FFI definitions and GIL-based safety guarantees don't account for yet.
+        ensure!(
+            !self.free_threaded,
+            "the configured Python interpreter is a free-threaded build, which PyO3 does not support yet"
+        );
+
+        Ok(())
+    }
+
+    /// Returns the extra native library search paths needed to link against a Conda Python.
+    ///
+    /// On Windows Conda installs `pythonXY.dll` in the environment root and its dependencies in
+    /// `Library\bin`. Elsewhere libpython lives in `$CONDA_PREFIX/lib`, which `LIBDIR` may not
+    /// point to, for example on macOS.
+    pub fn conda_link_search_paths(&self, target_os: &str) -> Vec<String> {
+        match (&self.conda_prefix, target_os) {
+            (Some(prefix), "windows") => {
+                vec![prefix.clone(), format!("{}\\Library\\bin", prefix)]
+            }
+            (Some(prefix), _) => vec![format!("{}/lib", prefix)],
+            (None, _) => Vec::new(),
+        }
+    }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -348,6 +379,17 @@ fn parse_script_output(output: &str) -> HashMap<String, String> {
         .collect()
 }
 
+/// Returns whether the build variables in `config_map` are of a free-threaded build.
+///
+/// `key` is unset by Python versions older than 3.13, which can't be free-threaded.
+fn is_free_threaded(config_map: &HashMap<String, String>, key: &str) -> Result<bool> {
+    if config_map.contains_key(key) {
+        config_map.get_bool(key)
+    } else {
+        Ok(false)
+    }
+}
+
 /// Parse sysconfigdata file
 ///
 /// The sysconfigdata is simply a dictionary containing all the build time variables used for the
@@ -366,7 +408,8 @@ KEYS = [
     "COUNT_ALLOCS",
     "Py_ENABLE_SHARED",
     "LDVERSION",
-    "SIZEOF_VOID_P"
+    "SIZEOF_VOID_P",
+    "Py_GIL_DISABLED"
 ]
 for key in KEYS:
     print(key, build_time_vars.get(key, 0))
@@ -517,10 +560,12 @@ fn load_cross_compile_from_sysconfigdata(
         abi3: is_abi3(),
         ld_version: Some(ld_version),
         base_prefix: None,
+        conda_prefix: None,
         executable: None,
         calcsize_pointer,
         implementation,
         build_flags: BuildFlags::from_config_map(&sysconfig_data).fixup(version, implementation),
+        free_threaded: is_free_threaded(&sysconfig_data, "Py_GIL_DISABLED")?,
     })
 }
 
@@ -553,10 +598,12 @@ fn windows_hardcoded_cross_compile(
         abi3: is_abi3(),
         ld_version: None,
         base_prefix: None,
+        conda_prefix: None,
         executable: None,
         calcsize_pointer: None,
         implementation: PythonImplementation::CPython,
         build_flags: BuildFlags::windows_hardcoded(),
+        free_threaded: false,
     })
 }
 
@@ -621,6 +668,28 @@ fn get_venv_path() -> Option<PathBuf> {
     }
 }
 
+/// Chooses the prefix of the Conda environment to link against.
+///
+/// The `CONDA_PREFIX` of an activated environment takes precedence over the prefix detected by the
+/// interpreter script, which is only set when the interpreter's base prefix contains `conda-meta`.
+/// It is only used if it is the interpreter's `sys.prefix` or `sys.base_prefix`, as the activated
+/// environment is ignored when e.g. `PYO3_PYTHON` selects another interpreter.
+fn get_conda_prefix(
+    env_conda_prefix: Option<OsString>,
+    interpreter_prefixes: &[Option<&String>],
+    detected_conda_prefix: Option<String>,
+) -> Option<String> {
+    env_conda_prefix
+        .and_then(|prefix| prefix.into_string().ok())
+        .filter(|prefix| {
+            interpreter_prefixes
+                .iter()
+                .flatten()
+                .any(|interpreter_prefix| Path::new(interpreter_prefix) == Path::new(prefix))
+        })
+        .or(detected_conda_prefix)
+}
+
 /// Attempts to locate a python interpreter. Locations are checked in the order listed:
 /// 1. If `PYO3_PYTHON` is set, this intepreter is used.
 /// 2. If in a virtualenv, that environment's interpreter is used.
@@ -688,11 +757,14 @@ print("version_minor", sys.version_info[1])
 print("implementation", platform.python_implementation())
 print_if_set("libdir", libdir)
 print_if_set("ld_version", get_config_var("LDVERSION"))
+print_if_set("prefix", sys.prefix)
 print_if_set("base_prefix", base_prefix)
+print_if_set("conda_prefix", base_prefix if ANACONDA else None)
 print("framework", bool(get_config_var("PYTHONFRAMEWORK")))
 print("shared", PYPY or ANACONDA or bool(get_config_var("Py_ENABLE_SHARED")))
 print("executable", sys.executable)
 print("calcsize_pointer", struct.calcsize("P"))
+print("free_threaded", bool(get_config_var("Py_GIL_DISABLED")))
 "#;
     let output = run_python_script(interpreter, script)?;
     let map: HashMap<String, String> = parse_script_output(&output);
@@ -724,9 +796,15 @@ print("calcsize_pointer", struct.calcsize("P"))
         abi3: is_abi3(),
         ld_version: map.get("ld_version").cloned(),
         base_prefix: map.get("base_prefix").cloned(),
+        conda_prefix: get_conda_prefix(
+            env_var("CONDA_PREFIX"),
+            &[map.get("prefix"), map.get("base_prefix")],
+            map.get("conda_prefix").cloned(),
+        ),
         executable: map.get("executable").cloned(),
         calcsize_pointer: Some(map["calcsize_pointer"].parse()?),
         build_flags: BuildFlags::from_interpreter(interpreter)?.fixup(version, implementation),
+        free_threaded: is_free_threaded(&map, "free_threaded")?,
     })
 }
 
@@ -751,10 +829,12 @@ fn get_interpreter_config() -> Result<InterpreterConfig> {
                 libdir: None,
                 build_flags: BuildFlags::abi3(),
                 base_prefix: None,
+                conda_prefix: None,
                 calcsize_pointer: None,
                 executable: None,
                 ld_version: None,
                 shared: true,
+                free_threaded: false,
             });
         }
     }
@@ -810,23 +890,167 @@ fn write_interpreter_config(interpreter_config: &InterpreterConfig) -> Result<()
             abi3: {abi3},
             build_flags: crate::impl_::BuildFlags(build_flags),
             base_prefix: {base_prefix:?}.map(|str: &str| str.to_string()),
+            conda_prefix: {conda_prefix:?}.map(|str: &str| str.to_string()),
             calcsize_pointer: {calcsize_pointer:?},
             executable: {executable:?}.map(|str: &str| str.to_string()),
             ld_version: {ld_version:?}.map(|str: &str| str.to_string()),
-            shared: {shared:?}
+            shared: {shared:?},
+            free_threaded: {free_threaded:?}
         }}"#,
         major = interpreter_config.version.major,
         minor = interpreter_config.version.minor,
         implementation = interpreter_config.implementation,
         base_prefix = interpreter_config.base_prefix,
+        conda_prefix = interpreter_config.conda_prefix,
         calcsize_pointer = interpreter_config.calcsize_pointer,
         executable = interpreter_config.executable,
         ld_version = interpreter_config.ld_version,
         libdir = interpreter_config.libdir,
         shared = interpreter_config.shared,
         abi3 = interpreter_config.abi3,
+        free_threaded = interpreter_config.free_threaded,
     )?;
     writeln!(out, "}}")?;
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interpreter_config() -> InterpreterConfig {
+        InterpreterConfig {
+            version: PythonVersion { major: 3, minor: 9 },
+            implementation: PythonImplementation::CPython,
+            abi3: false,
+            libdir: None,
+            build_flags: BuildFlags(HashSet::new()),
+            base_prefix: None,
+            conda_prefix: None,
+            calcsize_pointer: None,
+            executable: None,
+            ld_version: None,
+            shared: true,
+            free_threaded: false,
+        }
+    }
+
+    #[test]
+    fn test_free_threaded_detection() {
+        // Output of the interpreter script
+        let output = parse_script_output("version_major 3\nfree_threaded True\n");
+        assert!(is_free_threaded(&output, "free_threaded").unwrap());
+        let output = parse_script_output("version_major 3\nfree_threaded False\n");
+        assert!(!is_free_threaded(&output, "free_threaded").unwrap());
+
+        // Output of the sysconfigdata script when cross compiling
+        let sysconfig = parse_script_output("Py_GIL_DISABLED 1\n");
+        assert!(is_free_threaded(&sysconfig, "Py_GIL_DISABLED").unwrap());
+        let sysconfig = parse_script_output("Py_GIL_DISABLED 0\n");
+        assert!(!is_free_threaded(&sysconfig, "Py_GIL_DISABLED").unwrap());
+
+        let older = parse_script_output("version_major 3\n");
+        assert!(!is_free_threaded(&older, "free_threaded").unwrap());
+        let invalid = parse_script_output("free_threaded maybe\n");
+        assert!(is_free_threaded(&invalid, "free_threaded").is_err());
+    }
+
+    #[test]
+    fn test_ensure_gil_enabled() {
+        let mut config = interpreter_config();
+        assert!(config.ensure_gil_enabled().is_ok());
+
+        config.free_threaded = true;
+        assert_eq!(
+            config.ensure_gil_enabled().unwrap_err().to_string(),
+            "the configured Python interpreter is a free-threaded build, which PyO3 does not support yet"
+        );
+    }
+
+    #[test]
+    fn test_conda_prefix_prefers_env_var() {
+        let env = "/opt/conda/envs/dev".to_string();
+        let base = "/opt/conda".to_string();
+        assert_eq!(
+            get_conda_prefix(
+                Some(OsString::from("/opt/conda/envs/dev")),
+                &[Some(&env), Some(&base)],
+                Some(base.clone())
+            ),
+            Some(env.clone())
+        );
+        assert_eq!(
+            get_conda_prefix(
+                Some(OsString::from("/opt/conda/envs/dev/")),
+                &[Some(&env), None],
+                None
+            ),
+            Some("/opt/conda/envs/dev/".to_string())
+        );
+    }
+
+    #[test]
+    fn test_conda_prefix_falls_back_to_interpreter() {
+        let base = "/opt/conda".to_string();
+        assert_eq!(
+            get_conda_prefix(None, &[Some(&base)], Some(base.clone())),
+            Some(base.clone())
+        );
+        assert_eq!(
+            get_conda_prefix(Some(OsString::new()), &[Some(&base)], Some(base.clone())),
+            Some(base.clone())
+        );
+        assert_eq!(get_conda_prefix(None, &[None, None], None), None);
+    }
+
+    #[test]
+    fn test_conda_prefix_ignores_env_var_of_other_interpreter() {
+        // e.g. `PYO3_PYTHON` or `VIRTUAL_ENV` selects an interpreter outside the active environment
+        let venv = "/home/user/.venv".to_string();
+        let system = "/usr".to_string();
+        assert_eq!(
+            get_conda_prefix(
+                Some(OsString::from("/opt/conda/envs/dev")),
+                &[Some(&venv), Some(&system)],
+                None
+            ),
+            None
+        );
+        let base = "/opt/conda".to_string();
+        assert_eq!(
+            get_conda_prefix(
+                Some(OsString::from("/opt/conda/envs/dev")),
+                &[Some(&base), Some(&base)],
+                Some(base.clone())
+            ),
+            Some(base)
+        );
+    }
+
+    #[test]
+    fn test_conda_link_search_paths() {
+        let mut config = interpreter_config();
+        assert!(config.conda_link_search_paths("linux").is_empty());
+        assert!(config.conda_link_search_paths("windows").is_empty());
+
+        config.conda_prefix = Some("/opt/conda".to_string());
+        assert_eq!(
+            config.conda_link_search_paths("linux"),
+            vec!["/opt/conda/lib".to_string()]
+        );
+        assert_eq!(
+            config.conda_link_search_paths("macos"),
+            vec!["/opt/conda/lib".to_string()]
+        );
+
+        config.conda_prefix = Some("C:\\Miniconda3".to_string());
+        assert_eq!(
+            config.conda_link_search_paths("windows"),
+            vec![
+                "C:\\Miniconda3".to_string(),
+                "C:\\Miniconda3\\Library\\bin".to_string()
+            ]
+        );
+    }
+}
@@ -11,10 +11,16 @@ use crate::deprecations::{Deprecation, Deprecations};
 pub mod kw {
     syn::custom_keyword!(annotation);
     syn::custom_keyword!(attribute);
+    syn::custom_keyword!(base);
     syn::custom_keyword!(from_py_with);
     syn::custom_keyword!(item);
+    syn::custom_keyword!(message);
+    syn::custom_keyword!(method_of);
+    syn::custom_keyword!(module);
     syn::custom_keyword!(pass_module);
+    syn::custom_keyword!(python);
     syn::custom_keyword!(name);
+    syn::custom_keyword!(no_doc);
     syn::custom_keyword!(signature);
     syn::custom_keyword!(transparent);
 }
@@ -31,6 +37,18 @@ impl Parse for FromPyWithAttribute {
     }
 }
 
+#[derive(Clone, Debug, PartialEq)]
+pub struct MethodOfAttribute(pub syn::TypePath);
+
+impl Parse for MethodOfAttribute {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let _: kw::method_of = input.parse()?;
+        let _: Token![=] = input.parse()?;
+        let string_literal: LitStr = input.parse()?;
+        string_literal.parse().map(MethodOfAttribute)
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct NameAttribute(pub Ident);
 
@@ -1,4 +1,4 @@
-use crate::attributes::{self, get_pyo3_attributes, FromPyWithAttribute};
+use crate::attributes::{self, get_pyo3_attributes, FromPyWithAttribute, NameAttribute};
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::{
@@ -169,10 +169,11 @@ impl<'a> Container<'a> {
             }
             (Fields::Unit, _) => unreachable!(), // covered by length check above
         };
-        let err_name = options.annotation.map_or_else(
-            || path.segments.last().unwrap().ident.to_string(),
-            |lit_str| lit_str.value(),
-        );
+        let err_name = match (options.annotation, options.name) {
+            (Some(lit_str), _) => lit_str.value(),
+            (None, Some(NameAttribute(name))) => name.to_string(),
+            (None, None) => path.segments.last().unwrap().ident.to_string(),
+        };
 
         let v = Container {
             path,
@@ -251,11 +252,13 @@ impl<'a> Container<'a> {
     }
 }
 
-struct ContainerOptions {
+pub(crate) struct ContainerOptions {
     /// Treat the Container as a Wrapper, directly extract its fields from the input object.
-    transparent: bool,
+    pub(crate) transparent: bool,
     /// Change the name of an enum variant in the generated error message.
-    annotation: Option<syn::LitStr>,
+    pub(crate) annotation: Option<syn::LitStr>,
+    /// Change the Python name of an enum variant.
+    pub(crate) name: Option<NameAttribute>,
 }
 
 /// Attributes for deriving FromPyObject scoped on containers.
@@ -265,6 +268,8 @@ enum ContainerPyO3Attribute {
     Transparent(attributes::kw::transparent),
     /// Change the name of an enum variant in the generated error message.
     ErrorAnnotation(LitStr),
+    /// Change the Python name of an enum variant.
+    Name(NameAttribute),
 }
 
 impl Parse for ContainerPyO3Attribute {
@@ -277,6 +282,8 @@ impl Parse for ContainerPyO3Attribute {
             let _: attributes::kw::annotation = input.parse()?;
             let _: Token![=] = input.parse()?;
             input.parse().map(ContainerPyO3Attribute::ErrorAnnotation)
+        } else if lookahead.peek(attributes::kw::name) {
+            input.parse().map(ContainerPyO3Attribute::Name)
         } else {
             Err(lookahead.error())
         }
@@ -284,10 +291,11 @@ impl Parse for ContainerPyO3Attribute {
 }
 
 impl ContainerOptions {
-    fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
+    pub(crate) fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
         let mut options = ContainerOptions {
             transparent: false,
             annotation: None,
+            name: None,
         };
         for attr in attrs {
             if let Some(pyo3_attrs) = get_pyo3_attributes(attr)? {
@@ -307,6 +315,13 @@ impl ContainerOptions {
                             );
                             options.annotation = Some(lit_str);
                         }
+                        ContainerPyO3Attribute::Name(name) => {
+                            ensure_spanned!(
+                                options.name.is_none(),
+                                name.0.span() => "`name` may only be provided once"
+                            );
+                            options.name = Some(name);
+                        }
                     }
                 }
             }
@@ -317,13 +332,13 @@ impl ContainerOptions {
 
 /// Attributes for deriving FromPyObject scoped on fields.
 #[derive(Clone, Debug)]
-struct FieldPyO3Attributes {
-    getter: FieldGetter,
-    from_py_with: Option<FromPyWithAttribute>,
+pub(crate) struct FieldPyO3Attributes {
+    pub(crate) getter: FieldGetter,
+    pub(crate) from_py_with: Option<FromPyWithAttribute>,
 }
 
 #[derive(Clone, Debug)]
-enum FieldGetter {
+pub(crate) enum FieldGetter {
     GetItem(Option<syn::Lit>),
     GetAttr(Option<LitStr>),
 }
@@ -383,7 +398,7 @@ impl Parse for FieldPyO3Attribute {
 impl FieldPyO3Attributes {
     /// Extract the field attributes.
     ///
-    fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
+    pub(crate) fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
         let mut getter = None;
         let mut from_py_with = None;
 
@@ -461,6 +476,9 @@ pub fn build_derive_from_pyobject(tokens: &DeriveInput) -> Result<TokenStream> {
             if let Some(lit_str) = &options.annotation {
                 bail_spanned!(lit_str.span() => "`annotation` is unsupported for structs");
             }
+            if let Some(NameAttribute(name)) = &options.name {
+                bail_spanned!(name.span() => "`name` is unsupported for structs");
+            }
             let ident = &tokens.ident;
             let st = Container::new(&st.fields, parse_quote!(#ident), options, false)?;
             st.build()
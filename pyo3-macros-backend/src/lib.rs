@@ -17,15 +17,19 @@ mod method;
 mod module;
 mod proto_method;
 mod pyclass;
+mod pyerror;
 mod pyfunction;
 mod pyimpl;
 mod pymethod;
 mod pyproto;
+mod to_pyobject;
 
 pub use from_pyobject::build_derive_from_pyobject;
 pub use module::{process_functions_in_module, py_init};
-pub use pyclass::{build_py_class, PyClassArgs};
+pub use pyclass::{build_py_class, build_py_enum, PyClassArgs};
+pub use pyerror::build_derive_py_error;
 pub use pyfunction::{build_py_function, PyFunctionOptions};
 pub use pyimpl::{build_py_methods, PyClassMethodsType};
 pub use pyproto::build_py_proto;
+pub use to_pyobject::build_derive_to_pyobject;
 pub use utils::get_doc;
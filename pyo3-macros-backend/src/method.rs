@@ -44,13 +44,20 @@ impl<'a> FnArg<'a> {
                     _ => bail_spanned!(cap.pat.span() => "unsupported argument"),
                 };
 
+                let py = arg_attrs.python || utils::is_python(&cap.ty);
+                if let (true, Some(from_py_with)) = (py, &arg_attrs.from_py_with) {
+                    bail_spanned!(
+                        from_py_with.0.span() => "`from_py_with` cannot be used on the `Python` argument"
+                    );
+                }
+
                 Ok(FnArg {
                     name: ident,
                     by_ref,
                     mutability,
                     ty: &cap.ty,
                     optional: utils::option_type_argument(&cap.ty),
-                    py: utils::is_python(&cap.ty),
+                    py,
                     attrs: arg_attrs,
                 })
             }
@@ -74,6 +81,12 @@ pub enum MethodTypeAttribute {
     Getter,
     /// #[setter]
     Setter,
+    /// #[get_descriptor]
+    DescriptorGet,
+    /// #[set_descriptor]
+    DescriptorSet,
+    /// #[delete_descriptor]
+    DescriptorDelete,
 }
 
 #[derive(Clone, Debug)]
@@ -84,8 +97,12 @@ pub enum FnType {
     FnCall(SelfType),
     FnNew,
     FnClass,
+    FnInitSubclass,
     FnStatic,
     ClassAttribute,
+    DescriptorGet(SelfType),
+    DescriptorSet(SelfType),
+    DescriptorDelete(SelfType),
 }
 
 #[derive(Clone, Debug)]
@@ -115,6 +132,7 @@ impl SelfType {
                 quote_spanned! { *span =>
                     let _cell = _py.from_borrowed_ptr::<pyo3::PyCell<#cls>>(_slf);
                     #[allow(clippy::useless_conversion)]  // In case _slf is PyCell<Self>
+                    #[allow(unknown_lints, clippy::unnecessary_fallible_conversions)]  // In case _slf is Py<Self>
                     let _slf = std::convert::TryFrom::try_from(_cell)?;
                 }
             }
@@ -122,6 +140,17 @@ impl SelfType {
     }
 }
 
+/// The calling convention of a generated function wrapper, i.e. how Python passes the arguments.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum CallingConvention {
+    /// `METH_NOARGS`, for functions without arguments other than `Python`.
+    NoArgs,
+    /// `METH_O`, which passes a single positional argument without allocating a tuple.
+    SingleArg,
+    /// `METH_VARARGS | METH_KEYWORDS`, for all other functions.
+    Varargs,
+}
+
 pub struct FnSpec<'a> {
     pub tp: FnType,
     // Rust function name
@@ -134,6 +163,8 @@ pub struct FnSpec<'a> {
     pub output: syn::Type,
     pub doc: syn::LitStr,
     pub deprecations: Deprecations,
+    /// Set for `async fn`s, whose futures are wrapped in a `PyCoroutine`
+    pub asyncness: Option<syn::Token![async]>,
 }
 
 pub fn get_return_info(output: &syn::ReturnType) -> syn::Type {
@@ -183,6 +214,19 @@ impl<'a> FnSpec<'a> {
                 }
                 python_name = Some(syn::Ident::new("__call__", proc_macro2::Span::call_site()))
             }
+            Some(MethodTypeAttribute::DescriptorGet)
+            | Some(MethodTypeAttribute::DescriptorSet)
+            | Some(MethodTypeAttribute::DescriptorDelete) => {
+                if let Some(name) = &python_name {
+                    bail_spanned!(name.span() => "`name` not allowed with descriptor methods");
+                }
+                let dunder = match fn_type_attr {
+                    Some(MethodTypeAttribute::DescriptorGet) => "__get__",
+                    Some(MethodTypeAttribute::DescriptorSet) => "__set__",
+                    _ => "__delete__",
+                };
+                python_name = Some(syn::Ident::new(dunder, proc_macro2::Span::call_site()))
+            }
             _ => {}
         }
 
@@ -217,6 +261,7 @@ impl<'a> FnSpec<'a> {
             output: ty,
             doc,
             deprecations: options.deprecations,
+            asyncness: sig.asyncness,
         })
     }
 
@@ -242,14 +287,20 @@ impl<'a> FnSpec<'a> {
         };
 
         let text_signature = match &fn_type {
-            FnType::Fn(_) | FnType::FnClass | FnType::FnStatic => {
+            FnType::Fn(_) | FnType::FnClass | FnType::FnInitSubclass | FnType::FnStatic => {
                 utils::parse_text_signature_attrs(&mut *meth_attrs, &python_name)?
             }
             FnType::FnNew => parse_erroneous_text_signature(
                 "text_signature not allowed on __new__; if you want to add a signature on \
                  __new__, put it on the struct definition instead",
             )?,
-            FnType::FnCall(_) | FnType::Getter(_) | FnType::Setter(_) | FnType::ClassAttribute => {
+            FnType::FnCall(_)
+            | FnType::Getter(_)
+            | FnType::Setter(_)
+            | FnType::ClassAttribute
+            | FnType::DescriptorGet(_)
+            | FnType::DescriptorSet(_)
+            | FnType::DescriptorDelete(_) => {
                 parse_erroneous_text_signature("text_signature not allowed with this method type")?
             }
         };
@@ -319,6 +370,24 @@ impl<'a> FnSpec<'a> {
                     true,
                 )
             }
+            Some(MethodTypeAttribute::DescriptorGet) => (
+                FnType::DescriptorGet(parse_receiver("expected receiver for #[get_descriptor]")?),
+                true,
+            ),
+            Some(MethodTypeAttribute::DescriptorSet) => (
+                FnType::DescriptorSet(parse_receiver("expected receiver for #[set_descriptor]")?),
+                true,
+            ),
+            Some(MethodTypeAttribute::DescriptorDelete) => (
+                FnType::DescriptorDelete(parse_receiver(
+                    "expected receiver for #[delete_descriptor]",
+                )?),
+                true,
+            ),
+            // `__init_subclass__` is implicitly a class method, as in Python
+            None if python_name.as_ref().unwrap_or(name).unraw() == "__init_subclass__" => {
+                (FnType::FnInitSubclass, true)
+            }
             None => (
                 FnType::Fn(parse_receiver(
                     "static method needs #[staticmethod] attribute",
@@ -364,6 +433,37 @@ impl<'a> FnSpec<'a> {
         None
     }
 
+    /// Selects the fastest calling convention which doesn't change how the function can be called.
+    ///
+    /// `METH_O` doesn't accept keyword arguments, so it's only used when the single argument is
+    /// required and positional-only.
+    pub fn calling_convention(&self) -> CallingConvention {
+        let mut args = self.args.iter().filter(|arg| !arg.py);
+        match (args.next(), args.next()) {
+            (None, _) => CallingConvention::NoArgs,
+            (Some(arg), None)
+                if self.is_pos_only(arg.name)
+                    && arg.optional.is_none()
+                    && self.default_value(arg.name).is_none() =>
+            {
+                CallingConvention::SingleArg
+            }
+            _ => CallingConvention::Varargs,
+        }
+    }
+
+    pub fn is_pos_only(&self, name: &syn::Ident) -> bool {
+        let mut found = false;
+        for s in self.attrs.iter() {
+            match s {
+                Argument::PosOnlyArgsSeparator => return found,
+                Argument::Arg(path, _) if path.is_ident(name) => found = true,
+                _ => (),
+            }
+        }
+        false
+    }
+
     pub fn is_kw_only(&self, name: &syn::Ident) -> bool {
         for s in self.attrs.iter() {
             if let Argument::Kwarg(path, _) = s {
@@ -415,6 +515,12 @@ fn parse_method_attributes(
                     set_ty!(MethodTypeAttribute::StaticMethod, name);
                 } else if name.is_ident("classattr") {
                     set_ty!(MethodTypeAttribute::ClassAttribute, name);
+                } else if name.is_ident("get_descriptor") {
+                    set_ty!(MethodTypeAttribute::DescriptorGet, name);
+                } else if name.is_ident("set_descriptor") {
+                    set_ty!(MethodTypeAttribute::DescriptorSet, name);
+                } else if name.is_ident("delete_descriptor") {
+                    set_ty!(MethodTypeAttribute::DescriptorDelete, name);
                 } else if name.is_ident("setter") || name.is_ident("getter") {
                     if let syn::AttrStyle::Inner(_) = attr.style {
                         bail_spanned!(
@@ -31,9 +31,10 @@ pub fn py_init(fnname: &Ident, name: &Ident, doc: syn::LitStr) -> TokenStream {
     }
 }
 
-/// Finds and takes care of the #[pyfn(...)] in `#[pymodule]`
+/// Finds and takes care of the #[pyfn(...)] and #[pymodule_init] in `#[pymodule]`
 pub fn process_functions_in_module(func: &mut syn::ItemFn) -> syn::Result<()> {
     let mut stmts: Vec<syn::Stmt> = Vec::new();
+    let mut module_init: Option<syn::ItemFn> = None;
 
     for stmt in func.block.stmts.iter_mut() {
         if let syn::Stmt::Item(syn::Item::Fn(func)) = stmt {
@@ -48,11 +49,42 @@ pub fn process_functions_in_module(func: &mut syn::ItemFn) -> syn::Result<()> {
                 };
                 stmts.extend(item.block.stmts.into_iter());
             }
+            if take_pymodule_init_attr(&mut func.attrs)? {
+                ensure_spanned!(
+                    module_init.is_none(),
+                    func.sig.ident.span() => "only one `#[pymodule_init]` function is allowed"
+                );
+                module_init = Some(func.clone());
+                continue;
+            }
         };
         stmts.push(stmt.clone());
     }
 
     func.block.stmts = stmts;
+
+    if let Some(module_init) = module_init {
+        // Run the module body first, then the init function, so that it sees everything which
+        // was added to the module
+        let module_arg = match func.sig.inputs.iter().nth(1) {
+            Some(syn::FnArg::Typed(syn::PatType { pat, .. })) => match &**pat {
+                syn::Pat::Ident(pat) => pat.ident.clone(),
+                _ => bail_spanned!(pat.span() => "expected an identifier for the module argument"),
+            },
+            _ => bail_spanned!(
+                func.sig.inputs.span() => "expected a `&PyModule` as the second argument"
+            ),
+        };
+        let init_ident = &module_init.sig.ident;
+        let body = &func.block;
+        *func.block = syn::parse_quote! {{
+            #module_init
+            let result: pyo3::PyResult<()> = (|| -> pyo3::PyResult<()> #body)();
+            result?;
+            #init_ident(#module_arg)
+        }};
+    }
+
     Ok(())
 }
 
@@ -96,6 +128,24 @@ impl Parse for PyFnArgs {
     }
 }
 
+/// Removes the #[pymodule_init] attribute of a function, returning whether it was present
+fn take_pymodule_init_attr(attrs: &mut Vec<syn::Attribute>) -> syn::Result<bool> {
+    let mut found = false;
+    take_attributes(attrs, |attr| {
+        if is_attribute_ident(attr, "pymodule_init") {
+            ensure_spanned!(
+                !found,
+                attr.span() => "`#[pymodule_init]` may only be specified once"
+            );
+            found = true;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    })?;
+    Ok(found)
+}
+
 /// Extracts the data from the #[pyfn(...)] attribute of a function
 fn get_pyfn_attr(attrs: &mut Vec<syn::Attribute>) -> syn::Result<Option<PyFnArgs>> {
     let mut pyfn_args: Option<PyFnArgs> = None;
@@ -1,17 +1,17 @@
 // Copyright (c) 2017-present PyO3 Project and Contributors
 
 use crate::method::{FnType, SelfType};
-use crate::pyimpl::PyClassMethodsType;
+use crate::pyimpl::{build_py_methods, PyClassMethodsType};
 use crate::pymethod::{impl_py_getter_def, impl_py_setter_def, PropertyType};
 use crate::utils;
 use proc_macro2::{Span, TokenStream};
-use quote::quote;
+use quote::{quote, quote_spanned};
 use syn::ext::IdentExt;
 use syn::parse::{Parse, ParseStream};
-use syn::punctuated::Punctuated;
 use syn::{parse_quote, spanned::Spanned, Expr, Token};
 
 /// The parsed arguments of the pyclass macro
+#[derive(Clone)]
 pub struct PyClassArgs {
     pub freelist: Option<syn::Expr>,
     pub name: Option<syn::Ident>,
@@ -20,18 +20,38 @@ pub struct PyClassArgs {
     pub has_weaklist: bool,
     pub is_gc: bool,
     pub is_basetype: bool,
+    pub is_abstract: bool,
     pub has_extends: bool,
     pub has_unsendable: bool,
+    pub has_unsafe_lifetime: bool,
+    pub has_buffer: bool,
+    pub has_context_manager: bool,
+    pub has_finalizer: bool,
+    pub has_pickle: bool,
+    pub no_doc: bool,
     pub module: Option<syn::LitStr>,
+    /// The span of the `str` flag, if given, which generates `__repr__` from `Debug` or `Display`.
+    pub repr_from_format: Option<Span>,
+    /// The span of the `display` flag, if given, which generates `__str__` from `Display`.
+    pub str_from_display: Option<Span>,
 }
 
 impl Parse for PyClassArgs {
     fn parse(input: ParseStream) -> syn::parse::Result<Self> {
         let mut slf = PyClassArgs::default();
 
-        let vars = Punctuated::<Expr, Token![,]>::parse_terminated(input)?;
-        for expr in vars {
-            slf.add_expr(&expr)?;
+        while !input.is_empty() {
+            // `abstract` is a reserved keyword, so it can't be parsed as an expression
+            if input.peek(Token![abstract]) {
+                let _: Token![abstract] = input.parse()?;
+                slf.is_abstract = true;
+            } else {
+                let expr: Expr = input.parse()?;
+                slf.add_expr(&expr)?;
+            }
+            if !input.is_empty() {
+                let _: Token![,] = input.parse()?;
+            }
         }
         Ok(slf)
     }
@@ -48,8 +68,17 @@ impl Default for PyClassArgs {
             has_weaklist: false,
             is_gc: false,
             is_basetype: false,
+            is_abstract: false,
             has_extends: false,
             has_unsendable: false,
+            has_unsafe_lifetime: false,
+            has_buffer: false,
+            has_context_manager: false,
+            has_finalizer: false,
+            has_pickle: false,
+            no_doc: false,
+            repr_from_format: None,
+            str_from_display: None,
         }
     }
 }
@@ -154,8 +183,37 @@ impl PyClassArgs {
             "unsendable" => {
                 self.has_unsendable = true;
             }
+            "unsafe_lifetime" => {
+                self.has_unsafe_lifetime = true;
+            }
+            "buffer" => {
+                self.has_buffer = true;
+            }
+            "context_manager" => {
+                self.has_context_manager = true;
+            }
+            "finalizer" => {
+                if cfg!(Py_LIMITED_API) {
+                    bail_spanned!(
+                        exp.span() => "#[pyclass(finalizer)] is not supported with the limited API (`abi3`)"
+                    );
+                }
+                self.has_finalizer = true;
+            }
+            "pickle" => {
+                self.has_pickle = true;
+            }
+            "no_doc" => {
+                self.no_doc = true;
+            }
+            "str" => {
+                self.repr_from_format = Some(exp.span());
+            }
+            "display" => {
+                self.str_from_display = Some(exp.span());
+            }
             _ => bail_spanned!(
-                exp.path.span() => "expected one of gc/weakref/subclass/dict/unsendable"
+                exp.path.span() => "expected one of gc/weakref/subclass/abstract/dict/unsendable/unsafe_lifetime/buffer/context_manager/finalizer/pickle/no_doc/str/display"
             ),
         };
         Ok(())
@@ -171,40 +229,562 @@ pub fn build_py_class(
         &mut class.attrs,
         &get_class_python_name(&class.ident, attr),
     )?;
-    let doc = utils::get_doc(&class.attrs, text_signature, true)?;
+    let doc = if attr.no_doc {
+        if let Some(text_signature) = text_signature {
+            bail_spanned!(text_signature.span() => "text_signature cannot be used with `no_doc`");
+        }
+        syn::LitStr::new("\0", class.ident.span())
+    } else {
+        utils::get_doc(&class.attrs, text_signature, true)?
+    };
     let mut descriptors = Vec::new();
 
+    let cls: syn::Type = if attr.has_unsafe_lifetime {
+        ensure_spanned!(
+            class.generics.params.len() == 1
+                && class.generics.lifetimes().count() == 1
+                && class.generics.where_clause.is_none(),
+            class.generics.span() =>
+                "#[pyclass(unsafe_lifetime)] requires exactly one lifetime parameter"
+        );
+        let ident = &class.ident;
+        parse_quote! { #ident<'static> }
+    } else {
+        ensure_spanned!(
+            class.generics.params.is_empty(),
+            class.generics.span() => "#[pyclass] cannot have generic parameters"
+        );
+        let ident = &class.ident;
+        parse_quote! { #ident }
+    };
+
+    for (index, field) in class.fields.iter_mut().enumerate() {
+        let field_descs = parse_descriptors(field)?;
+        if !field_descs.fns.is_empty() {
+            descriptors.push(FieldDescriptors::new(field, index, field_descs));
+        }
+    }
+
+    let descriptors = if descriptors.is_empty() {
+        quote! {}
+    } else {
+        impl_descriptors(&cls, descriptors)?
+    };
+    let into_pyobject = impl_into_pyobject(&cls, attr);
+    let pickle = if attr.has_pickle {
+        ensure_spanned!(
+            !attr.has_extends,
+            attr.base.span() => "#[pyclass(pickle)] cannot be used with `extends`"
+        );
+        impl_pickle(&class.ident, &cls, attr, &class.fields)
+    } else {
+        quote! {}
+    };
+
+    let class_impl = impl_class(
+        &class.ident,
+        &cls,
+        &attr,
+        doc,
+        descriptors,
+        into_pyobject,
+        methods_type,
+    )?;
+    Ok(quote! {
+        #class_impl
+        #pickle
+    })
+}
+
+/// Implements `PyPickle` for the `pickle` flag, storing each field under its name, and exposes
+/// it to Python.
+fn impl_pickle(
+    cls_ident: &syn::Ident,
+    cls: &syn::Type,
+    attr: &PyClassArgs,
+    fields: &syn::Fields,
+) -> TokenStream {
+    let cls_name = get_class_python_name(cls_ident, attr).to_string();
+    let members: Vec<syn::Member> = fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| match &field.ident {
+            Some(ident) => syn::Member::Named(ident.clone()),
+            None => syn::Member::Unnamed(index.into()),
+        })
+        .collect();
+    let keys: Vec<String> = members
+        .iter()
+        .map(|member| match member {
+            syn::Member::Named(ident) => ident.unraw().to_string(),
+            syn::Member::Unnamed(index) => index.index.to_string(),
+        })
+        .collect();
+    // Check the field types with their own spans, so that errors point at the offending field
+    let assertions = fields.iter().map(|field| {
+        let ty = &field.ty;
+        quote_spanned! { ty.span() =>
+            pyo3::class::pickle::assert_picklable_field::<#ty>();
+        }
+    });
+    let assertion_name = format!("__assertion_closure_pickle_{}", cls_ident);
+    let assertion_name = syn::Ident::new(&assertion_name, Span::call_site());
+
+    quote! {
+        fn #assertion_name() {
+            #(#assertions)*
+        }
+
+        impl pyo3::class::pickle::PyPickle for #cls {
+            fn __getstate__<'p>(
+                &self,
+                py: pyo3::Python<'p>,
+            ) -> pyo3::PyResult<&'p pyo3::types::PyDict> {
+                let state = pyo3::types::PyDict::new(py);
+                #(
+                    state.set_item(#keys, pyo3::ToPyObject::to_object(&self.#members, py))?;
+                )*
+                Ok(state)
+            }
+
+            fn from_state(state: &pyo3::types::PyDict) -> pyo3::PyResult<Self> {
+                Ok(#cls_ident {
+                    #(
+                        #members: pyo3::class::pickle::extract_field(state, #cls_name, #keys)?,
+                    )*
+                })
+            }
+        }
+
+        impl pyo3::class::impl_::PyClassPickleMethods<#cls>
+            for pyo3::class::impl_::PyClassImplCollector<#cls>
+        {
+            fn pickle_methods(self) -> &'static [pyo3::class::PyMethodDefType] {
+                static METHODS: &[pyo3::class::PyMethodDefType] = &[
+                    pyo3::class::PyMethodDefType::Method(pyo3::class::PyMethodDef::noargs(
+                        "__getstate__\0",
+                        pyo3::class::methods::PyCFunction(pyo3::class::pickle::getstate::<#cls>),
+                        "\0",
+                    )),
+                    pyo3::class::PyMethodDefType::Method(pyo3::class::PyMethodDef::onearg(
+                        "__setstate__\0",
+                        pyo3::class::methods::PyCFunction(pyo3::class::pickle::setstate::<#cls>),
+                        "\0",
+                    )),
+                    pyo3::class::PyMethodDefType::Method(pyo3::class::PyMethodDef::onearg(
+                        "__reduce_ex__\0",
+                        pyo3::class::methods::PyCFunction(pyo3::class::pickle::reduce_ex::<#cls>),
+                        "\0",
+                    )),
+                    pyo3::class::PyMethodDefType::Class(
+                        pyo3::class::PyMethodDef::onearg(
+                            "_pyo3_unpickle\0",
+                            pyo3::class::methods::PyCFunction(
+                                pyo3::class::pickle::unpickle::<#cls>
+                            ),
+                            "\0",
+                        )
+                        .flags(pyo3::ffi::METH_CLASS)
+                    ),
+                ];
+                METHODS
+            }
+        }
+    }
+}
+
+/// Builds a `#[pyclass]` for an enum.
+///
+/// The enum itself becomes a base class, and each variant becomes a subclass of it which is
+/// available as a class attribute of the base class, e.g. `Shape.Circle`. The Rust types of the
+/// variant classes are declared in an anonymous `const` block, so they can't be named elsewhere.
+pub fn build_py_enum(
+    enum_: &mut syn::ItemEnum,
+    attr: &PyClassArgs,
+    methods_type: PyClassMethodsType,
+) -> syn::Result<TokenStream> {
+    ensure_spanned!(
+        enum_.generics.params.is_empty(),
+        enum_.generics.span() => "#[pyclass] cannot have generic parameters"
+    );
+    ensure_spanned!(!attr.has_extends, attr.base.span() => "enums cannot extend other classes");
     ensure_spanned!(
-        class.generics.params.is_empty(),
-        class.generics.span() => "#[pyclass] cannot have generic parameters"
+        !attr.has_unsafe_lifetime,
+        enum_.ident.span() => "#[pyclass(unsafe_lifetime)] cannot be used on enums"
     );
+    ensure_spanned!(
+        !attr.has_pickle,
+        enum_.ident.span() => "#[pyclass(pickle)] cannot be used on enums"
+    );
+    ensure_spanned!(
+        !enum_.variants.is_empty(),
+        enum_.ident.span() => "#[pyclass] cannot be used on empty enums"
+    );
+
+    let text_signature = utils::parse_text_signature_attrs(
+        &mut enum_.attrs,
+        &get_class_python_name(&enum_.ident, attr),
+    )?;
+    let doc = if attr.no_doc {
+        if let Some(text_signature) = text_signature {
+            bail_spanned!(text_signature.span() => "text_signature cannot be used with `no_doc`");
+        }
+        syn::LitStr::new("\0", enum_.ident.span())
+    } else {
+        utils::get_doc(&enum_.attrs, text_signature, true)?
+    };
+
+    let ident = &enum_.ident;
+    let cls: syn::Type = parse_quote! { #ident };
+    let base_attr = PyClassArgs {
+        is_basetype: true,
+        ..attr.clone()
+    };
+
+    let variants = enum_
+        .variants
+        .iter()
+        .map(|variant| EnumVariant::new(ident, &enum_.vis, variant))
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let descriptors = impl_enum_descriptors(&cls, &variants);
+    let into_pyobject = impl_enum_into_pyobject(&cls, &variants);
+    let to_pyobject = impl_enum_to_pyobject(&cls, &variants);
+    // The impls which name the variant classes are declared alongside them
+    let base_class = impl_class(
+        ident,
+        &cls,
+        &base_attr,
+        doc,
+        quote! {},
+        quote! {},
+        methods_type,
+    )?;
+
+    let variant_classes = variants
+        .iter()
+        .map(|variant| variant.impl_class(attr, methods_type))
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    Ok(quote! {
+        #base_class
+
+        const _: () = {
+            #descriptors
+            #into_pyobject
+            #to_pyobject
+            #(#variant_classes)*
+        };
+    })
+}
+
+/// A variant of a `#[pyclass]` enum, which is exposed to Python as a subclass of the enum.
+struct EnumVariant<'a> {
+    enum_ident: &'a syn::Ident,
+    vis: &'a syn::Visibility,
+    variant: &'a syn::Variant,
+    /// The Rust type of the subclass, e.g. `ShapeCircle` for `Shape::Circle`
+    class_ident: syn::Ident,
+    /// The field names used as Python attributes; tuple fields are named `_0`, `_1`, ...
+    field_idents: Vec<syn::Ident>,
+}
+
+impl<'a> EnumVariant<'a> {
+    fn new(
+        enum_ident: &'a syn::Ident,
+        vis: &'a syn::Visibility,
+        variant: &'a syn::Variant,
+    ) -> syn::Result<Self> {
+        let class_ident = syn::Ident::new(
+            &format!("{}{}", enum_ident.unraw(), variant.ident.unraw()),
+            variant.ident.span(),
+        );
+        let field_idents = variant
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(i, field)| match &field.ident {
+                Some(ident) => ident.clone(),
+                None => syn::Ident::new(&format!("_{}", i), field.span()),
+            })
+            .collect();
+        Ok(EnumVariant {
+            enum_ident,
+            vis,
+            variant,
+            class_ident,
+            field_idents,
+        })
+    }
+
+    /// The variant pattern, binding all fields to their `field_idents`
+    fn pattern(&self) -> TokenStream {
+        let enum_ident = self.enum_ident;
+        let variant_ident = &self.variant.ident;
+        let field_idents = &self.field_idents;
+        match &self.variant.fields {
+            syn::Fields::Named(_) => quote! { #enum_ident::#variant_ident { #(#field_idents),* } },
+            syn::Fields::Unnamed(_) => {
+                quote! { #enum_ident::#variant_ident ( #(#field_idents),* ) }
+            }
+            syn::Fields::Unit => quote! { #enum_ident::#variant_ident },
+        }
+    }
+
+    /// The variant, constructed from clones of the fields bound by `pattern()`
+    fn clone_from_pattern(&self) -> TokenStream {
+        let enum_ident = self.enum_ident;
+        let variant_ident = &self.variant.ident;
+        let field_idents = &self.field_idents;
+        let clones = field_idents
+            .iter()
+            .map(|ident| quote! { std::clone::Clone::clone(#ident) });
+        match &self.variant.fields {
+            syn::Fields::Named(_) => {
+                quote! { #enum_ident::#variant_ident { #(#field_idents: #clones),* } }
+            }
+            syn::Fields::Unnamed(_) => quote! { #enum_ident::#variant_ident ( #(#clones),* ) },
+            syn::Fields::Unit => quote! { #enum_ident::#variant_ident },
+        }
+    }
+
+    /// The variant pattern, binding only the field at `index`
+    fn field_pattern(&self, index: usize) -> TokenStream {
+        let enum_ident = self.enum_ident;
+        let variant_ident = &self.variant.ident;
+        let field_ident = &self.field_idents[index];
+        match &self.variant.fields {
+            syn::Fields::Named(_) => quote! { #enum_ident::#variant_ident { #field_ident, .. } },
+            _ => {
+                let fields = self.field_idents.iter().enumerate().map(|(i, ident)| {
+                    if i == index {
+                        quote! { #ident }
+                    } else {
+                        quote! { _ }
+                    }
+                });
+                quote! { #enum_ident::#variant_ident ( #(#fields),* ) }
+            }
+        }
+    }
+
+    /// The variant pattern, ignoring all fields
+    fn wildcard_pattern(&self) -> TokenStream {
+        let enum_ident = self.enum_ident;
+        let variant_ident = &self.variant.ident;
+        match &self.variant.fields {
+            syn::Fields::Named(_) => quote! { #enum_ident::#variant_ident { .. } },
+            syn::Fields::Unnamed(_) => quote! { #enum_ident::#variant_ident(..) },
+            syn::Fields::Unit => quote! { #enum_ident::#variant_ident },
+        }
+    }
+
+    fn python_name(&self) -> String {
+        self.variant.ident.unraw().to_string()
+    }
+
+    /// Generates the subclass for this variant, with a constructor taking the variant's fields
+    /// and a getter for each field.
+    fn impl_class(
+        &self,
+        attr: &PyClassArgs,
+        methods_type: PyClassMethodsType,
+    ) -> syn::Result<TokenStream> {
+        let enum_ident = self.enum_ident;
+        let vis = self.vis;
+        let class_ident = &self.class_ident;
+        let cls: syn::Type = parse_quote! { #class_ident };
+        let variant_attr = PyClassArgs {
+            name: Some(self.variant.ident.clone()),
+            module: attr.module.clone(),
+            base: parse_quote! { #enum_ident },
+            has_extends: true,
+            has_unsendable: attr.has_unsendable,
+            ..Default::default()
+        };
+
+        let doc = utils::get_doc(&self.variant.attrs, None, true)?;
+        let doc_comment = format!(
+            "The `{}` variant of [`{}`] as a Python class.",
+            self.variant.ident, enum_ident
+        );
+        let class_item: syn::ItemStruct = parse_quote! {
+            #[doc = #doc_comment]
+            #vis struct #class_ident;
+        };
+        let class = impl_class(
+            class_ident,
+            &cls,
+            &variant_attr,
+            doc,
+            quote! {},
+            quote! {},
+            methods_type,
+        )?;
+
+        let pattern = self.pattern();
+        let field_idents = &self.field_idents;
+        let field_types = self.variant.fields.iter().map(|field| &field.ty);
+        let getters = self
+            .variant
+            .fields
+            .iter()
+            .zip(field_idents)
+            .enumerate()
+            .map(|(index, (field, field_ident))| {
+                let ty = &field.ty;
+                let pattern = self.field_pattern(index);
+                let python_name = field_ident.unraw().to_string();
+                // A method taking `&mut self` may have replaced the enum with another variant
+                let changed = format!(
+                    "'{}' object has no attribute '{}', as it is no longer a `{}` variant",
+                    enum_ident.unraw(),
+                    python_name,
+                    self.python_name()
+                );
+                quote! {
+                    #[getter]
+                    fn #field_ident(slf: pyo3::PyRef<Self>) -> pyo3::PyResult<#ty> {
+                        match std::convert::AsRef::<#enum_ident>::as_ref(&slf) {
+                            #pattern => Ok(std::clone::Clone::clone(#field_ident)),
+                            #[allow(unreachable_patterns)]
+                            _ => Err(pyo3::exceptions::PyAttributeError::new_err(#changed)),
+                        }
+                    }
+                }
+            });
+        let mut methods_item: syn::ItemImpl = parse_quote! {
+            impl #class_ident {
+                #[new]
+                fn __pyo3_new(#(#field_idents: #field_types),*) -> pyo3::PyClassInitializer<Self> {
+                    pyo3::PyClassInitializer::from(#pattern).add_subclass(#class_ident)
+                }
+
+                #(#getters)*
+            }
+        };
+        let methods = build_py_methods(&mut methods_item, methods_type)?;
+
+        Ok(quote! {
+            #class_item
+            #class
+            #methods_item
+            #methods
+        })
+    }
+}
+
+/// Generates the `variant` getter of the enum, and a class attribute for each variant's class.
+fn impl_enum_descriptors(cls: &syn::Type, variants: &[EnumVariant]) -> TokenStream {
+    let slf = SelfType::Receiver { mutable: false }.receiver(cls);
+    let variant_names = variants.iter().map(|variant| {
+        let pattern = variant.wildcard_pattern();
+        let name = variant.python_name();
+        quote! { #pattern => #name }
+    });
+    let class_attributes = variants.iter().map(|variant| {
+        let python_name = format!("{}\0", variant.python_name());
+        let class_ident = &variant.class_ident;
+        quote! {
+            pyo3::class::PyMethodDefType::ClassAttribute({
+                fn __wrap(py: pyo3::Python<'_>) -> pyo3::PyObject {
+                    pyo3::IntoPy::into_py(
+                        <#class_ident as pyo3::type_object::PyTypeObject>::type_object(py),
+                        py,
+                    )
+                }
+                pyo3::class::PyClassAttributeDef::new(
+                    #python_name,
+                    pyo3::class::methods::PyClassAttributeFactory(__wrap)
+                )
+            })
+        }
+    });
 
-    match &mut class.fields {
-        syn::Fields::Named(fields) => {
-            for field in fields.named.iter_mut() {
-                let field_descs = parse_descriptors(field)?;
-                if !field_descs.is_empty() {
-                    descriptors.push((field.clone(), field_descs));
+    quote! {
+        impl pyo3::class::impl_::PyClassDescriptors<#cls>
+            for pyo3::class::impl_::PyClassImplCollector<#cls>
+        {
+            fn py_class_descriptors(self) -> &'static [pyo3::class::methods::PyMethodDefType] {
+                unsafe extern "C" fn __wrap(
+                    _slf: *mut pyo3::ffi::PyObject, _: *mut std::os::raw::c_void) -> *mut pyo3::ffi::PyObject
+                {
+                    pyo3::callback::handle_panic(|_py| {
+                        #slf
+                        let variant: &'static str = match &**_slf {
+                            #(#variant_names),*
+                        };
+                        pyo3::callback::convert(_py, variant)
+                    })
                 }
+                static METHODS: &[pyo3::class::methods::PyMethodDefType] = &[
+                    pyo3::class::PyMethodDefType::Getter(pyo3::class::PyGetterDef::new(
+                        "variant\0",
+                        pyo3::class::methods::PyGetter(__wrap),
+                        "The name of the enum variant.\0"
+                    )),
+                    #(#class_attributes),*
+                ];
+                METHODS
             }
         }
-        syn::Fields::Unnamed(fields) => {
-            for field in fields.unnamed.iter_mut() {
-                let field_descs = parse_descriptors(field)?;
-                if !field_descs.is_empty() {
-                    descriptors.push((field.clone(), field_descs));
+    }
+}
+
+/// Converts the enum into an instance of the class of its variant.
+fn impl_enum_into_pyobject(cls: &syn::Type, variants: &[EnumVariant]) -> TokenStream {
+    let arms = variants.iter().map(|variant| {
+        let pattern = variant.wildcard_pattern();
+        let class_ident = &variant.class_ident;
+        quote! {
+            #pattern => {
+                let initializer = pyo3::PyClassInitializer::from(self).add_subclass(#class_ident);
+                pyo3::IntoPy::into_py(pyo3::Py::new(py, initializer).unwrap(), py)
+            }
+        }
+    });
+    quote! {
+        impl pyo3::IntoPy<pyo3::PyObject> for #cls {
+            fn into_py(self, py: pyo3::Python) -> pyo3::PyObject {
+                match self {
+                    #(#arms)*
                 }
             }
         }
-        syn::Fields::Unit => { /* No fields for unit struct */ }
     }
+}
+
+/// Converts a reference to the enum into an instance of the class of its variant.
+///
+/// The enum needn't implement `Clone`, as the variant is rebuilt from clones of its fields.
+fn impl_enum_to_pyobject(cls: &syn::Type, variants: &[EnumVariant]) -> TokenStream {
+    let arms = variants.iter().map(|variant| {
+        let pattern = variant.pattern();
+        let value = variant.clone_from_pattern();
+        quote! { #pattern => #value }
+    });
+    quote! {
+        impl pyo3::ToPyObject for #cls {
+            fn to_object(&self, py: pyo3::Python) -> pyo3::PyObject {
+                let value = match self {
+                    #(#arms),*
+                };
+                pyo3::IntoPy::into_py(value, py)
+            }
+        }
+    }
+}
 
-    impl_class(&class.ident, &attr, doc, descriptors, methods_type)
+/// The options given by `#[pyo3(get, set, name = "...")]` on a field.
+struct FieldPyO3Options {
+    fns: Vec<FnType>,
+    name: Option<syn::Ident>,
 }
 
-/// Parses `#[pyo3(get, set)]`
-fn parse_descriptors(item: &mut syn::Field) -> syn::Result<Vec<FnType>> {
+/// Parses `#[pyo3(get, set, name = "...")]`
+fn parse_descriptors(item: &mut syn::Field) -> syn::Result<FieldPyO3Options> {
     let mut descs = Vec::new();
+    let mut name: Option<syn::Ident> = None;
     let mut new_attrs = Vec::new();
     for attr in item.attrs.drain(..) {
         if let Ok(syn::Meta::List(list)) = attr.parse_meta() {
@@ -215,8 +795,25 @@ fn parse_descriptors(item: &mut syn::Field) -> syn::Result<Vec<FnType>> {
                             descs.push(FnType::Getter(SelfType::Receiver { mutable: false }));
                         } else if metaitem.path().is_ident("set") {
                             descs.push(FnType::Setter(SelfType::Receiver { mutable: true }));
+                        } else if let syn::Meta::NameValue(syn::MetaNameValue {
+                            path,
+                            lit: syn::Lit::Str(lit),
+                            ..
+                        }) = metaitem
+                        {
+                            ensure_spanned!(
+                                path.is_ident("name"),
+                                path.span() => "only get, set and name are supported"
+                            );
+                            ensure_spanned!(
+                                name.is_none(),
+                                path.span() => "`name` may only be specified once"
+                            );
+                            name = Some(lit.parse().map_err(|_| {
+                                err_spanned!(lit.span() => "expected a single identifier in double-quotes")
+                            })?);
                         } else {
-                            bail_spanned!(metaitem.span() => "only get and set are supported");
+                            bail_spanned!(metaitem.span() => "only get, set and name are supported");
                         }
                     }
                 }
@@ -227,14 +824,49 @@ fn parse_descriptors(item: &mut syn::Field) -> syn::Result<Vec<FnType>> {
             new_attrs.push(attr);
         }
     }
+    if let (Some(name), true) = (&name, descs.is_empty()) {
+        bail_spanned!(name.span() => "`name` can only be used together with `get` or `set`");
+    }
     item.attrs = new_attrs;
-    Ok(descs)
+    Ok(FieldPyO3Options { fns: descs, name })
+}
+
+/// A field with `#[pyo3(get)]` or `#[pyo3(set)]`, along with the Python name of the property.
+///
+/// Tuple struct fields are named `_0`, `_1`, etc. unless a name is given.
+struct FieldDescriptors {
+    field: syn::Field,
+    member: syn::Member,
+    python_name: syn::Ident,
+    fns: Vec<FnType>,
+}
+
+impl FieldDescriptors {
+    fn new(field: &syn::Field, index: usize, options: FieldPyO3Options) -> Self {
+        let member = match &field.ident {
+            Some(ident) => syn::Member::Named(ident.clone()),
+            None => syn::Member::Unnamed(syn::Index {
+                index: index as u32,
+                span: field.ty.span(),
+            }),
+        };
+        let python_name = options.name.unwrap_or_else(|| match &field.ident {
+            Some(ident) => ident.unraw(),
+            None => syn::Ident::new(&format!("_{}", index), field.ty.span()),
+        });
+        FieldDescriptors {
+            field: field.clone(),
+            member,
+            python_name,
+            fns: options.fns,
+        }
+    }
 }
 
 /// To allow multiple #[pymethods] block, we define inventory types.
-fn impl_methods_inventory(cls: &syn::Ident) -> TokenStream {
+fn impl_methods_inventory(cls_ident: &syn::Ident, cls: &syn::Type) -> TokenStream {
     // Try to build a unique type for better error messages
-    let name = format!("Pyo3MethodsInventoryFor{}", cls.unraw());
+    let name = format!("Pyo3MethodsInventoryFor{}", cls_ident.unraw());
     let inventory_cls = syn::Ident::new(&name, Span::call_site());
 
     quote! {
@@ -264,13 +896,15 @@ fn get_class_python_name<'a>(cls: &'a syn::Ident, attr: &'a PyClassArgs) -> &'a
 }
 
 fn impl_class(
-    cls: &syn::Ident,
+    cls_ident: &syn::Ident,
+    cls: &syn::Type,
     attr: &PyClassArgs,
     doc: syn::LitStr,
-    descriptors: Vec<(syn::Field, Vec<FnType>)>,
+    descriptors: TokenStream,
+    into_pyobject: TokenStream,
     methods_type: PyClassMethodsType,
 ) -> syn::Result<TokenStream> {
-    let cls_name = get_class_python_name(cls, attr).to_string();
+    let cls_name = get_class_python_name(cls_ident, attr).to_string();
 
     let extra = {
         if let Some(freelist) = &attr.freelist {
@@ -296,16 +930,9 @@ fn impl_class(
         }
     };
 
-    let extra = if !descriptors.is_empty() {
-        let path = syn::Path::from(syn::PathSegment::from(cls.clone()));
-        let ty = syn::Type::from(syn::TypePath { path, qself: None });
-        let desc_impls = impl_descriptors(&ty, descriptors)?;
-        quote! {
-            #desc_impls
-            #extra
-        }
-    } else {
-        extra
+    let extra = quote! {
+        #descriptors
+        #extra
     };
 
     // insert space for weak ref
@@ -331,7 +958,7 @@ fn impl_class(
 
     // Enforce at compile time that PyGCProtocol is implemented
     let gc_impl = if attr.is_gc {
-        let closure_name = format!("__assertion_closure_{}", cls);
+        let closure_name = format!("__assertion_closure_{}", cls_ident);
         let closure_token = syn::Ident::new(&closure_name, Span::call_site());
         quote! {
             fn #closure_token() {
@@ -345,10 +972,121 @@ fn impl_class(
         quote! {}
     };
 
+    // Enforce at compile time that the `unsafe_lifetime` contract was acknowledged
+    let unsafe_lifetime_impl = if attr.has_unsafe_lifetime {
+        let closure_name = format!("__assertion_closure_lifetime_{}", cls_ident);
+        let closure_token = syn::Ident::new(&closure_name, Span::call_site());
+        quote! {
+            fn #closure_token() {
+                fn _assert_implements_unsafe_lifetime<T: pyo3::pyclass::PyClassUnsafeLifetime>() {}
+                _assert_implements_unsafe_lifetime::<#cls>();
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Implement the buffer protocol slots using `PyBufferInfo`
+    let buffer_impl = if attr.has_buffer {
+        let buffer_procs = if cfg!(not(Py_3_9)) {
+            Some(quote! {
+                impl pyo3::class::impl_::PyBufferProtocolProcs<#cls>
+                    for pyo3::class::impl_::PyClassImplCollector<#cls>
+                {
+                    fn buffer_procs(
+                        self
+                    ) -> Option<&'static pyo3::class::impl_::PyBufferProcs> {
+                        static PROCS: pyo3::class::impl_::PyBufferProcs
+                            = pyo3::class::impl_::PyBufferProcs {
+                                bf_getbuffer: Some(pyo3::class::buffer::getbuffer_info::<#cls>),
+                                bf_releasebuffer: Some(pyo3::class::buffer::releasebuffer_info::<#cls>),
+                            };
+                        Some(&PROCS)
+                    }
+                }
+            })
+        } else {
+            None
+        };
+        quote! {
+            #buffer_procs
+
+            impl pyo3::class::impl_::PyBufferProtocolSlots<#cls>
+                for pyo3::class::impl_::PyClassImplCollector<#cls>
+            {
+                fn buffer_protocol_slots(self) -> &'static [pyo3::ffi::PyType_Slot] {
+                    &[
+                        pyo3::ffi::PyType_Slot {
+                            slot: pyo3::ffi::Py_bf_getbuffer,
+                            pfunc: pyo3::class::buffer::getbuffer_info::<#cls> as _,
+                        },
+                        pyo3::ffi::PyType_Slot {
+                            slot: pyo3::ffi::Py_bf_releasebuffer,
+                            pfunc: pyo3::class::buffer::releasebuffer_info::<#cls> as _,
+                        },
+                    ]
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let finalizer_impl = if attr.has_finalizer {
+        quote! {
+            impl pyo3::class::impl_::PyClassFinalizerSlots<#cls>
+                for pyo3::class::impl_::PyClassImplCollector<#cls>
+            {
+                fn finalizer_slots(self) -> &'static [pyo3::ffi::PyType_Slot] {
+                    &[pyo3::ffi::PyType_Slot {
+                        slot: pyo3::ffi::Py_tp_finalize,
+                        pfunc: pyo3::class::finalize::finalize::<#cls> as _,
+                    }]
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let context_manager_impl = if attr.has_context_manager {
+        quote! {
+            impl pyo3::class::impl_::PyClassContextManagerMethods<#cls>
+                for pyo3::class::impl_::PyClassImplCollector<#cls>
+            {
+                fn context_manager_methods(self) -> &'static [pyo3::class::PyMethodDefType] {
+                    static METHODS: &[pyo3::class::PyMethodDefType] = &[
+                        pyo3::class::PyMethodDefType::Method(pyo3::class::PyMethodDef::noargs(
+                            "__enter__\0",
+                            pyo3::class::methods::PyCFunction(
+                                pyo3::class::context::enter::<#cls>
+                            ),
+                            "\0",
+                        )),
+                        pyo3::class::PyMethodDefType::Method(
+                            pyo3::class::PyMethodDef::cfunction_with_keywords(
+                                "__exit__\0",
+                                pyo3::class::methods::PyCFunctionWithKeywords(
+                                    pyo3::class::context::exit::<#cls>
+                                ),
+                                "\0",
+                            )
+                        ),
+                    ];
+                    METHODS
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let format_impl = impl_format_slots(cls, attr);
+
     let (impl_inventory, iter_py_methods) = match methods_type {
         PyClassMethodsType::Specialization => (None, quote! { collector.py_methods().iter() }),
         PyClassMethodsType::Inventory => (
-            Some(impl_methods_inventory(&cls)),
+            Some(impl_methods_inventory(cls_ident, cls)),
             quote! {
                 pyo3::inventory::iter::<<Self as pyo3::class::impl_::HasMethodsInventory>::Methods>
                     .into_iter()
@@ -364,19 +1102,6 @@ fn impl_class(
         quote! { pyo3::PyAny }
     };
 
-    // If #cls is not extended type, we allow Self->PyObject conversion
-    let into_pyobject = if !attr.has_extends {
-        quote! {
-            impl pyo3::IntoPy<pyo3::PyObject> for #cls {
-                fn into_py(self, py: pyo3::Python) -> pyo3::PyObject {
-                    pyo3::IntoPy::into_py(pyo3::Py::new(py, self).unwrap(), py)
-                }
-            }
-        }
-    } else {
-        quote! {}
-    };
-
     let thread_checker = if attr.has_unsendable {
         quote! { pyo3::class::impl_::ThreadCheckerImpl<#cls> }
     } else if attr.has_extends {
@@ -389,6 +1114,7 @@ fn impl_class(
 
     let is_gc = attr.is_gc;
     let is_basetype = attr.is_basetype;
+    let is_abstract = attr.is_abstract;
     let is_subclass = attr.has_extends;
 
     Ok(quote! {
@@ -430,6 +1156,7 @@ fn impl_class(
             const DOC: &'static str = #doc;
             const IS_GC: bool = #is_gc;
             const IS_BASETYPE: bool = #is_basetype;
+            const IS_ABSTRACT: bool = #is_abstract;
             const IS_SUBCLASS: bool = #is_subclass;
 
             type Layout = PyCell<Self>;
@@ -447,6 +1174,8 @@ fn impl_class(
                     .chain(collector.descr_protocol_methods())
                     .chain(collector.mapping_protocol_methods())
                     .chain(collector.number_protocol_methods())
+                    .chain(collector.context_manager_methods())
+                    .chain(collector.pickle_methods())
                     .for_each(visitor)
             }
             fn get_new() -> Option<pyo3::ffi::newfunc> {
@@ -459,13 +1188,29 @@ fn impl_class(
                 let collector = PyClassImplCollector::<Self>::new();
                 collector.call_impl()
             }
+            fn get_richcompare() -> Option<pyo3::ffi::richcmpfunc> {
+                use pyo3::class::impl_::*;
+                let collector = PyClassImplCollector::<Self>::new();
+                collector.richcompare_impl()
+            }
+            fn get_descr_get() -> Option<pyo3::ffi::descrgetfunc> {
+                use pyo3::class::impl_::*;
+                let collector = PyClassImplCollector::<Self>::new();
+                collector.descr_get_impl()
+            }
+            fn get_descr_set() -> Option<pyo3::ffi::descrsetfunc> {
+                use pyo3::class::impl_::*;
+                let collector = PyClassImplCollector::<Self>::new();
+                collector.descr_set_impl()
+            }
 
             fn for_each_proto_slot(visitor: &mut dyn FnMut(&pyo3::ffi::PyType_Slot)) {
                 // Implementation which uses dtolnay specialization to load all slots.
                 use pyo3::class::impl_::*;
                 let collector = PyClassImplCollector::<Self>::new();
-                collector.object_protocol_slots()
+                collector.format_slots()
                     .iter()
+                    .chain(collector.object_protocol_slots())
                     .chain(collector.number_protocol_slots())
                     .chain(collector.iter_protocol_slots())
                     .chain(collector.gc_protocol_slots())
@@ -474,6 +1219,7 @@ fn impl_class(
                     .chain(collector.sequence_protocol_slots())
                     .chain(collector.async_protocol_slots())
                     .chain(collector.buffer_protocol_slots())
+                    .chain(collector.finalizer_slots())
                     .for_each(visitor);
             }
 
@@ -487,32 +1233,123 @@ fn impl_class(
         #extra
 
         #gc_impl
+
+        #unsafe_lifetime_impl
+
+        #buffer_impl
+
+        #context_manager_impl
+
+        #finalizer_impl
+
+        #format_impl
     })
 }
 
+/// Implements `__repr__` and `__str__` for the `str` and `display` flags using Rust's formatting.
+fn impl_format_slots(cls: &syn::Type, attr: &PyClassArgs) -> TokenStream {
+    fn format_fn(cls: &syn::Type, name: &syn::Ident, format: TokenStream) -> TokenStream {
+        quote! {
+            unsafe extern "C" fn #name(
+                slf: *mut pyo3::ffi::PyObject
+            ) -> *mut pyo3::ffi::PyObject {
+                pyo3::callback::handle_panic(|py| {
+                    let cell = py.from_borrowed_ptr::<pyo3::PyCell<#cls>>(slf);
+                    let value = cell.try_borrow()?;
+                    let formatted: String = #format;
+                    pyo3::callback::convert(py, formatted)
+                })
+            }
+        }
+    }
+
+    let mut fns = Vec::new();
+    let mut slots = Vec::new();
+    if let Some(span) = attr.repr_from_format {
+        let name = syn::Ident::new("__pyo3_repr", Span::call_site());
+        let format = quote_spanned! { span=>
+            {
+                #[allow(unused_imports)]
+                use pyo3::class::impl_::{FormatWithDebug, FormatWithDisplay};
+                (&&pyo3::class::impl_::FormatWith(&*value)).pyo3_format()
+            }
+        };
+        fns.push(format_fn(cls, &name, format));
+        slots.push(quote! {
+            pyo3::ffi::PyType_Slot { slot: pyo3::ffi::Py_tp_repr, pfunc: #name as _ }
+        });
+    }
+    if let Some(span) = attr.str_from_display {
+        let name = syn::Ident::new("__pyo3_str", Span::call_site());
+        let format = quote_spanned! { span=> format!("{}", &*value) };
+        fns.push(format_fn(cls, &name, format));
+        slots.push(quote! {
+            pyo3::ffi::PyType_Slot { slot: pyo3::ffi::Py_tp_str, pfunc: #name as _ }
+        });
+    }
+    if slots.is_empty() {
+        return quote! {};
+    }
+
+    quote! {
+        impl pyo3::class::impl_::PyClassFormatSlots<#cls>
+            for pyo3::class::impl_::PyClassImplCollector<#cls>
+        {
+            fn format_slots(self) -> &'static [pyo3::ffi::PyType_Slot] {
+                #(#fns)*
+                &[#(#slots),*]
+            }
+        }
+    }
+}
+
+/// If #cls is not extended type, we allow Self->PyObject conversion
+fn impl_into_pyobject(cls: &syn::Type, attr: &PyClassArgs) -> TokenStream {
+    if !attr.has_extends {
+        quote! {
+            impl pyo3::IntoPy<pyo3::PyObject> for #cls {
+                fn into_py(self, py: pyo3::Python) -> pyo3::PyObject {
+                    pyo3::IntoPy::into_py(pyo3::Py::new(py, self).unwrap(), py)
+                }
+            }
+        }
+    } else {
+        quote! {}
+    }
+}
+
 fn impl_descriptors(
     cls: &syn::Type,
-    descriptors: Vec<(syn::Field, Vec<FnType>)>,
+    descriptors: Vec<FieldDescriptors>,
 ) -> syn::Result<TokenStream> {
     let py_methods: Vec<TokenStream> = descriptors
         .iter()
-        .flat_map(|(field, fns)| {
-            fns.iter()
-                .map(|desc| {
-                    let doc = utils::get_doc(&field.attrs, None, true)
+        .flat_map(|descriptor| {
+            descriptor
+                .fns
+                .iter()
+                .map(move |desc| {
+                    let doc = utils::get_doc(&descriptor.field.attrs, None, true)
                         .unwrap_or_else(|_| syn::LitStr::new("", Span::call_site()));
-                    let property_type = PropertyType::Descriptor(
-                        field.ident.as_ref().ok_or_else(
-                            || err_spanned!(field.span() => "`#[pyo3(get, set)]` is not supported on tuple struct fields")
-                        )?
-                    );
+                    let property_type = PropertyType::Descriptor {
+                        member: &descriptor.member,
+                        python_name: &descriptor.python_name,
+                    };
                     match desc {
-                        FnType::Getter(self_ty) => {
-                            impl_py_getter_def(cls, property_type, self_ty, &doc, &Default::default())
-                        }
-                        FnType::Setter(self_ty) => {
-                            impl_py_setter_def(cls, property_type, self_ty, &doc, &Default::default())
-                        }
+                        FnType::Getter(self_ty) => impl_py_getter_def(
+                            cls,
+                            property_type,
+                            self_ty,
+                            &doc,
+                            &Default::default(),
+                        ),
+                        FnType::Setter(self_ty) => impl_py_setter_def(
+                            cls,
+                            property_type,
+                            self_ty,
+                            &doc,
+                            &Default::default(),
+                        ),
                         _ => unreachable!(),
                     }
                 })
@@ -0,0 +1,170 @@
+// Copyright (c) 2017-present PyO3 Project and Contributors
+//! Code generation for `#[derive(PyError)]`.
+
+use crate::attributes::{self, is_attribute_ident};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    spanned::Spanned,
+    token::Comma,
+    DeriveInput, Fields, LitStr, Result, Token,
+};
+
+/// A single option in a `#[pyerror(...)]` attribute.
+enum PyErrorAttribute {
+    Base(syn::TypePath),
+    Module(LitStr),
+    Message(syn::Expr),
+}
+
+impl Parse for PyErrorAttribute {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let lookahead = input.lookahead1();
+        if lookahead.peek(attributes::kw::base) {
+            let _: attributes::kw::base = input.parse()?;
+            let _: Token![=] = input.parse()?;
+            let string_literal: LitStr = input.parse()?;
+            string_literal.parse().map(PyErrorAttribute::Base)
+        } else if lookahead.peek(attributes::kw::module) {
+            let _: attributes::kw::module = input.parse()?;
+            let _: Token![=] = input.parse()?;
+            input.parse().map(PyErrorAttribute::Module)
+        } else if lookahead.peek(attributes::kw::message) {
+            let _: attributes::kw::message = input.parse()?;
+            let _: Token![=] = input.parse()?;
+            let string_literal: LitStr = input.parse()?;
+            string_literal.parse().map(PyErrorAttribute::Message)
+        } else {
+            Err(lookahead.error())
+        }
+    }
+}
+
+#[derive(Default)]
+struct PyErrorOptions {
+    base: Option<syn::TypePath>,
+    module: Option<LitStr>,
+    message: Option<syn::Expr>,
+}
+
+impl PyErrorOptions {
+    fn from_attrs(attrs: &[syn::Attribute]) -> Result<Self> {
+        let mut options = PyErrorOptions::default();
+        for attr in attrs {
+            if !is_attribute_ident(attr, "pyerror") {
+                continue;
+            }
+            let pyerror_attrs: Punctuated<PyErrorAttribute, Comma> =
+                attr.parse_args_with(Punctuated::parse_terminated)?;
+            for pyerror_attr in pyerror_attrs {
+                match pyerror_attr {
+                    PyErrorAttribute::Base(base) => {
+                        ensure_spanned!(
+                            options.base.is_none(),
+                            base.span() => "`base` may only be provided once"
+                        );
+                        options.base = Some(base);
+                    }
+                    PyErrorAttribute::Module(module) => {
+                        ensure_spanned!(
+                            options.module.is_none(),
+                            module.span() => "`module` may only be provided once"
+                        );
+                        options.module = Some(module);
+                    }
+                    PyErrorAttribute::Message(message) => {
+                        ensure_spanned!(
+                            options.message.is_none(),
+                            message.span() => "`message` may only be provided once"
+                        );
+                        options.message = Some(message);
+                    }
+                }
+            }
+        }
+        Ok(options)
+    }
+}
+
+/// Derives a Python exception type for a Rust struct, and a conversion of the struct into a
+/// `PyErr` raising that exception.
+pub fn build_derive_py_error(tokens: &DeriveInput) -> Result<TokenStream> {
+    let ident = &tokens.ident;
+    let fields = match &tokens.data {
+        syn::Data::Struct(st) => &st.fields,
+        _ => bail_spanned!(tokens.span() => "#[derive(PyError)] is only supported for structs"),
+    };
+    ensure_spanned!(
+        tokens.generics.params.is_empty(),
+        tokens.generics.span() => "#[derive(PyError)] cannot be used on generic structs"
+    );
+
+    let options = PyErrorOptions::from_attrs(&tokens.attrs)?;
+    let module = options.module.ok_or_else(|| {
+        err_spanned!(
+            ident.span() => "#[derive(PyError)] requires a module, e.g. `#[pyerror(module = \"mymodule\")]`"
+        )
+    })?;
+    let qualified_name = format!("{}.{}", module.value(), ident);
+    let base = match options.base {
+        Some(base) => quote! { #base },
+        None => quote! { pyo3::exceptions::PyException },
+    };
+
+    // The arguments passed to the exception, evaluated with the struct as `self`.
+    let args = match options.message {
+        Some(message) => quote! { #message },
+        None => match fields {
+            Fields::Unit => quote! {},
+            Fields::Named(fields) if fields.named.len() == 1 => {
+                let field = fields.named[0].ident.as_ref().unwrap();
+                quote! { self.#field }
+            }
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => quote! { self.0 },
+            _ => bail_spanned!(
+                fields.span() => "#[derive(PyError)] requires `#[pyerror(message = \"...\")]` \
+                    for structs without exactly one field"
+            ),
+        },
+    };
+
+    Ok(quote! {
+        unsafe impl pyo3::type_object::PyTypeObject for #ident {
+            fn type_object(py: pyo3::Python) -> &pyo3::types::PyType {
+                use pyo3::once_cell::GILOnceCell;
+                static TYPE_OBJECT: GILOnceCell<pyo3::Py<pyo3::types::PyType>> =
+                    GILOnceCell::new();
+
+                TYPE_OBJECT
+                    .get_or_init(py, || unsafe {
+                        pyo3::Py::from_owned_ptr(
+                            py,
+                            pyo3::PyErr::new_type(
+                                py,
+                                #qualified_name,
+                                Some(py.get_type::<#base>()),
+                                None,
+                            )
+                            .as_ptr() as *mut pyo3::ffi::PyObject,
+                        )
+                    })
+                    .as_ref(py)
+            }
+        }
+
+        impl #ident {
+            #[doc(hidden)]
+            fn __pyo3_exception_args(self) -> impl pyo3::PyErrArguments + 'static {
+                #args
+            }
+        }
+
+        impl std::convert::From<#ident> for pyo3::PyErr {
+            fn from(err: #ident) -> pyo3::PyErr {
+                pyo3::PyErr::new::<#ident, _>(err.__pyo3_exception_args())
+            }
+        }
+    })
+}
@@ -3,11 +3,11 @@
 use crate::{
     attributes::{
         self, get_deprecated_name_attribute, get_pyo3_attributes, take_attributes,
-        FromPyWithAttribute, NameAttribute,
+        FromPyWithAttribute, MethodOfAttribute, NameAttribute,
     },
     deprecations::Deprecations,
-    method::{self, FnArg, FnSpec},
-    pymethod::{check_generic, get_arg_names, impl_arg_params},
+    method::{self, CallingConvention, FnArg, FnSpec},
+    pymethod::{check_generic, get_arg_names, impl_arg_param, impl_arg_params},
     utils::{self, ensure_not_async_fn},
 };
 use proc_macro2::{Span, TokenStream};
@@ -21,6 +21,7 @@ use syn::{
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Argument {
+    PosOnlyArgsSeparator,
     VarArgsSeparator,
     VarArgs(syn::Path),
     KeywordArgs(syn::Path),
@@ -33,6 +34,7 @@ pub enum Argument {
 pub struct PyFunctionSignature {
     pub arguments: Vec<Argument>,
     has_kw: bool,
+    has_posonly_args: bool,
     has_varargs: bool,
     has_kwargs: bool,
 }
@@ -40,10 +42,12 @@ pub struct PyFunctionSignature {
 #[derive(Clone, PartialEq, Debug)]
 pub struct PyFunctionArgPyO3Attributes {
     pub from_py_with: Option<FromPyWithAttribute>,
+    pub python: bool,
 }
 
 enum PyFunctionArgPyO3Attribute {
     FromPyWith(FromPyWithAttribute),
+    Python(attributes::kw::python),
 }
 
 impl Parse for PyFunctionArgPyO3Attribute {
@@ -51,6 +55,8 @@ impl Parse for PyFunctionArgPyO3Attribute {
         let lookahead = input.lookahead1();
         if lookahead.peek(attributes::kw::from_py_with) {
             input.parse().map(PyFunctionArgPyO3Attribute::FromPyWith)
+        } else if lookahead.peek(attributes::kw::python) {
+            input.parse().map(PyFunctionArgPyO3Attribute::Python)
         } else {
             Err(lookahead.error())
         }
@@ -58,9 +64,12 @@ impl Parse for PyFunctionArgPyO3Attribute {
 }
 
 impl PyFunctionArgPyO3Attributes {
-    /// Parses #[pyo3(from_python_with = "func")]
+    /// Parses #[pyo3(from_python_with = "func")] and #[pyo3(python)]
     pub fn from_attrs(attrs: &mut Vec<syn::Attribute>) -> syn::Result<Self> {
-        let mut attributes = PyFunctionArgPyO3Attributes { from_py_with: None };
+        let mut attributes = PyFunctionArgPyO3Attributes {
+            from_py_with: None,
+            python: false,
+        };
         take_attributes(attrs, |attr| {
             if let Some(pyo3_attrs) = get_pyo3_attributes(attr)? {
                 for attr in pyo3_attrs {
@@ -72,6 +81,13 @@ impl PyFunctionArgPyO3Attributes {
                             );
                             attributes.from_py_with = Some(from_py_with);
                         }
+                        PyFunctionArgPyO3Attribute::Python(python) => {
+                            ensure_spanned!(
+                                !attributes.python,
+                                python.span() => "`python` may only be specified once per argument"
+                            );
+                            attributes.python = true;
+                        }
                     }
                 }
                 Ok(true)
@@ -79,6 +95,11 @@ impl PyFunctionArgPyO3Attributes {
                 Ok(false)
             }
         })?;
+        if let (true, Some(from_py_with)) = (attributes.python, &attributes.from_py_with) {
+            bail_spanned!(
+                from_py_with.0.span() => "`python` and `from_py_with` cannot be used together"
+            );
+        }
         Ok(attributes)
     }
 }
@@ -125,7 +146,15 @@ impl PyFunctionSignature {
                 self.arguments.push(Argument::VarArgsSeparator);
                 Ok(())
             }
-            _ => bail_spanned!(item.span() => "expected \"*\""),
+            syn::Lit::Str(lits) if lits.value() == "/" => {
+                // "/"
+                self.posonly_args_is_ok(item)?;
+                self.has_posonly_args = true;
+                // any arguments _before_ this become positional-only
+                self.arguments.push(Argument::PosOnlyArgsSeparator);
+                Ok(())
+            }
+            _ => bail_spanned!(item.span() => "expected \"*\" or \"/\""),
         }
     }
 
@@ -142,6 +171,18 @@ impl PyFunctionSignature {
         Ok(())
     }
 
+    fn posonly_args_is_ok(&self, item: &NestedMeta) -> syn::Result<()> {
+        ensure_spanned!(
+            !(self.has_posonly_args || self.has_varargs || self.has_kwargs),
+            item.span() => "/ is not allowed after /, varargs(*) or kwargs(**)"
+        );
+        ensure_spanned!(
+            !self.arguments.is_empty(),
+            item.span() => "/ must be preceded by at least one positional argument"
+        );
+        Ok(())
+    }
+
     fn vararg_is_ok(&self, item: &NestedMeta) -> syn::Result<()> {
         ensure_spanned!(
             !(self.has_kwargs || self.has_varargs),
@@ -209,7 +250,9 @@ impl PyFunctionSignature {
 #[derive(Default)]
 pub struct PyFunctionOptions {
     pub pass_module: bool,
+    pub no_doc: bool,
     pub name: Option<NameAttribute>,
+    pub method_of: Option<MethodOfAttribute>,
     pub signature: Option<PyFunctionSignature>,
     pub deprecations: Deprecations,
 }
@@ -218,7 +261,9 @@ impl Parse for PyFunctionOptions {
     fn parse(input: ParseStream) -> Result<Self> {
         let mut options = PyFunctionOptions {
             pass_module: false,
+            no_doc: false,
             name: None,
+            method_of: None,
             signature: None,
             deprecations: Deprecations::new(),
         };
@@ -228,6 +273,8 @@ impl Parse for PyFunctionOptions {
             if lookahead.peek(attributes::kw::name)
                 || lookahead.peek(attributes::kw::pass_module)
                 || lookahead.peek(attributes::kw::signature)
+                || lookahead.peek(attributes::kw::no_doc)
+                || lookahead.peek(attributes::kw::method_of)
             {
                 options.add_attributes(std::iter::once(input.parse()?))?;
                 if !input.is_empty() {
@@ -250,6 +297,8 @@ pub enum PyFunctionOption {
     Name(NameAttribute),
     PassModule(attributes::kw::pass_module),
     Signature(PyFunctionSignature),
+    NoDoc(attributes::kw::no_doc),
+    MethodOf(MethodOfAttribute),
 }
 
 impl Parse for PyFunctionOption {
@@ -261,6 +310,10 @@ impl Parse for PyFunctionOption {
             input.parse().map(PyFunctionOption::PassModule)
         } else if lookahead.peek(attributes::kw::signature) {
             input.parse().map(PyFunctionOption::Signature)
+        } else if lookahead.peek(attributes::kw::no_doc) {
+            input.parse().map(PyFunctionOption::NoDoc)
+        } else if lookahead.peek(attributes::kw::method_of) {
+            input.parse().map(PyFunctionOption::MethodOf)
         } else {
             Err(lookahead.error())
         }
@@ -313,6 +366,20 @@ impl PyFunctionOptions {
                     );
                     self.signature = Some(signature);
                 }
+                PyFunctionOption::NoDoc(kw) => {
+                    ensure_spanned!(
+                        !self.no_doc,
+                        kw.span() => "`no_doc` may only be specified once"
+                    );
+                    self.no_doc = true;
+                }
+                PyFunctionOption::MethodOf(method_of) => {
+                    ensure_spanned!(
+                        self.method_of.is_none(),
+                        method_of.0.span() => "`method_of` may only be specified once"
+                    );
+                    self.method_of = Some(method_of);
+                }
             }
         }
         Ok(())
@@ -355,6 +422,13 @@ pub fn impl_wrap_pyfunction(
         .name
         .map_or_else(|| func.sig.ident.unraw(), |name| name.0);
 
+    if let Some(MethodOfAttribute(cls)) = &options.method_of {
+        ensure_spanned!(
+            options.signature.is_none() && !options.pass_module,
+            cls.span() => "`method_of` cannot be combined with `signature` or `pass_module`"
+        );
+    }
+
     let signature = options.signature.unwrap_or_default();
 
     let mut arguments = func
@@ -377,10 +451,24 @@ pub fn impl_wrap_pyfunction(
         );
     }
 
+    if options.method_of.is_some() {
+        ensure_spanned!(
+            arguments.iter().filter(|arg| !arg.py).count() == 1,
+            func.sig.inputs.span() => "expected exactly one argument with `method_of`"
+        );
+    }
+
     let ty = method::get_return_info(&func.sig.output);
 
     let text_signature = utils::parse_text_signature_attrs(&mut func.attrs, &python_name)?;
-    let doc = utils::get_doc(&func.attrs, text_signature, true)?;
+    let doc = if options.no_doc {
+        if let Some(text_signature) = text_signature {
+            bail_spanned!(text_signature.span() => "text_signature cannot be used with `no_doc`");
+        }
+        syn::LitStr::new("\0", func.sig.ident.span())
+    } else {
+        utils::get_doc(&func.attrs, text_signature, true)?
+    };
 
     let function_wrapper_ident = function_wrapper_ident(&func.sig.ident);
 
@@ -393,6 +481,7 @@ pub fn impl_wrap_pyfunction(
         output: ty,
         doc,
         deprecations: options.deprecations,
+        asyncness: None,
     };
 
     let doc = &spec.doc;
@@ -400,16 +489,20 @@ pub fn impl_wrap_pyfunction(
 
     let name = &func.sig.ident;
     let wrapper_ident = format_ident!("__pyo3_raw_{}", name);
-    let wrapper = function_c_wrapper(name, &wrapper_ident, &spec, options.pass_module)?;
-    let methoddef = if spec.args.is_empty() {
-        quote!(noargs)
-    } else {
-        quote!(cfunction_with_keywords)
-    };
-    let cfunc = if spec.args.is_empty() {
-        quote!(PyCFunction)
-    } else {
-        quote!(PyCFunctionWithKeywords)
+    let wrapper = function_c_wrapper(
+        name,
+        &wrapper_ident,
+        &spec,
+        options.pass_module,
+        options.method_of.as_ref(),
+    )?;
+    let (methoddef, cfunc) = match calling_convention(&spec, options.method_of.as_ref()) {
+        CallingConvention::SingleArg => (quote!(onearg), quote!(PyCFunction)),
+        CallingConvention::NoArgs => (quote!(noargs), quote!(PyCFunction)),
+        CallingConvention::Varargs => (
+            quote!(cfunction_with_keywords),
+            quote!(PyCFunctionWithKeywords),
+        ),
     };
     let wrapped_pyfunction = quote! {
         #wrapper
@@ -429,34 +522,98 @@ pub fn impl_wrap_pyfunction(
     Ok((function_wrapper_ident, wrapped_pyfunction))
 }
 
+/// `method_of` functions always take their single argument with `METH_O`.
+fn calling_convention(
+    spec: &FnSpec<'_>,
+    method_of: Option<&MethodOfAttribute>,
+) -> CallingConvention {
+    if method_of.is_some() {
+        CallingConvention::SingleArg
+    } else {
+        spec.calling_convention()
+    }
+}
+
 /// Generate static function wrapper (PyCFunction, PyCFunctionWithKeywords)
 fn function_c_wrapper(
     name: &Ident,
     wrapper_ident: &Ident,
     spec: &FnSpec<'_>,
     pass_module: bool,
+    method_of: Option<&MethodOfAttribute>,
 ) -> Result<TokenStream> {
     let names: Vec<Ident> = get_arg_names(&spec);
-    let (cb, slf_module) = if pass_module {
+    let (call, slf_module) = if pass_module {
         (
-            quote! {
-                pyo3::callback::convert(_py, #name(_slf, #(#names),*))
-            },
+            quote! { #name(_slf, #(#names),*) },
             Some(quote! {
                 let _slf = _py.from_borrowed_ptr::<pyo3::types::PyModule>(_slf);
             }),
         )
     } else {
-        (
-            quote! {
-                pyo3::callback::convert(_py, #name(#(#names),*))
-            },
-            None,
-        )
+        (quote! { #name(#(#names),*) }, None)
+    };
+    let cb = if type_is_impl_iterator(&spec.output) {
+        // `impl Iterator` can't implement `IntoPy`, so wrap it in a Python iterator object
+        quote! {
+            pyo3::callback::convert(_py, pyo3::impl_::iter::wrap_iterator(_py, #call))
+        }
+    } else {
+        quote! {
+            pyo3::callback::convert(_py, #call)
+        }
     };
     let py = syn::Ident::new("_py", Span::call_site());
     let deprecations = &spec.deprecations;
-    if spec.args.is_empty() {
+    // With METH_NOARGS and METH_O, the arguments are converted from the (at most one) argument
+    // passed by Python instead of from `*args` and `**kwargs`
+    let args_array = syn::Ident::new("output", Span::call_site());
+    let mut option_pos = 0;
+    let mut param_conversion = Vec::new();
+    let convention = calling_convention(spec, method_of);
+    if convention != CallingConvention::Varargs {
+        for (idx, arg) in spec.args.iter().enumerate() {
+            param_conversion.push(impl_arg_param(
+                arg,
+                spec,
+                idx,
+                None,
+                &mut option_pos,
+                &py,
+                &args_array,
+            )?);
+        }
+    }
+    if convention == CallingConvention::SingleArg {
+        // METH_O: the single argument is passed directly
+        let check_method_of = method_of.map(|MethodOfAttribute(cls)| {
+            // With `method_of`, the argument must be an instance of `cls`
+            let arg_name = spec.args.iter().find(|arg| !arg.py).unwrap().name;
+            quote! {
+                _arg.downcast::<pyo3::PyCell<#cls>>().map_err(|e| {
+                    pyo3::derive_utils::argument_extraction_error(#py, stringify!(#arg_name), e.into())
+                })?;
+            }
+        });
+        Ok(quote! {
+            unsafe extern "C" fn #wrapper_ident(
+                _slf: *mut pyo3::ffi::PyObject,
+                _arg: *mut pyo3::ffi::PyObject) -> *mut pyo3::ffi::PyObject
+            {
+                #deprecations
+                pyo3::callback::handle_panic(|#py| {
+                    #slf_module
+                    let _arg = #py.from_borrowed_ptr::<pyo3::PyAny>(_arg);
+                    #check_method_of
+                    let #args_array = [Some(_arg)];
+
+                    #(#param_conversion)*
+
+                    #cb
+                })
+            }
+        })
+    } else if convention == CallingConvention::NoArgs {
         Ok(quote! {
             unsafe extern "C" fn #wrapper_ident(
                 _slf: *mut pyo3::ffi::PyObject,
@@ -465,6 +622,9 @@ fn function_c_wrapper(
                 #deprecations
                 pyo3::callback::handle_panic(|#py| {
                     #slf_module
+
+                    #(#param_conversion)*
+
                     #cb
                 })
             }
@@ -490,6 +650,23 @@ fn function_c_wrapper(
     }
 }
 
+/// Checks whether the type is `impl Iterator<...>`
+fn type_is_impl_iterator(ty: &syn::Type) -> bool {
+    if let syn::Type::ImplTrait(impl_trait) = ty {
+        impl_trait.bounds.iter().any(|bound| match bound {
+            syn::TypeParamBound::Trait(trait_bound) => trait_bound
+                .path
+                .segments
+                .last()
+                .map(|seg| seg.ident == "Iterator")
+                .unwrap_or(false),
+            _ => false,
+        })
+    } else {
+        false
+    }
+}
+
 fn type_is_pymodule(ty: &syn::Type) -> bool {
     if let syn::Type::Reference(tyref) = ty {
         if let syn::Type::Path(typath) = tyref.elem.as_ref() {
@@ -525,6 +702,9 @@ mod test {
         assert!(items(quote! {test, "*", args="*"}).is_err());
         assert!(items(quote! {test, kwargs="**", args="*"}).is_err());
         assert!(items(quote! {test, kwargs="**", args}).is_err());
+        assert!(items(quote! {"/", test}).is_err());
+        assert!(items(quote! {test, "/", "/"}).is_err());
+        assert!(items(quote! {test, "*", test2, "/"}).is_err());
     }
 
     #[test]
@@ -579,6 +759,20 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_posonly_args() {
+        let args = items(quote! {test1, "/", test2="None", "*", test3="None"}).unwrap();
+        assert!(
+            args == vec![
+                Argument::Arg(parse_quote! {test1}, None),
+                Argument::PosOnlyArgsSeparator,
+                Argument::Arg(parse_quote! {test2}, Some("None".to_owned())),
+                Argument::VarArgsSeparator,
+                Argument::Kwarg(parse_quote! {test3}, Some("None".to_owned())),
+            ]
+        );
+    }
+
     #[test]
     fn test_all() {
         let args =
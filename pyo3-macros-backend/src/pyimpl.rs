@@ -7,10 +7,11 @@ use crate::{
 };
 use proc_macro2::TokenStream;
 use pymethod::GeneratedPyMethod;
-use quote::quote;
+use quote::{quote, quote_spanned};
 use syn::spanned::Spanned;
 
 /// The mechanism used to collect `#[pymethods]` into the type object
+#[derive(Copy, Clone)]
 pub enum PyClassMethodsType {
     Specialization,
     Inventory,
@@ -39,6 +40,12 @@ pub fn impl_methods(
 ) -> syn::Result<TokenStream> {
     let mut new_impls = Vec::new();
     let mut call_impls = Vec::new();
+    let mut richcompare_arms = Vec::new();
+    let mut descr_get_impl = None;
+    let mut descr_set_arm = None;
+    let mut descr_delete_arm = None;
+    let mut has_eq = false;
+    let mut has_ne = false;
     let mut methods = Vec::new();
     for iimpl in impls.iter_mut() {
         match iimpl {
@@ -57,6 +64,31 @@ pub fn impl_methods(
                         let attrs = get_cfg_attributes(&meth.attrs);
                         call_impls.push(quote!(#(#attrs)* #token_stream));
                     }
+                    GeneratedPyMethod::RichCompare(op, token_stream) => {
+                        let attrs = get_cfg_attributes(&meth.attrs);
+                        has_eq |= op == "Py_EQ";
+                        has_ne |= op == "Py_NE";
+                        richcompare_arms.push(quote!(#(#attrs)* #token_stream));
+                    }
+                    GeneratedPyMethod::DescriptorGet(token_stream) => {
+                        let attrs = get_cfg_attributes(&meth.attrs);
+                        ensure_spanned!(
+                            descr_get_impl.replace(quote!(#(#attrs)* #token_stream)).is_none(),
+                            meth.sig.ident.span() => "`#[get_descriptor]` may only be used once"
+                        );
+                    }
+                    GeneratedPyMethod::DescriptorSet(token_stream) => {
+                        ensure_spanned!(
+                            descr_set_arm.replace(token_stream).is_none(),
+                            meth.sig.ident.span() => "`#[set_descriptor]` may only be used once"
+                        );
+                    }
+                    GeneratedPyMethod::DescriptorDelete(token_stream) => {
+                        ensure_spanned!(
+                            descr_delete_arm.replace(token_stream).is_none(),
+                            meth.sig.ident.span() => "`#[delete_descriptor]` may only be used once"
+                        );
+                    }
                 }
             }
             syn::ImplItem::Const(konst) => {
@@ -80,11 +112,39 @@ pub fn impl_methods(
         PyClassMethodsType::Inventory => submit_methods_inventory(ty, methods),
     };
 
+    let richcompare_impl = if richcompare_arms.is_empty() {
+        TokenStream::default()
+    } else {
+        pymethod::impl_py_method_def_richcompare(ty, &richcompare_arms, has_eq && !has_ne)
+    };
+
+    let descr_set_impl = if descr_set_arm.is_none() && descr_delete_arm.is_none() {
+        TokenStream::default()
+    } else {
+        pymethod::impl_py_method_def_descr_set(
+            ty,
+            descr_set_arm.as_ref(),
+            descr_delete_arm.as_ref(),
+        )
+    };
+
+    let assert_pyclass = quote_spanned! { ty.span() =>
+        const _: fn() = pyo3::class::impl_::assert_pyclass::<#ty>;
+    };
+
     Ok(quote! {
+        #assert_pyclass
+
         #(#new_impls)*
 
         #(#call_impls)*
 
+        #richcompare_impl
+
+        #descr_get_impl
+
+        #descr_set_impl
+
         #methods_registration
     })
 }
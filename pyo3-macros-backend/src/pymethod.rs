@@ -1,7 +1,6 @@
-use crate::utils::ensure_not_async_fn;
 // Copyright (c) 2017-present PyO3 Project and Contributors
+use crate::deprecations::Deprecations;
 use crate::{attributes::FromPyWithAttribute, konst::ConstSpec};
-use crate::{deprecations::Deprecations, utils};
 use crate::{
     method::{FnArg, FnSpec, FnType, SelfType},
     pyfunction::PyFunctionOptions,
@@ -12,7 +11,10 @@ use syn::{ext::IdentExt, spanned::Spanned, Result};
 
 #[derive(Clone, Copy)]
 pub enum PropertyType<'a> {
-    Descriptor(&'a syn::Ident),
+    Descriptor {
+        member: &'a syn::Member,
+        python_name: &'a syn::Ident,
+    },
     Function(&'a FnSpec<'a>),
 }
 
@@ -20,6 +22,10 @@ pub enum GeneratedPyMethod {
     Method(TokenStream),
     New(TokenStream),
     Call(TokenStream),
+    RichCompare(syn::Ident, TokenStream),
+    DescriptorGet(TokenStream),
+    DescriptorSet(TokenStream),
+    DescriptorDelete(TokenStream),
 }
 
 pub fn gen_py_method(
@@ -29,18 +35,24 @@ pub fn gen_py_method(
     options: PyFunctionOptions,
 ) -> Result<GeneratedPyMethod> {
     check_generic(sig)?;
-    ensure_not_async_fn(sig)?;
     let spec = FnSpec::parse(sig, &mut *meth_attrs, options)?;
+    check_async(&spec)?;
 
     Ok(match &spec.tp {
-        FnType::Fn(self_ty) => {
-            GeneratedPyMethod::Method(impl_py_method_def(cls, &spec, self_ty, None)?)
-        }
+        FnType::Fn(self_ty) => match richcompare_op(&spec.python_name) {
+            Some(op) => {
+                let arm = impl_py_richcompare_arm(cls, &spec, self_ty, &op)?;
+                GeneratedPyMethod::RichCompare(op, arm)
+            }
+            None => GeneratedPyMethod::Method(impl_py_method_def(cls, &spec, self_ty, None)?),
+        },
         FnType::FnNew => GeneratedPyMethod::New(impl_py_method_def_new(cls, &spec)?),
         FnType::FnCall(self_ty) => {
             GeneratedPyMethod::Call(impl_py_method_def_call(cls, &spec, self_ty)?)
         }
-        FnType::FnClass => GeneratedPyMethod::Method(impl_py_method_def_class(cls, &spec)?),
+        FnType::FnClass | FnType::FnInitSubclass => {
+            GeneratedPyMethod::Method(impl_py_method_def_class(cls, &spec)?)
+        }
         FnType::FnStatic => GeneratedPyMethod::Method(impl_py_method_def_static(cls, &spec)?),
         FnType::ClassAttribute => {
             GeneratedPyMethod::Method(impl_py_method_class_attribute(cls, &spec))
@@ -59,9 +71,54 @@ pub fn gen_py_method(
             &spec.doc,
             &spec.deprecations,
         )?),
+        FnType::DescriptorGet(self_ty) => {
+            GeneratedPyMethod::DescriptorGet(impl_py_method_def_descr_get(cls, &spec, self_ty)?)
+        }
+        FnType::DescriptorSet(self_ty) => GeneratedPyMethod::DescriptorSet(impl_py_descr_set_arm(
+            cls,
+            &spec,
+            self_ty,
+            &["_obj", "_value"],
+        )?),
+        FnType::DescriptorDelete(self_ty) => GeneratedPyMethod::DescriptorDelete(
+            impl_py_descr_set_arm(cls, &spec, self_ty, &["_obj"])?,
+        ),
     })
 }
 
+/// Checks that `async fn` is only used for method types which can return a coroutine.
+fn check_async(spec: &FnSpec) -> syn::Result<()> {
+    let asyncness = match &spec.asyncness {
+        Some(asyncness) => asyncness,
+        None => return Ok(()),
+    };
+    match &spec.tp {
+        FnType::Fn(SelfType::Receiver { .. }) => bail_spanned!(
+            asyncness.span() => "`async fn` methods cannot borrow `self`, because the returned \
+            coroutine may outlive the borrow; take `slf: Py<Self>` instead"
+        ),
+        FnType::Fn(_) if richcompare_op(&spec.python_name).is_none() => Ok(()),
+        FnType::FnClass | FnType::FnStatic => Ok(()),
+        FnType::FnNew => bail_spanned!(
+            asyncness.span() => "`async fn` is not supported for `#[new]`, which must return \
+            the new object synchronously"
+        ),
+        _ => bail_spanned!(
+            asyncness.span() => "`async fn` is only supported for instance methods, \
+            `#[classmethod]`s and `#[staticmethod]`s"
+        ),
+    }
+}
+
+/// Wraps the future returned by an `async fn` in a Python coroutine.
+fn wrap_async(spec: &FnSpec, call: TokenStream) -> TokenStream {
+    if spec.asyncness.is_some() {
+        quote! { pyo3::types::PyCoroutine::new(_py, #call) }
+    } else {
+        call
+    }
+}
+
 pub(crate) fn check_generic(sig: &syn::Signature) -> syn::Result<()> {
     let err_msg = |typ| format!("Python functions cannot have generic {} parameters", typ);
     for param in &sig.generics.params {
@@ -80,7 +137,7 @@ pub fn gen_py_const(cls: &syn::Type, spec: &ConstSpec) -> TokenStream {
     let wrapper = quote! {{
         fn __wrap(py: pyo3::Python<'_>) -> pyo3::PyObject {
             #deprecations
-            pyo3::IntoPy::into_py(#cls::#member, py)
+            pyo3::IntoPy::into_py(<#cls>::#member, py)
         }
         __wrap
     }};
@@ -143,7 +200,7 @@ pub fn impl_wrap_noargs(cls: &syn::Type, spec: &FnSpec<'_>, self_ty: &SelfType)
 pub fn impl_wrap_new(cls: &syn::Type, spec: &FnSpec<'_>) -> Result<TokenStream> {
     let name = &spec.name;
     let names: Vec<syn::Ident> = get_arg_names(&spec);
-    let cb = quote! { #cls::#name(#(#names),*) };
+    let cb = quote! { <#cls>::#name(#(#names),*) };
     let py = syn::Ident::new("_py", Span::call_site());
     let body = impl_arg_params(spec, Some(cls), cb, &py)?;
     let deprecations = &spec.deprecations;
@@ -160,6 +217,14 @@ pub fn impl_wrap_new(cls: &syn::Type, spec: &FnSpec<'_>) -> Result<TokenStream>
                 let _args = #py.from_borrowed_ptr::<pyo3::types::PyTuple>(_args);
                 let _kwargs: Option<&pyo3::types::PyDict> = #py.from_borrowed_ptr_or_opt(_kwargs);
 
+                if <#cls as pyo3::class::impl_::PyClassImpl>::IS_ABSTRACT
+                    && subtype == <#cls as pyo3::type_object::PyTypeInfo>::type_object_raw(#py)
+                {
+                    return Err(pyo3::exceptions::PyTypeError::new_err(
+                        "cannot instantiate abstract class"
+                    ));
+                }
+
                 let initializer: pyo3::PyClassInitializer::<#cls> = #body.convert(#py)?;
                 let cell = initializer.create_cell_from_subtype(#py, subtype)?;
                 Ok(cell as *mut pyo3::ffi::PyObject)
@@ -173,7 +238,24 @@ pub fn impl_wrap_new(cls: &syn::Type, spec: &FnSpec<'_>) -> Result<TokenStream>
 pub fn impl_wrap_class(cls: &syn::Type, spec: &FnSpec<'_>) -> Result<TokenStream> {
     let name = &spec.name;
     let names: Vec<syn::Ident> = get_arg_names(&spec);
-    let cb = quote! { pyo3::callback::convert(_py, #cls::#name(&_cls, #(#names),*)) };
+    let call = if spec.asyncness.is_some() {
+        // The coroutine can't hold on to a borrowed `&PyType`, so pass `Py<PyType>` instead.
+        quote! { <#cls>::#name(std::convert::Into::into(_cls), #(#names),*) }
+    } else {
+        quote! { <#cls>::#name(&_cls, #(#names),*) }
+    };
+    let call = wrap_async(spec, call);
+    let cb = if let FnType::FnInitSubclass = spec.tp {
+        // After argument extraction `_kwargs` holds only the keyword arguments collected by
+        // `**kwargs`, if any, which are passed on to the base class.
+        quote! {
+            pyo3::callback::convert::<_, ()>(_py, #call)?;
+            pyo3::class::impl_::init_subclass_super::<#cls>(_py, _cls, _kwargs)?;
+            pyo3::callback::convert(_py, ())
+        }
+    } else {
+        quote! { pyo3::callback::convert(_py, #call) }
+    };
     let py = syn::Ident::new("_py", Span::call_site());
     let body = impl_arg_params(spec, Some(cls), cb, &py)?;
     let deprecations = &spec.deprecations;
@@ -201,7 +283,8 @@ pub fn impl_wrap_class(cls: &syn::Type, spec: &FnSpec<'_>) -> Result<TokenStream
 pub fn impl_wrap_static(cls: &syn::Type, spec: &FnSpec<'_>) -> Result<TokenStream> {
     let name = &spec.name;
     let names: Vec<syn::Ident> = get_arg_names(&spec);
-    let cb = quote! { pyo3::callback::convert(_py, #cls::#name(#(#names),*)) };
+    let call = wrap_async(spec, quote! { <#cls>::#name(#(#names),*) });
+    let cb = quote! { pyo3::callback::convert(_py, #call) };
     let py = syn::Ident::new("_py", Span::call_site());
     let body = impl_arg_params(spec, Some(cls), cb, &py)?;
     let deprecations = &spec.deprecations;
@@ -229,7 +312,7 @@ pub fn impl_wrap_static(cls: &syn::Type, spec: &FnSpec<'_>) -> Result<TokenStrea
 /// To be called in `pyo3::pyclass::initialize_type_object`.
 pub fn impl_wrap_class_attribute(cls: &syn::Type, spec: &FnSpec<'_>) -> TokenStream {
     let name = &spec.name;
-    let cb = quote! { #cls::#name() };
+    let cb = quote! { <#cls>::#name() };
     let deprecations = &spec.deprecations;
     quote! {{
         fn __wrap(py: pyo3::Python<'_>) -> pyo3::PyObject {
@@ -249,9 +332,9 @@ fn impl_call_getter(cls: &syn::Type, spec: &FnSpec) -> syn::Result<TokenStream>
 
     let name = &spec.name;
     let fncall = if py_arg.is_some() {
-        quote!(#cls::#name(_slf, _py))
+        quote!(<#cls>::#name(_slf, _py))
     } else {
-        quote!(#cls::#name(_slf))
+        quote!(<#cls>::#name(_slf))
     };
 
     Ok(fncall)
@@ -264,8 +347,8 @@ pub(crate) fn impl_wrap_getter(
     self_ty: &SelfType,
 ) -> syn::Result<TokenStream> {
     let getter_impl = match &property_type {
-        PropertyType::Descriptor(ident) => {
-            quote!(_slf.#ident.clone())
+        PropertyType::Descriptor { member, .. } => {
+            quote!(_slf.#member.clone())
         }
         PropertyType::Function(spec) => impl_call_getter(cls, spec)?,
     };
@@ -298,9 +381,9 @@ fn impl_call_setter(cls: &syn::Type, spec: &FnSpec) -> syn::Result<TokenStream>
 
     let name = &spec.name;
     let fncall = if py_arg.is_some() {
-        quote!(#cls::#name(_slf, _py, _val))
+        quote!(<#cls>::#name(_slf, _py, _val))
     } else {
-        quote!(#cls::#name(_slf, _val))
+        quote!(<#cls>::#name(_slf, _val))
     };
 
     Ok(fncall)
@@ -313,8 +396,8 @@ pub(crate) fn impl_wrap_setter(
     self_ty: &SelfType,
 ) -> syn::Result<TokenStream> {
     let setter_impl = match &property_type {
-        PropertyType::Descriptor(ident) => {
-            quote!({ _slf.#ident = _val; })
+        PropertyType::Descriptor { member, .. } => {
+            quote!({ _slf.#member = _val; })
         }
         PropertyType::Function(spec) => impl_call_setter(cls, spec)?,
     };
@@ -348,7 +431,8 @@ pub fn get_arg_names(spec: &FnSpec) -> Vec<syn::Ident> {
 fn impl_call(cls: &syn::Type, spec: &FnSpec<'_>) -> TokenStream {
     let fname = &spec.name;
     let names = get_arg_names(spec);
-    quote! { pyo3::callback::convert(_py, #cls::#fname(_slf, #(#names),*)) }
+    let call = wrap_async(spec, quote! { <#cls>::#fname(_slf, #(#names),*) });
+    quote! { pyo3::callback::convert(_py, #call) }
 }
 
 pub fn impl_arg_params(
@@ -362,6 +446,7 @@ pub fn impl_arg_params(
     }
 
     let mut positional_parameter_names = Vec::new();
+    let mut positional_only_parameters = 0usize;
     let mut required_positional_parameters = 0usize;
     let mut keyword_only_parameters = Vec::new();
 
@@ -381,6 +466,13 @@ pub fn impl_arg_params(
                 }
             });
         } else {
+            if spec.is_pos_only(&arg.name) {
+                ensure_spanned!(
+                    positional_only_parameters == positional_parameter_names.len(),
+                    arg.name.span() => "positional-only arguments must come before other positional arguments"
+                );
+                positional_only_parameters += 1;
+            }
             if required {
                 required_positional_parameters += 1;
             }
@@ -430,8 +522,7 @@ pub fn impl_arg_params(
                 cls_name: #cls_name,
                 func_name: stringify!(#python_name),
                 positional_parameter_names: &[#(#positional_parameter_names),*],
-                // TODO: https://github.com/PyO3/pyo3/issues/1439 - support specifying these
-                positional_only_parameters: 0,
+                positional_only_parameters: #positional_only_parameters,
                 required_positional_parameters: #required_positional_parameters,
                 keyword_only_parameters: &[#(#keyword_only_parameters),*],
                 accept_varargs: #accept_args,
@@ -450,7 +541,7 @@ pub fn impl_arg_params(
 
 /// Re option_pos: The option slice doesn't contain the py: Python argument, so the argument
 /// index and the index in option diverge when using py: Python
-fn impl_arg_param(
+pub fn impl_arg_param(
     arg: &FnArg<'_>,
     spec: &FnSpec<'_>,
     idx: usize,
@@ -477,6 +568,13 @@ fn impl_arg_param(
         |e| pyo3::derive_utils::argument_extraction_error(#py, stringify!(#name), e)
     };
 
+    if let Some(FromPyWithAttribute(expr_path)) = &arg.attrs.from_py_with {
+        ensure_spanned!(
+            !spec.is_args(&name) && !spec.is_kwargs(&name),
+            expr_path.span() => "`from_py_with` cannot be used on `*args` or `**kwargs` arguments"
+        );
+    }
+
     if spec.is_args(&name) {
         ensure_spanned!(
             arg.optional.is_none(),
@@ -524,7 +622,7 @@ fn impl_arg_param(
         }
     };
 
-    return if let syn::Type::Reference(tref) = arg.optional.as_ref().unwrap_or(&ty) {
+    if let syn::Type::Reference(tref) = arg.optional.as_ref().unwrap_or(&ty) {
         let (tref, mut_) = preprocess_tref(tref, self_);
         let (target_ty, borrow_tmp) = if arg.optional.is_some() {
             // Get Option<&T> from Option<PyRef<T>>
@@ -552,35 +650,35 @@ fn impl_arg_param(
         Ok(quote_arg_span! {
             let #arg_name = #arg_value_or_default;
         })
-    };
+    }
+}
 
-    /// Replace `Self`, remove lifetime and get mutability from the type
-    fn preprocess_tref(
-        tref: &syn::TypeReference,
-        self_: Option<&syn::Type>,
-    ) -> (syn::TypeReference, Option<syn::token::Mut>) {
-        let mut tref = tref.to_owned();
-        if let Some(syn::Type::Path(tpath)) = self_ {
-            replace_self(&mut tref, &tpath.path);
-        }
-        tref.lifetime = None;
-        let mut_ = tref.mutability;
-        (tref, mut_)
+/// Replace `Self`, remove lifetime and get mutability from the type
+fn preprocess_tref(
+    tref: &syn::TypeReference,
+    self_: Option<&syn::Type>,
+) -> (syn::TypeReference, Option<syn::token::Mut>) {
+    let mut tref = tref.to_owned();
+    if let Some(syn::Type::Path(tpath)) = self_ {
+        replace_self(&mut tref, &tpath.path);
     }
+    tref.lifetime = None;
+    let mut_ = tref.mutability;
+    (tref, mut_)
+}
 
-    /// Replace `Self` with the exact type name since it is used out of the impl block
-    fn replace_self(tref: &mut syn::TypeReference, self_path: &syn::Path) {
-        match &mut *tref.elem {
-            syn::Type::Reference(tref_inner) => replace_self(tref_inner, self_path),
-            syn::Type::Path(tpath) => {
-                if let Some(ident) = tpath.path.get_ident() {
-                    if ident == "Self" {
-                        tpath.path = self_path.to_owned();
-                    }
+/// Replace `Self` with the exact type name since it is used out of the impl block
+fn replace_self(tref: &mut syn::TypeReference, self_path: &syn::Path) {
+    match &mut *tref.elem {
+        syn::Type::Reference(tref_inner) => replace_self(tref_inner, self_path),
+        syn::Type::Path(tpath) => {
+            if let Some(ident) = tpath.path.get_ident() {
+                if ident == "Self" {
+                    tpath.path = self_path.to_owned();
                 }
             }
-            _ => {}
         }
+        _ => {}
     }
 }
 
@@ -704,6 +802,262 @@ pub fn impl_py_method_def_call(
     })
 }
 
+/// Returns the `tp_richcompare` operator implemented by a comparison method, e.g. `Py_EQ`
+pub fn richcompare_op(python_name: &syn::Ident) -> Option<syn::Ident> {
+    let op = match python_name.to_string().as_str() {
+        "__lt__" => "Py_LT",
+        "__le__" => "Py_LE",
+        "__eq__" => "Py_EQ",
+        "__ne__" => "Py_NE",
+        "__gt__" => "Py_GT",
+        "__ge__" => "Py_GE",
+        _ => return None,
+    };
+    Some(syn::Ident::new(op, Span::call_site()))
+}
+
+/// Generate the match arm of the `tp_richcompare` wrapper which calls a comparison method
+pub fn impl_py_richcompare_arm(
+    cls: &syn::Type,
+    spec: &FnSpec,
+    self_ty: &SelfType,
+    op: &syn::Ident,
+) -> Result<TokenStream> {
+    ensure_spanned!(
+        spec.args.iter().filter(|arg| !arg.py).count() == 1,
+        spec.name.span() => "comparison methods must take exactly one argument besides `self`"
+    );
+
+    let slf = self_ty.receiver(cls);
+    let body = impl_call(cls, spec);
+    let deprecations = &spec.deprecations;
+    let param_conversion = spec.args.iter().enumerate().map(|(idx, arg)| {
+        let arg_name = syn::Ident::new(&format!("arg{}", idx), Span::call_site());
+        if arg.py {
+            return quote! { let #arg_name = _py; };
+        }
+        let extract = if let Some(FromPyWithAttribute(expr_path)) = &arg.attrs.from_py_with {
+            quote_spanned! { arg.ty.span() => #expr_path(_other) }
+        } else {
+            quote_spanned! { arg.ty.span() => _other.extract() }
+        };
+        // Comparing with an object of an unsupported type is not an error in Python
+        let extract = quote_spanned! { arg.ty.span() =>
+            match #extract {
+                Ok(value) => value,
+                Err(_) => return Ok(pyo3::IntoPyPointer::into_ptr(_py.NotImplemented())),
+            }
+        };
+        if let syn::Type::Reference(tref) = arg.ty {
+            // Get &T from PyRef<T>
+            let (tref, mut_) = preprocess_tref(tref, Some(cls));
+            quote_spanned! { arg.ty.span() =>
+                let #mut_ _tmp: <#tref as pyo3::derive_utils::ExtractExt>::Target = #extract;
+                let #arg_name = &#mut_ *_tmp;
+            }
+        } else {
+            quote_spanned! { arg.ty.span() => let #arg_name = #extract; }
+        }
+    });
+
+    Ok(quote! {
+        pyo3::ffi::#op => {
+            #deprecations
+            let _other = _py.from_borrowed_ptr::<pyo3::PyAny>(_other);
+            #slf
+            #(#param_conversion)*
+            #body
+        }
+    })
+}
+
+/// Generate the `tp_richcompare` slot dispatching to the comparison methods in `arms`
+pub fn impl_py_method_def_richcompare(
+    cls: &syn::Type,
+    arms: &[TokenStream],
+    ne_from_eq: bool,
+) -> TokenStream {
+    // Like Python's `object.__ne__`, invert the result of `__eq__` if `__ne__` is not defined
+    let ne_arm = if ne_from_eq {
+        quote! {
+            pyo3::ffi::Py_NE => {
+                let _eq: pyo3::PyObject =
+                    pyo3::Py::from_owned_ptr_or_err(_py, __wrap(_slf, _other, pyo3::ffi::Py_EQ))?;
+                if _eq.as_ptr() == pyo3::ffi::Py_NotImplemented() {
+                    Ok(pyo3::IntoPyPointer::into_ptr(_eq))
+                } else {
+                    let _ne = !_eq.as_ref(_py).is_true()?;
+                    Ok(pyo3::IntoPyPointer::into_ptr(pyo3::IntoPy::<pyo3::PyObject>::into_py(_ne, _py)))
+                }
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+    quote! {
+        impl pyo3::class::impl_::PyClassRichCompareImpl<#cls> for pyo3::class::impl_::PyClassImplCollector<#cls> {
+            fn richcompare_impl(self) -> Option<pyo3::ffi::richcmpfunc> {
+                unsafe extern "C" fn __wrap(
+                    _slf: *mut pyo3::ffi::PyObject,
+                    _other: *mut pyo3::ffi::PyObject,
+                    _op: std::os::raw::c_int) -> *mut pyo3::ffi::PyObject
+                {
+                    use pyo3::AsPyPointer;
+                    pyo3::callback::handle_panic(|_py| {
+                        match _op {
+                            #(#arms)*
+                            #ne_arm
+                            _ => Ok(pyo3::IntoPyPointer::into_ptr(_py.NotImplemented())),
+                        }
+                    })
+                }
+                Some(__wrap)
+            }
+        }
+    }
+}
+
+/// Generate the argument conversions of a descriptor method, taking the arguments which are not
+/// `Python<'_>` from the wrapper's `sources` in order
+fn impl_descr_param_conversion(
+    cls: &syn::Type,
+    spec: &FnSpec,
+    sources: &[&str],
+) -> Result<Vec<TokenStream>> {
+    ensure_spanned!(
+        spec.args.iter().filter(|arg| !arg.py).count() == sources.len(),
+        spec.name.span() => format!(
+            "`{}` must take exactly {} argument(s) besides `self`",
+            spec.python_name,
+            sources.len()
+        )
+    );
+
+    let mut sources = sources
+        .iter()
+        .map(|source| syn::Ident::new(source, Span::call_site()));
+    Ok(spec
+        .args
+        .iter()
+        .enumerate()
+        .map(|(idx, arg)| {
+            let arg_name = syn::Ident::new(&format!("arg{}", idx), Span::call_site());
+            if arg.py {
+                return quote! { let #arg_name = _py; };
+            }
+            let source = sources.next().unwrap();
+            let extract = if let Some(FromPyWithAttribute(expr_path)) = &arg.attrs.from_py_with {
+                quote_spanned! { arg.ty.span() => #expr_path(#source)? }
+            } else {
+                quote_spanned! { arg.ty.span() => #source.extract()? }
+            };
+            if let syn::Type::Reference(tref) = arg.ty {
+                // Get &T from PyRef<T>
+                let (tref, mut_) = preprocess_tref(tref, Some(cls));
+                quote_spanned! { arg.ty.span() =>
+                    let #mut_ _tmp: <#tref as pyo3::derive_utils::ExtractExt>::Target = #extract;
+                    let #arg_name = &#mut_ *_tmp;
+                }
+            } else {
+                quote_spanned! { arg.ty.span() => let #arg_name = #extract; }
+            }
+        })
+        .collect())
+}
+
+/// Generate the `tp_descr_get` slot which calls a `#[get_descriptor]` method
+pub fn impl_py_method_def_descr_get(
+    cls: &syn::Type,
+    spec: &FnSpec,
+    self_ty: &SelfType,
+) -> Result<TokenStream> {
+    let slf = self_ty.receiver(cls);
+    let param_conversion = impl_descr_param_conversion(cls, spec, &["_obj", "_objtype"])?;
+    let body = impl_call(cls, spec);
+    let deprecations = &spec.deprecations;
+    Ok(quote! {
+        impl pyo3::class::impl_::PyClassDescrGetImpl<#cls> for pyo3::class::impl_::PyClassImplCollector<#cls> {
+            fn descr_get_impl(self) -> Option<pyo3::ffi::descrgetfunc> {
+                unsafe extern "C" fn __wrap(
+                    _slf: *mut pyo3::ffi::PyObject,
+                    _obj: *mut pyo3::ffi::PyObject,
+                    _objtype: *mut pyo3::ffi::PyObject) -> *mut pyo3::ffi::PyObject
+                {
+                    #deprecations
+                    pyo3::callback::handle_panic(|_py| {
+                        #slf
+                        // Python passes NULL for the instance when the descriptor is looked up on
+                        // the owning class itself, and may omit the owner
+                        let _obj = _py.from_borrowed_ptr::<pyo3::PyAny>(
+                            if _obj.is_null() { pyo3::ffi::Py_None() } else { _obj }
+                        );
+                        let _objtype = _py.from_borrowed_ptr::<pyo3::PyAny>(
+                            if _objtype.is_null() { pyo3::ffi::Py_None() } else { _objtype }
+                        );
+                        #(#param_conversion)*
+                        #body
+                    })
+                }
+                Some(__wrap)
+            }
+        }
+    })
+}
+
+/// Generate the part of the `tp_descr_set` wrapper which calls a `#[set_descriptor]` or
+/// `#[delete_descriptor]` method
+pub fn impl_py_descr_set_arm(
+    cls: &syn::Type,
+    spec: &FnSpec,
+    self_ty: &SelfType,
+    sources: &[&str],
+) -> Result<TokenStream> {
+    let slf = self_ty.receiver(cls);
+    let param_conversion = impl_descr_param_conversion(cls, spec, sources)?;
+    let body = impl_call(cls, spec);
+    let deprecations = &spec.deprecations;
+    Ok(quote! {{
+        #deprecations
+        #slf
+        #(#param_conversion)*
+        #body
+    }})
+}
+
+/// Generate the `tp_descr_set` slot, which Python calls with a NULL value to delete the attribute
+pub fn impl_py_method_def_descr_set(
+    cls: &syn::Type,
+    set_arm: Option<&TokenStream>,
+    delete_arm: Option<&TokenStream>,
+) -> TokenStream {
+    let set_arm = set_arm.cloned().unwrap_or_else(|| {
+        quote! { Err(pyo3::exceptions::PyAttributeError::new_err("__set__")) }
+    });
+    let delete_arm = delete_arm.cloned().unwrap_or_else(|| {
+        quote! { Err(pyo3::exceptions::PyAttributeError::new_err("__delete__")) }
+    });
+    quote! {
+        impl pyo3::class::impl_::PyClassDescrSetImpl<#cls> for pyo3::class::impl_::PyClassImplCollector<#cls> {
+            fn descr_set_impl(self) -> Option<pyo3::ffi::descrsetfunc> {
+                unsafe extern "C" fn __wrap(
+                    _slf: *mut pyo3::ffi::PyObject,
+                    _obj: *mut pyo3::ffi::PyObject,
+                    _value: *mut pyo3::ffi::PyObject) -> std::os::raw::c_int
+                {
+                    pyo3::callback::handle_panic(|_py| {
+                        let _obj = _py.from_borrowed_ptr::<pyo3::PyAny>(_obj);
+                        match _py.from_borrowed_ptr_or_opt::<pyo3::PyAny>(_value) {
+                            Some(_value) => #set_arm,
+                            None => #delete_arm,
+                        }
+                    })
+                }
+                Some(__wrap)
+            }
+        }
+    }
+}
+
 pub(crate) fn impl_py_setter_def(
     cls: &syn::Type,
     property_type: PropertyType,
@@ -712,8 +1066,8 @@ pub(crate) fn impl_py_setter_def(
     deprecations: &Deprecations,
 ) -> Result<TokenStream> {
     let python_name = match property_type {
-        PropertyType::Descriptor(ident) => {
-            let formatted_name = format!("{}\0", ident.unraw());
+        PropertyType::Descriptor { python_name, .. } => {
+            let formatted_name = format!("{}\0", python_name);
             quote!(#formatted_name)
         }
         PropertyType::Function(spec) => spec.null_terminated_python_name(),
@@ -739,8 +1093,8 @@ pub(crate) fn impl_py_getter_def(
     deprecations: &Deprecations,
 ) -> Result<TokenStream> {
     let python_name = match property_type {
-        PropertyType::Descriptor(ident) => {
-            let formatted_name = format!("{}\0", ident.unraw());
+        PropertyType::Descriptor { python_name, .. } => {
+            let formatted_name = format!("{}\0", python_name);
             quote!(#formatted_name)
         }
         PropertyType::Function(spec) => spec.null_terminated_python_name(),
@@ -760,11 +1114,7 @@ pub(crate) fn impl_py_getter_def(
 
 /// Split an argument of pyo3::Python from the front of the arg list, if present
 fn split_off_python_arg<'a>(args: &'a [FnArg<'a>]) -> (Option<&FnArg>, &[FnArg]) {
-    if args
-        .get(0)
-        .map(|py| utils::is_python(&py.ty))
-        .unwrap_or(false)
-    {
+    if args.get(0).map(|py| py.py).unwrap_or(false) {
         (Some(&args[0]), &args[1..])
     } else {
         (None, args)
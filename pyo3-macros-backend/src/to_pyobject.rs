@@ -0,0 +1,239 @@
+// Copyright (c) 2017-present PyO3 Project and Contributors
+//! Code generation for `#[derive(ToPyObject)]`.
+
+use crate::attributes::NameAttribute;
+use crate::from_pyobject::{ContainerOptions, FieldGetter, FieldPyO3Attributes};
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{
+    ext::IdentExt, parse_quote, punctuated::Punctuated, spanned::Spanned, token::Comma, DataEnum,
+    DeriveInput, Fields, Ident, Result,
+};
+
+/// The key under which the variant name is stored for enums with fields.
+const VARIANT_KEY: &str = "type";
+
+/// Returns the key of a named field in the generated `dict`.
+fn field_key(ident: &Ident, attrs: &FieldPyO3Attributes) -> Result<TokenStream> {
+    match &attrs.getter {
+        FieldGetter::GetItem(Some(key)) => Ok(quote!(#key)),
+        FieldGetter::GetAttr(Some(name)) => bail_spanned!(
+            name.span() => "`attribute(\"...\")` is not supported by ToPyObject; use `item(\"...\")` to rename the key"
+        ),
+        _ => {
+            let name = ident.unraw().to_string();
+            Ok(quote!(#name))
+        }
+    }
+}
+
+/// Builds a `dict` from pairs of keys and references to values.
+fn build_dict(items: &[(TokenStream, TokenStream)]) -> TokenStream {
+    let set_items = items.iter().map(|(key, value)| {
+        quote! {
+            dict.set_item(#key, #value).expect("Failed to set_item on dict");
+        }
+    });
+    quote!({
+        let dict = pyo3::types::PyDict::new(py);
+        #(#set_items)*
+        dict.into()
+    })
+}
+
+/// Builds the conversion of a struct, whose fields are accessed through `self`.
+fn build_struct(fields: &Fields, options: &ContainerOptions) -> Result<TokenStream> {
+    ensure_spanned!(
+        !fields.is_empty(),
+        fields.span() => "cannot derive ToPyObject for empty structs"
+    );
+    if options.transparent {
+        ensure_spanned!(
+            fields.len() == 1,
+            fields.span() => "transparent structs can only have 1 field"
+        );
+    }
+    Ok(match fields {
+        Fields::Named(named) if options.transparent => {
+            let ident = named.named[0].ident.as_ref().unwrap();
+            quote!(pyo3::ToPyObject::to_object(&self.#ident, py))
+        }
+        Fields::Named(named) => {
+            let mut items = Vec::new();
+            for field in named.named.iter() {
+                let ident = field.ident.as_ref().unwrap();
+                let attrs = FieldPyO3Attributes::from_attrs(&field.attrs)?;
+                items.push((field_key(ident, &attrs)?, quote!(&self.#ident)));
+            }
+            build_dict(&items)
+        }
+        Fields::Unnamed(unnamed) if unnamed.unnamed.len() == 1 => {
+            quote!(pyo3::ToPyObject::to_object(&self.0, py))
+        }
+        Fields::Unnamed(unnamed) => {
+            let elements = (0..unnamed.unnamed.len()).map(|i| {
+                let index = syn::Index::from(i);
+                quote!(pyo3::ToPyObject::to_object(&self.#index, py))
+            });
+            quote!(pyo3::types::PyTuple::new(py, &[#(#elements),*]).into())
+        }
+        Fields::Unit => unreachable!(), // covered by the check above
+    })
+}
+
+/// Returns the integer type of the discriminants from `#[repr(...)]`, which defaults to `isize`.
+fn discriminant_type(attrs: &[syn::Attribute]) -> Result<Ident> {
+    const INT_TYPES: &[&str] = &[
+        "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+    ];
+    for attr in attrs {
+        if attr.path.is_ident("repr") {
+            let reprs: Punctuated<Ident, Comma> =
+                attr.parse_args_with(Punctuated::parse_terminated)?;
+            if let Some(int_type) = reprs.into_iter().find(|repr| {
+                let repr = repr.to_string();
+                INT_TYPES.contains(&repr.as_str())
+            }) {
+                return Ok(int_type);
+            }
+        }
+    }
+    Ok(Ident::new("isize", Span::call_site()))
+}
+
+/// Builds the conversion of an enum, matching on `self`.
+fn build_enum(data_enum: &DataEnum, tokens: &DeriveInput) -> Result<TokenStream> {
+    let ident = &tokens.ident;
+    ensure_spanned!(
+        !data_enum.variants.is_empty(),
+        ident.span() => "cannot derive ToPyObject for empty enum"
+    );
+    let unit_only = data_enum
+        .variants
+        .iter()
+        .all(|variant| match variant.fields {
+            Fields::Unit => true,
+            _ => false,
+        });
+    let has_discriminants = data_enum
+        .variants
+        .iter()
+        .any(|variant| variant.discriminant.is_some());
+
+    let int_type = discriminant_type(&tokens.attrs)?;
+
+    let mut arms = Vec::new();
+    for variant in &data_enum.variants {
+        let var_ident = &variant.ident;
+        let options = ContainerOptions::from_attrs(&variant.attrs)?;
+        let name = match &options.name {
+            Some(NameAttribute(name)) => name.to_string(),
+            None => var_ident.unraw().to_string(),
+        };
+
+        let arm = if unit_only && has_discriminants {
+            quote! {
+                #ident::#var_ident => pyo3::ToPyObject::to_object(&(#ident::#var_ident as #int_type), py)
+            }
+        } else if unit_only {
+            quote! {
+                #ident::#var_ident => pyo3::types::PyString::new(py, #name).into()
+            }
+        } else {
+            let mut items = vec![(quote!(#VARIANT_KEY), quote!(#name))];
+            let pattern = match &variant.fields {
+                Fields::Named(named) => {
+                    // Fields are bound to generated names, so that they can't shadow `py` or
+                    // `dict` in the generated code
+                    let mut bindings = Vec::new();
+                    for (i, field) in named.named.iter().enumerate() {
+                        let field_ident = field.ident.as_ref().unwrap();
+                        let binding = Ident::new(&format!("__pyo3_field_{}", i), Span::call_site());
+                        let attrs = FieldPyO3Attributes::from_attrs(&field.attrs)?;
+                        let has_key = match attrs.getter {
+                            FieldGetter::GetItem(Some(_)) => true,
+                            _ => false,
+                        };
+                        ensure_spanned!(
+                            has_key || field_ident.unraw() != VARIANT_KEY,
+                            field_ident.span() => "field `type` conflicts with the key of the \
+                                variant name; use `#[pyo3(item(\"...\"))]` to rename it"
+                        );
+                        items.push((field_key(field_ident, &attrs)?, quote!(#binding)));
+                        bindings.push(quote!(#field_ident: #binding));
+                    }
+                    quote!(#ident::#var_ident { #(#bindings),* })
+                }
+                Fields::Unnamed(unnamed) => {
+                    let mut bindings = Vec::new();
+                    for i in 0..unnamed.unnamed.len() {
+                        let binding = Ident::new(&format!("_{}", i), Span::call_site());
+                        let key = i.to_string();
+                        items.push((quote!(#key), quote!(#binding)));
+                        bindings.push(binding);
+                    }
+                    quote!(#ident::#var_ident(#(#bindings),*))
+                }
+                Fields::Unit => quote!(#ident::#var_ident),
+            };
+            let dict = build_dict(&items);
+            quote!(#pattern => #dict)
+        };
+        arms.push(arm);
+    }
+
+    Ok(quote! {
+        match self {
+            #(#arms),*
+        }
+    })
+}
+
+/// Derive ToPyObject for enums and structs.
+///
+///   * Structs with named fields convert to a `dict` of their fields, keyed by field name or by
+///     the key given with `#[pyo3(item("key"))]`
+///   * Newtype structs and `#[pyo3(transparent)]` structs convert their only field
+///   * Tuple structs convert to a `tuple`
+///   * Enums with only unit variants convert to the variant name, or to the value of the variant
+///     if any variant has an explicit discriminant
+///   * Other enums convert to a `dict` of the variant's fields, with the variant name under
+///     `"type"`, and tuple fields keyed by their index
+///   * `#[pyo3(name = "...")]` changes the name of a variant
+///   * Derivation for structs with generic fields like `struct<T> Foo(T)`
+///     adds `T: ToPyObject` on the derived implementation.
+pub fn build_derive_to_pyobject(tokens: &DeriveInput) -> Result<TokenStream> {
+    let mut generics = tokens.generics.clone();
+    let where_clause = generics.make_where_clause();
+    for param in tokens.generics.type_params() {
+        let gen_ident = &param.ident;
+        where_clause
+            .predicates
+            .push(parse_quote!(#gen_ident: pyo3::ToPyObject))
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let body = match &tokens.data {
+        syn::Data::Enum(en) => build_enum(en, tokens)?,
+        syn::Data::Struct(st) => {
+            let options = ContainerOptions::from_attrs(&tokens.attrs)?;
+            if let Some(NameAttribute(name)) = &options.name {
+                bail_spanned!(name.span() => "`name` is unsupported for structs");
+            }
+            build_struct(&st.fields, &options)?
+        }
+        syn::Data::Union(_) => bail_spanned!(
+            tokens.span() => "#[derive(ToPyObject)] is not supported for unions"
+        ),
+    };
+
+    let ident = &tokens.ident;
+    Ok(quote!(
+        #[automatically_derived]
+        impl #impl_generics pyo3::ToPyObject for #ident #ty_generics #where_clause {
+            fn to_object(&self, py: pyo3::Python) -> pyo3::PyObject {
+                #body
+            }
+        }
+    ))
+}
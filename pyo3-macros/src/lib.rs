@@ -7,9 +7,9 @@ extern crate proc_macro;
 
 use proc_macro::TokenStream;
 use pyo3_macros_backend::{
-    build_derive_from_pyobject, build_py_class, build_py_function, build_py_methods,
-    build_py_proto, get_doc, process_functions_in_module, py_init, PyClassArgs, PyClassMethodsType,
-    PyFunctionOptions,
+    build_derive_from_pyobject, build_derive_py_error, build_derive_to_pyobject, build_py_class,
+    build_py_enum, build_py_function, build_py_methods, build_py_proto, get_doc,
+    process_functions_in_module, py_init, PyClassArgs, PyClassMethodsType, PyFunctionOptions,
 };
 use quote::quote;
 use syn::parse_macro_input;
@@ -245,15 +245,42 @@ pub fn derive_from_py_object(item: TokenStream) -> TokenStream {
     .into()
 }
 
+#[proc_macro_derive(ToPyObject, attributes(pyo3))]
+pub fn derive_to_py_object(item: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(item as syn::DeriveInput);
+    let expanded = build_derive_to_pyobject(&ast).unwrap_or_else(|e| e.to_compile_error());
+    quote!(
+        #expanded
+    )
+    .into()
+}
+
+#[proc_macro_derive(PyError, attributes(pyerror))]
+pub fn derive_py_error(item: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(item as syn::DeriveInput);
+    let expanded = build_derive_py_error(&ast).unwrap_or_else(|e| e.to_compile_error());
+    quote!(
+        #expanded
+    )
+    .into()
+}
+
 fn pyclass_impl(
     attr: TokenStream,
     input: TokenStream,
     methods_type: PyClassMethodsType,
 ) -> TokenStream {
-    let mut ast = parse_macro_input!(input as syn::ItemStruct);
+    let mut ast = parse_macro_input!(input as syn::Item);
     let args = parse_macro_input!(attr as PyClassArgs);
-    let expanded =
-        build_py_class(&mut ast, &args, methods_type).unwrap_or_else(|e| e.to_compile_error());
+    let expanded = match &mut ast {
+        syn::Item::Struct(struct_) => build_py_class(struct_, &args, methods_type),
+        syn::Item::Enum(enum_) => build_py_enum(enum_, &args, methods_type),
+        item => Err(syn::Error::new_spanned(
+            item,
+            "#[pyclass] can only be used on structs and enums",
+        )),
+    }
+    .unwrap_or_else(|e| e.to_compile_error());
 
     quote!(
         #ast
@@ -189,8 +189,13 @@ impl<T: Element> PyBuffer<T> {
         };
         // Create PyBuffer immediately so that if validation checks fail, the PyBuffer::drop code
         // will call PyBuffer_Release (thus avoiding any leaks).
-        let buf = PyBuffer(Pin::from(buf), PhantomData);
+        PyBuffer(Pin::from(buf), PhantomData).validate()
+    }
 
+    /// Checks that the buffer has a shape and strides, and that its contents are compatible
+    /// with `T`.
+    fn validate(self) -> PyResult<PyBuffer<T>> {
+        let buf = self;
         if buf.0.shape.is_null() {
             Err(PyBufferError::new_err("shape is null"))
         } else if buf.0.strides.is_null() {
@@ -590,6 +595,106 @@ impl<T> Drop for PyBuffer<T> {
     }
 }
 
+/// A C-contiguous buffer which can be used as a Rust slice while the GIL is held.
+///
+/// Unlike [`PyBuffer`], `SafeBuffer` is bound to the lifetime of the GIL, so it dereferences to
+/// `[T]` without needing a `Python` token. The buffer is released when the `SafeBuffer` is dropped.
+///
+/// Creating a `SafeBuffer` is unsafe, because Python buffers may be modified while they are
+/// borrowed. The caller must make sure that nothing modifies the contents of the buffer while the
+/// `SafeBuffer` exists; for example, `bytes` objects are immutable, but `bytearray`s may be
+/// modified by Python code.
+///
+/// # Examples
+///
+/// ```
+/// use pyo3::buffer::SafeBuffer;
+/// use pyo3::prelude::*;
+///
+/// Python::with_gil(|py| -> PyResult<()> {
+///     let bytes = py.eval("b'abc'", None, None)?;
+///     // Safety: bytes objects are immutable
+///     let buffer: SafeBuffer<u8> = unsafe { SafeBuffer::get(bytes)? };
+///     assert_eq!(&*buffer, b"abc");
+///     Ok(())
+/// })
+/// .unwrap();
+/// ```
+pub struct SafeBuffer<'py, T> {
+    buffer: PyBuffer<T>,
+    _py: PhantomData<Python<'py>>,
+}
+
+impl<'py, T: Element> SafeBuffer<'py, T> {
+    /// Gets a C-contiguous buffer of `T` from the specified Python object.
+    ///
+    /// # Safety
+    ///
+    /// The contents of the buffer must not be modified while the `SafeBuffer` exists.
+    pub unsafe fn get(obj: &'py PyAny) -> PyResult<Self> {
+        Self::from_buffer(PyBuffer::get(obj)?)
+    }
+
+    /// Takes ownership of a `Py_buffer` which has been filled in by `PyObject_GetBuffer`.
+    ///
+    /// The view is released when the `SafeBuffer` is dropped, or if it is not a C-contiguous
+    /// buffer of `T`.
+    ///
+    /// # Safety
+    ///
+    /// `view` must have been filled in by a successful call to `PyObject_GetBuffer`, with flags
+    /// which include `PyBUF_FORMAT` and `PyBUF_STRIDES`, and must not have been released. The
+    /// contents of the buffer must not be modified while the `SafeBuffer` exists.
+    pub unsafe fn from_raw(_py: Python<'py>, view: Pin<Box<ffi::Py_buffer>>) -> PyResult<Self> {
+        Self::from_buffer(PyBuffer(view, PhantomData).validate()?)
+    }
+
+    fn from_buffer(buffer: PyBuffer<T>) -> PyResult<Self> {
+        if buffer.is_c_contiguous() {
+            Ok(SafeBuffer {
+                buffer,
+                _py: PhantomData,
+            })
+        } else {
+            Err(PyBufferError::new_err("buffer is not C-contiguous"))
+        }
+    }
+
+    /// Returns the underlying `Py_buffer`.
+    pub fn as_raw(&self) -> &ffi::Py_buffer {
+        &self.buffer.0
+    }
+
+    /// Returns the underlying `Py_buffer` without releasing it.
+    ///
+    /// The caller becomes responsible for calling `PyBuffer_Release` on the view.
+    pub fn into_raw(self) -> Pin<Box<ffi::Py_buffer>> {
+        let buffer = mem::ManuallyDrop::new(self.buffer);
+        // Safety: the PyBuffer is never used or dropped again
+        unsafe { ptr::read(&buffer.0) }
+    }
+}
+
+impl<'py, T: Element> std::ops::Deref for SafeBuffer<'py, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        let len = self.buffer.item_count();
+        if len == 0 {
+            &[]
+        } else {
+            // Safety: the buffer is C-contiguous with compatible, aligned elements
+            unsafe { slice::from_raw_parts(self.buffer.0.buf as *const T, len) }
+        }
+    }
+}
+
+impl<'py, T: Element + Debug> Debug for SafeBuffer<'py, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("SafeBuffer").field(&&**self).finish()
+    }
+}
+
 /// Like `std::mem::cell`, but only provides read-only access to the data.
 ///
 /// `&ReadOnlyCell<T>` is basically a safe version of `*const T`:
@@ -639,9 +744,9 @@ impl_element!(f64, Float);
 
 #[cfg(test)]
 mod test {
-    use super::PyBuffer;
+    use super::{PyBuffer, SafeBuffer};
     use crate::ffi;
-    use crate::Python;
+    use crate::{AsPyPointer, Python};
 
     #[test]
     fn test_compatible_size() {
@@ -680,6 +785,46 @@ mod test {
         assert_eq!(buffer.to_vec(py).unwrap(), b"abcde");
     }
 
+    #[test]
+    fn test_safe_buffer() {
+        Python::with_gil(|py| {
+            let bytes = py.eval("b'abcde'", None, None).unwrap();
+            let buffer: SafeBuffer<u8> = unsafe { SafeBuffer::get(bytes).unwrap() };
+            assert_eq!(&*buffer, b"abcde");
+            assert_eq!(buffer.as_raw().len, 5);
+
+            let array = py
+                .eval("__import__('array').array('i', [1, 2, 3])", None, None)
+                .unwrap();
+            let buffer: SafeBuffer<i32> = unsafe { SafeBuffer::get(array).unwrap() };
+            assert_eq!(&*buffer, [1, 2, 3]);
+            assert!(unsafe { SafeBuffer::<u64>::get(array) }.is_err());
+
+            let empty = py.eval("b''", None, None).unwrap();
+            assert!(unsafe { SafeBuffer::<u8>::get(empty).unwrap() }.is_empty());
+
+            let strided = py.eval("memoryview(b'abcde')[::2]", None, None).unwrap();
+            assert!(unsafe { SafeBuffer::<u8>::get(strided) }.is_err());
+        });
+    }
+
+    #[test]
+    fn test_safe_buffer_raw() {
+        Python::with_gil(|py| {
+            let bytes = py.eval("b'abc'", None, None).unwrap();
+            let mut view = Box::pin(unsafe { std::mem::zeroed::<ffi::Py_buffer>() });
+            let result =
+                unsafe { ffi::PyObject_GetBuffer(bytes.as_ptr(), &mut *view, ffi::PyBUF_FULL_RO) };
+            assert_eq!(result, 0);
+            let buffer: SafeBuffer<u8> = unsafe { SafeBuffer::from_raw(py, view).unwrap() };
+            assert_eq!(&*buffer, b"abc");
+
+            let mut view = buffer.into_raw();
+            assert_eq!(view.obj, bytes.as_ptr());
+            unsafe { ffi::PyBuffer_Release(&mut *view) };
+        });
+    }
+
     #[allow(clippy::float_cmp)] // The test wants to ensure that no precision was lost on the Python round-trip
     #[test]
     fn test_array_buffer() {
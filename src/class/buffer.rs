@@ -5,8 +5,10 @@
 //! For more information check [buffer protocol](https://docs.python.org/3/c-api/buffer.html)
 //! c-api
 use crate::callback::IntoPyCallbackOutput;
-use crate::{ffi, PyCell, PyClass, PyRefMut};
-use std::os::raw::c_int;
+use crate::exceptions::PyBufferError;
+use crate::{ffi, AsPyPointer, PyCell, PyClass, PyRef, PyRefMut};
+use std::os::raw::{c_int, c_void};
+use std::ptr;
 
 /// Buffer protocol interface
 ///
@@ -58,3 +60,152 @@ where
         T::bf_releasebuffer(slf.try_borrow_mut()?, arg1).convert(py)
     })
 }
+
+/// Describes the memory exposed through the buffer protocol by a `#[pyclass(buffer)]` type.
+///
+/// The buffer is C-contiguous. Unless `shape` is given, it is one-dimensional and contains
+/// `len / itemsize` items.
+pub struct BufferInfo {
+    /// Pointer to the start of the buffer.
+    pub ptr: *mut c_void,
+    /// Length of the buffer in bytes.
+    pub len: usize,
+    /// Size of a single item in bytes.
+    pub itemsize: usize,
+    /// The [`struct` module](https://docs.python.org/3/library/struct.html) format of a single
+    /// item, as a nul-terminated string, e.g. `"B\0"` for unsigned bytes.
+    pub format: &'static str,
+    /// Whether Python is forbidden from writing to the buffer.
+    pub readonly: bool,
+    /// The number of items in each dimension, for buffers with more than one dimension. The
+    /// product of the dimensions must equal `len / itemsize`.
+    pub shape: Option<Vec<usize>>,
+}
+
+impl BufferInfo {
+    /// Describes a read-only buffer of unsigned bytes.
+    pub fn bytes(data: &[u8]) -> Self {
+        BufferInfo {
+            ptr: data.as_ptr() as *mut c_void,
+            len: data.len(),
+            itemsize: 1,
+            format: "B\0",
+            readonly: true,
+            shape: None,
+        }
+    }
+
+    /// Sets the shape of a multi-dimensional buffer, with the items laid out in C order.
+    pub fn with_shape(mut self, shape: Vec<usize>) -> Self {
+        self.shape = Some(shape);
+        self
+    }
+}
+
+/// Buffer protocol support for `#[pyclass(buffer)]` types.
+///
+/// While a Python buffer is exported, the object stays immutably borrowed, so methods taking
+/// `&mut self` will fail with `RuntimeError` until the buffer is released.
+///
+/// # Safety
+/// The memory described by the returned [`BufferInfo`] must be valid for as long as `self` is
+/// immutably borrowed. Unless `readonly` is set, it must also be safe for Python to write to it
+/// during that time.
+pub unsafe trait PyBufferInfo: PyClass {
+    fn __buffer_info__(&self) -> BufferInfo;
+}
+
+#[doc(hidden)]
+pub unsafe extern "C" fn getbuffer_info<T>(
+    slf: *mut ffi::PyObject,
+    view: *mut ffi::Py_buffer,
+    flags: c_int,
+) -> c_int
+where
+    T: PyBufferInfo,
+{
+    crate::callback::handle_panic(|py| {
+        if view.is_null() {
+            return Err(PyBufferError::new_err("View is null"));
+        }
+        let slf = py.from_borrowed_ptr::<PyCell<T>>(slf);
+        let borrow = slf.try_borrow()?;
+        let info = borrow.__buffer_info__();
+
+        if !info.format.ends_with('\0') {
+            return Err(PyBufferError::new_err(
+                "buffer format string is not nul-terminated",
+            ));
+        }
+        if info.itemsize == 0 || info.len % info.itemsize != 0 {
+            return Err(PyBufferError::new_err(
+                "buffer length is not a multiple of the item size",
+            ));
+        }
+        let item_count = info.len / info.itemsize;
+        let shape = info.shape.unwrap_or_else(|| vec![item_count]);
+        if shape.iter().product::<usize>() != item_count {
+            return Err(PyBufferError::new_err(
+                "buffer shape does not match the number of items",
+            ));
+        }
+        if info.readonly && (flags & ffi::PyBUF_WRITABLE) == ffi::PyBUF_WRITABLE {
+            return Err(PyBufferError::new_err("Object is not writable"));
+        }
+
+        (*view).obj = slf.as_ptr();
+        ffi::Py_INCREF((*view).obj);
+
+        (*view).buf = info.ptr;
+        (*view).len = info.len as ffi::Py_ssize_t;
+        (*view).readonly = info.readonly as c_int;
+        (*view).itemsize = info.itemsize as ffi::Py_ssize_t;
+
+        (*view).format = ptr::null_mut();
+        if (flags & ffi::PyBUF_FORMAT) == ffi::PyBUF_FORMAT {
+            (*view).format = info.format.as_ptr() as *mut _;
+        }
+
+        // The shape followed by the C-contiguous strides are stored in `internal`, so that
+        // `shape` and `strides` can point into it until the buffer is released.
+        let ndim = shape.len();
+        let mut dims: Vec<ffi::Py_ssize_t> = shape.iter().map(|&n| n as ffi::Py_ssize_t).collect();
+        let mut stride = info.itemsize as ffi::Py_ssize_t;
+        let mut strides = vec![0; ndim];
+        for i in (0..ndim).rev() {
+            strides[i] = stride;
+            stride *= dims[i];
+        }
+        dims.extend(strides);
+        let dims = Box::into_raw(Box::new(dims));
+        (*view).internal = dims as *mut c_void;
+        (*view).ndim = ndim as c_int;
+        (*view).shape = ptr::null_mut();
+        if (flags & ffi::PyBUF_ND) == ffi::PyBUF_ND {
+            (*view).shape = (*dims).as_mut_ptr();
+        }
+
+        (*view).strides = ptr::null_mut();
+        if (flags & ffi::PyBUF_STRIDES) == ffi::PyBUF_STRIDES {
+            (*view).strides = (*dims).as_mut_ptr().add(ndim);
+        }
+        (*view).suboffsets = ptr::null_mut();
+
+        // Keep the object borrowed until the buffer is released.
+        std::mem::forget(borrow);
+        Ok(0)
+    })
+}
+
+#[doc(hidden)]
+pub unsafe extern "C" fn releasebuffer_info<T>(slf: *mut ffi::PyObject, view: *mut ffi::Py_buffer)
+where
+    T: PyBufferInfo,
+{
+    crate::callback::handle_panic(|py| {
+        let slf = py.from_borrowed_ptr::<PyCell<T>>(slf);
+        drop(Box::from_raw((*view).internal as *mut Vec<ffi::Py_ssize_t>));
+        PyRef::release_forgotten(slf);
+        Ok(())
+    })
+}
@@ -5,7 +5,9 @@
 //!
 
 use crate::callback::IntoPyCallbackOutput;
-use crate::{PyClass, PyObject};
+use crate::exceptions::{PyBaseException, PyTypeError};
+use crate::types::{PyDict, PyTuple, PyType};
+use crate::{ffi, PyAny, PyCell, PyClass, PyObject, PyResult, Python};
 
 /// Context manager interface
 #[allow(unused_variables)]
@@ -48,3 +50,112 @@ pub trait PyContextExitProtocol<'p>: PyContextProtocol<'p> {
     type Traceback: crate::FromPyObject<'p>;
     type Result: IntoPyCallbackOutput<PyObject>;
 }
+
+/// Context manager interface for `#[pyclass(context_manager)]`.
+///
+/// With the `context_manager` flag, `#[pyclass]` exposes the implementation of this trait to
+/// Python as `__enter__` and `__exit__`, so that the class can be used in a `with` statement.
+///
+/// # Examples
+///
+/// ```
+/// use pyo3::class::context::PyContextManager;
+/// use pyo3::exceptions::{PyBaseException, PyValueError};
+/// use pyo3::prelude::*;
+/// use pyo3::types::PyType;
+///
+/// #[pyclass(context_manager)]
+/// struct Transaction {
+///     committed: bool,
+/// }
+///
+/// impl PyContextManager for Transaction {
+///     fn __enter__(&mut self, py: Python) -> PyResult<PyObject> {
+///         Ok(py.None())
+///     }
+///
+///     fn __exit__(
+///         &mut self,
+///         exc_type: Option<&PyType>,
+///         _exc_value: Option<&PyBaseException>,
+///         _traceback: Option<&PyAny>,
+///     ) -> PyResult<bool> {
+///         // Commit if the block completed, and suppress `ValueError`s
+///         self.committed = exc_type.is_none();
+///         match exc_type {
+///             Some(ty) => ty.is_subclass::<PyValueError>(),
+///             None => Ok(false),
+///         }
+///     }
+/// }
+///
+/// Python::with_gil(|py| {
+///     let transaction = PyCell::new(py, Transaction { committed: false }).unwrap();
+///     pyo3::py_run!(py, transaction, "with transaction: raise ValueError()");
+///     assert!(!transaction.borrow().committed);
+///     pyo3::py_run!(py, transaction, "with transaction: pass");
+///     assert!(transaction.borrow().committed);
+/// });
+/// ```
+pub trait PyContextManager: PyClass {
+    /// Called when entering the `with` block. The returned object is bound to the `as` target.
+    fn __enter__(&mut self, py: Python) -> PyResult<PyObject>;
+
+    /// Called when leaving the `with` block.
+    ///
+    /// If the block raised an exception, the arguments are its type, value and traceback,
+    /// otherwise they are all `None`. Returning `true` suppresses the exception.
+    fn __exit__(
+        &mut self,
+        exc_type: Option<&PyType>,
+        exc_value: Option<&PyBaseException>,
+        traceback: Option<&PyAny>,
+    ) -> PyResult<bool>;
+}
+
+#[doc(hidden)]
+pub unsafe extern "C" fn enter<T>(
+    slf: *mut ffi::PyObject,
+    _args: *mut ffi::PyObject,
+) -> *mut ffi::PyObject
+where
+    T: PyContextManager,
+{
+    crate::callback::handle_panic(|py| {
+        let slf = py.from_borrowed_ptr::<PyCell<T>>(slf);
+        let result = slf.try_borrow_mut()?.__enter__(py)?;
+        crate::callback::convert(py, result)
+    })
+}
+
+#[doc(hidden)]
+pub unsafe extern "C" fn exit<T>(
+    slf: *mut ffi::PyObject,
+    args: *mut ffi::PyObject,
+    kwargs: *mut ffi::PyObject,
+) -> *mut ffi::PyObject
+where
+    T: PyContextManager,
+{
+    crate::callback::handle_panic(|py| {
+        let slf = py.from_borrowed_ptr::<PyCell<T>>(slf);
+        let args = py.from_borrowed_ptr::<PyTuple>(args);
+        let kwargs = py.from_borrowed_ptr_or_opt::<PyDict>(kwargs);
+        let has_kwargs = match kwargs {
+            Some(kwargs) => !kwargs.is_empty(),
+            None => false,
+        };
+        if args.len() != 3 || has_kwargs {
+            return Err(PyTypeError::new_err(
+                "__exit__() takes exactly 3 positional arguments",
+            ));
+        }
+        let exc_type = args.get_item(0).extract()?;
+        let exc_value = args.get_item(1).extract()?;
+        let traceback: Option<&PyAny> = args.get_item(2).extract()?;
+        let result = slf
+            .try_borrow_mut()?
+            .__exit__(exc_type, exc_value, traceback)?;
+        crate::callback::convert(py, result)
+    })
+}
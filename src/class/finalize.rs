@@ -0,0 +1,68 @@
+// Copyright (c) 2017-present PyO3 Project and Contributors
+
+//! Finalizer support for `#[pyclass(finalizer)]`
+//!
+//! For more information check [PEP 442](https://www.python.org/dev/peps/pep-0442/)
+
+use crate::{ffi, PyCell, PyClass};
+use std::ptr;
+
+/// Finalizer interface for `#[pyclass(finalizer)]`.
+///
+/// With the `finalizer` flag, `#[pyclass]` installs the implementation of this trait as the
+/// class's `tp_finalize` slot, so that it behaves like `__del__` in Python. It is called once,
+/// before the object is destroyed, both when its reference count drops to zero and when it is
+/// collected by the cyclic garbage collector.
+///
+/// If `__del__` resurrects the object by storing a new reference to it, the object isn't
+/// destroyed. As in CPython, a `gc` class isn't finalized again when that reference is released,
+/// while other classes run their finalizer each time their reference count drops to zero.
+///
+/// As in Python, errors can't be propagated out of a finalizer. If `__del__` panics, or the
+/// object is already borrowed when it is finalized, the error is reported using
+/// `sys.unraisablehook` and finalization continues.
+///
+/// # Examples
+///
+/// ```
+/// use pyo3::class::finalize::PyFinalizerProtocol;
+/// use pyo3::prelude::*;
+///
+/// #[pyclass(finalizer)]
+/// struct Connection {
+///     open: bool,
+/// }
+///
+/// impl PyFinalizerProtocol for Connection {
+///     fn __del__(&mut self) {
+///         self.open = false;
+///     }
+/// }
+/// ```
+pub trait PyFinalizerProtocol: PyClass {
+    /// Called before the object is destroyed.
+    fn __del__(&mut self);
+}
+
+#[doc(hidden)]
+pub unsafe extern "C" fn finalize<T>(slf: *mut ffi::PyObject)
+where
+    T: PyFinalizerProtocol,
+{
+    // Finalizers may run while an exception is set, which must be preserved.
+    let mut ptype = ptr::null_mut();
+    let mut pvalue = ptr::null_mut();
+    let mut ptraceback = ptr::null_mut();
+    ffi::PyErr_Fetch(&mut ptype, &mut pvalue, &mut ptraceback);
+
+    crate::callback::handle_panic(|py| {
+        let slf = py.from_borrowed_ptr::<PyCell<T>>(slf);
+        slf.try_borrow_mut()?.__del__();
+        Ok(())
+    });
+    if !ffi::PyErr_Occurred().is_null() {
+        ffi::PyErr_WriteUnraisable(slf);
+    }
+
+    ffi::PyErr_Restore(ptype, pvalue, ptraceback);
+}
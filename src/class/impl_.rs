@@ -5,9 +5,10 @@ use crate::{
     pycell::PyCellLayout,
     pyclass_init::PyObjectInit,
     type_object::{PyLayout, PyTypeObject},
-    PyClass, PyMethodDefType, PyNativeType, PyTypeInfo,
+    types::{PyDict, PyType},
+    PyClass, PyMethodDefType, PyNativeType, PyResult, PyTypeInfo, Python,
 };
-use std::{marker::PhantomData, thread};
+use std::{fmt, marker::PhantomData, thread};
 
 /// This type is used as a "dummy" type on which dtolnay specializations are
 /// applied to apply implementations from `#[pymethods]` & `#[pyproto]`
@@ -47,6 +48,9 @@ pub trait PyClassImpl: Sized {
     /// #[pyclass(subclass)]
     const IS_BASETYPE: bool = false;
 
+    /// #[pyclass(abstract)]
+    const IS_ABSTRACT: bool = false;
+
     /// #[pyclass(extends=...)]
     const IS_SUBCLASS: bool = false;
 
@@ -72,12 +76,42 @@ pub trait PyClassImpl: Sized {
     fn get_call() -> Option<ffi::PyCFunctionWithKeywords> {
         None
     }
+    fn get_richcompare() -> Option<ffi::richcmpfunc> {
+        None
+    }
+    fn get_descr_get() -> Option<ffi::descrgetfunc> {
+        None
+    }
+    fn get_descr_set() -> Option<ffi::descrsetfunc> {
+        None
+    }
     fn for_each_proto_slot(_visitor: &mut dyn FnMut(&ffi::PyType_Slot)) {}
     fn get_buffer() -> Option<&'static PyBufferProcs> {
         None
     }
 }
 
+/// Checked by `#[pymethods]` so that using it on a type without `#[pyclass]` fails early, with
+/// the error pointing at the type.
+pub fn assert_pyclass<T: PyClass>() {}
+
+/// Called by the `__init_subclass__` generated by `#[pymethods]` after the user's implementation,
+/// to continue with `super(T, cls).__init_subclass__(**kwargs)`.
+pub fn init_subclass_super<T: PyTypeInfo>(
+    py: Python,
+    cls: &PyType,
+    kwargs: Option<&PyDict>,
+) -> PyResult<()> {
+    let super_ = py
+        .builtins()
+        .get_item("super")
+        .expect("builtins should contain super");
+    super_
+        .call1((T::type_object(py), cls))?
+        .call_method("__init_subclass__", (), kwargs)?;
+    Ok(())
+}
+
 // Traits describing known special methods.
 
 pub trait PyClassNewImpl<T> {
@@ -100,6 +134,36 @@ impl<T> PyClassCallImpl<T> for &'_ PyClassImplCollector<T> {
     }
 }
 
+pub trait PyClassRichCompareImpl<T> {
+    fn richcompare_impl(self) -> Option<ffi::richcmpfunc>;
+}
+
+impl<T> PyClassRichCompareImpl<T> for &'_ PyClassImplCollector<T> {
+    fn richcompare_impl(self) -> Option<ffi::richcmpfunc> {
+        None
+    }
+}
+
+pub trait PyClassDescrGetImpl<T> {
+    fn descr_get_impl(self) -> Option<ffi::descrgetfunc>;
+}
+
+impl<T> PyClassDescrGetImpl<T> for &'_ PyClassImplCollector<T> {
+    fn descr_get_impl(self) -> Option<ffi::descrgetfunc> {
+        None
+    }
+}
+
+pub trait PyClassDescrSetImpl<T> {
+    fn descr_set_impl(self) -> Option<ffi::descrsetfunc>;
+}
+
+impl<T> PyClassDescrSetImpl<T> for &'_ PyClassImplCollector<T> {
+    fn descr_set_impl(self) -> Option<ffi::descrsetfunc> {
+        None
+    }
+}
+
 // General methods implementation: either dtolnay specialization trait or inventory if
 // multiple-pymethods feature is enabled.
 
@@ -174,6 +238,8 @@ slots_trait!(PyNumberProtocolSlots, number_protocol_slots);
 slots_trait!(PyAsyncProtocolSlots, async_protocol_slots);
 slots_trait!(PySequenceProtocolSlots, sequence_protocol_slots);
 slots_trait!(PyBufferProtocolSlots, buffer_protocol_slots);
+slots_trait!(PyClassFormatSlots, format_slots);
+slots_trait!(PyClassFinalizerSlots, finalizer_slots);
 
 methods_trait!(PyObjectProtocolMethods, object_protocol_methods);
 methods_trait!(PyAsyncProtocolMethods, async_protocol_methods);
@@ -181,6 +247,34 @@ methods_trait!(PyContextProtocolMethods, context_protocol_methods);
 methods_trait!(PyDescrProtocolMethods, descr_protocol_methods);
 methods_trait!(PyMappingProtocolMethods, mapping_protocol_methods);
 methods_trait!(PyNumberProtocolMethods, number_protocol_methods);
+methods_trait!(PyClassContextManagerMethods, context_manager_methods);
+methods_trait!(PyClassPickleMethods, pickle_methods);
+
+// Formatting for `#[pyclass(str)]`, which uses `Debug` if it is implemented and `Display`
+// otherwise. This relies on the same autoref specialization as the slots above, so it must be
+// called as `(&&FormatWith(value)).pyo3_format()` on a concrete type.
+
+pub struct FormatWith<'a, T>(pub &'a T);
+
+pub trait FormatWithDebug {
+    fn pyo3_format(&self) -> String;
+}
+
+impl<T: fmt::Debug> FormatWithDebug for &'_ FormatWith<'_, T> {
+    fn pyo3_format(&self) -> String {
+        format!("{:?}", self.0)
+    }
+}
+
+pub trait FormatWithDisplay {
+    fn pyo3_format(&self) -> String;
+}
+
+impl<T: fmt::Display> FormatWithDisplay for FormatWith<'_, T> {
+    fn pyo3_format(&self) -> String {
+        self.0.to_string()
+    }
+}
 
 // On Python < 3.9 setting the buffer protocol using slots doesn't work, so these procs are used
 // on those versions to set the slots manually (on the limited API).
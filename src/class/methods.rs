@@ -91,6 +91,16 @@ impl PyMethodDef {
         }
     }
 
+    /// Define a function which takes exactly one positional argument.
+    pub const fn onearg(name: &'static str, cfunction: PyCFunction, doc: &'static str) -> Self {
+        Self {
+            ml_name: name,
+            ml_meth: PyMethodType::PyCFunction(cfunction),
+            ml_flags: ffi::METH_O,
+            ml_doc: doc,
+        }
+    }
+
     /// Define a function that can take `*args` and `**kwargs`.
     pub const fn cfunction_with_keywords(
         name: &'static str,
@@ -121,7 +131,10 @@ impl PyMethodDef {
             ml_name: get_name(self.ml_name)?.as_ptr(),
             ml_meth: Some(meth),
             ml_flags: self.ml_flags,
-            ml_doc: get_doc(self.ml_doc)?.as_ptr(),
+            ml_doc: match self.ml_doc {
+                "\0" => std::ptr::null(),
+                doc => get_doc(doc)?.as_ptr(),
+            },
         })
     }
 }
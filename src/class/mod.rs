@@ -11,6 +11,9 @@ pub mod basic;
 pub mod buffer;
 pub mod context;
 pub mod descr;
+#[cfg(not(Py_LIMITED_API))]
+#[cfg_attr(docsrs, doc(cfg(not(Py_LIMITED_API))))]
+pub mod finalize;
 pub mod gc;
 #[doc(hidden)]
 pub mod impl_;
@@ -19,6 +22,7 @@ pub mod mapping;
 #[doc(hidden)]
 pub mod methods;
 pub mod number;
+pub mod pickle;
 pub mod pyasync;
 pub mod sequence;
 
@@ -0,0 +1,138 @@
+// Copyright (c) 2017-present PyO3 Project and Contributors
+
+//! Pickling support for `#[pyclass(pickle)]`
+//!
+//! The state of an instance is a `dict` mapping field names to their values. Unpickling
+//! creates the instance directly from this state, so it doesn't call the class's `#[new]`
+//! constructor.
+
+use crate::class::impl_::PyClassBaseType;
+use crate::exceptions::PyTypeError;
+use crate::pyclass_init::PyNativeTypeInitializer;
+use crate::types::{PyAny, PyDict};
+use crate::{ffi, FromPyObject, PyCell, PyClass, PyClassInitializer, PyResult, Python, ToPyObject};
+
+/// Conversion of a class to and from the state stored by `pickle`.
+///
+/// With the `pickle` flag, `#[pyclass]` implements this trait for a struct whose fields all
+/// implement `ToPyObject` and `FromPyObject`, and exposes it to Python as `__getstate__`,
+/// `__setstate__` and `__reduce_ex__`, so that instances can be pickled and copied.
+///
+/// As with any other class, `pickle` stores a reference to the class by its module and name, so
+/// the class must be given a `module` and added to that module to be pickled. The `copy` module
+/// doesn't need this.
+///
+/// # Examples
+///
+/// ```
+/// use pyo3::prelude::*;
+///
+/// #[pyclass(pickle)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// Python::with_gil(|py| {
+///     let point = PyCell::new(py, Point { x: 1, y: 2 }).unwrap();
+///     pyo3::py_run!(py, point, r#"
+///         import copy
+///         assert point.__getstate__() == {"x": 1, "y": 2}
+///         assert copy.deepcopy(point).__getstate__() == {"x": 1, "y": 2}
+///     "#);
+/// });
+/// ```
+pub trait PyPickle: PyClass {
+    /// Returns the state of `self` as a `dict` mapping field names to their values.
+    fn __getstate__<'p>(&self, py: Python<'p>) -> PyResult<&'p PyDict>;
+
+    /// Creates an instance from the state returned by `__getstate__`.
+    fn from_state(state: &PyDict) -> PyResult<Self>;
+
+    /// Replaces the fields of `self` with the state returned by `__getstate__`.
+    fn __setstate__(&mut self, state: &PyDict) -> PyResult<()> {
+        *self = Self::from_state(state)?;
+        Ok(())
+    }
+}
+
+#[doc(hidden)]
+pub fn assert_picklable_field<T: ToPyObject + for<'a> FromPyObject<'a>>() {}
+
+#[doc(hidden)]
+pub fn extract_field<'a, T>(state: &'a PyDict, cls: &str, field: &str) -> PyResult<T>
+where
+    T: FromPyObject<'a>,
+{
+    match state.get_item(field) {
+        Some(value) => value.extract(),
+        None => Err(PyTypeError::new_err(format!(
+            "pickled state of `{}` is missing field `{}`",
+            cls, field
+        ))),
+    }
+}
+
+#[doc(hidden)]
+pub unsafe extern "C" fn getstate<T>(
+    slf: *mut ffi::PyObject,
+    _args: *mut ffi::PyObject,
+) -> *mut ffi::PyObject
+where
+    T: PyPickle,
+{
+    crate::callback::handle_panic(|py| {
+        let slf = py.from_borrowed_ptr::<PyCell<T>>(slf);
+        let state = slf.try_borrow()?.__getstate__(py)?;
+        crate::callback::convert(py, state)
+    })
+}
+
+#[doc(hidden)]
+pub unsafe extern "C" fn setstate<T>(
+    slf: *mut ffi::PyObject,
+    state: *mut ffi::PyObject,
+) -> *mut ffi::PyObject
+where
+    T: PyPickle,
+{
+    crate::callback::handle_panic(|py| {
+        let slf = py.from_borrowed_ptr::<PyCell<T>>(slf);
+        let state: &PyDict = py.from_borrowed_ptr::<PyAny>(state).downcast()?;
+        slf.try_borrow_mut()?.__setstate__(state)?;
+        crate::callback::convert(py, ())
+    })
+}
+
+#[doc(hidden)]
+pub unsafe extern "C" fn reduce_ex<T>(
+    slf: *mut ffi::PyObject,
+    _protocol: *mut ffi::PyObject,
+) -> *mut ffi::PyObject
+where
+    T: PyPickle,
+{
+    crate::callback::handle_panic(|py| {
+        let slf = py.from_borrowed_ptr::<PyCell<T>>(slf);
+        let state = slf.try_borrow()?.__getstate__(py)?;
+        let unpickle = slf.get_type().getattr("_pyo3_unpickle")?;
+        crate::callback::convert(py, (unpickle, (state,)))
+    })
+}
+
+#[doc(hidden)]
+pub unsafe extern "C" fn unpickle<T>(
+    cls: *mut ffi::PyObject,
+    state: *mut ffi::PyObject,
+) -> *mut ffi::PyObject
+where
+    T: PyPickle,
+    T::BaseType: PyClassBaseType<Initializer = PyNativeTypeInitializer<T::BaseType>>,
+{
+    crate::callback::handle_panic(|py| {
+        let state: &PyDict = py.from_borrowed_ptr::<PyAny>(state).downcast()?;
+        let init = PyClassInitializer::from(T::from_state(state)?);
+        let cell = init.create_cell_from_subtype(py, cls as *mut ffi::PyTypeObject)?;
+        Ok(cell as *mut ffi::PyObject)
+    })
+}
@@ -0,0 +1,48 @@
+//! Python callables implemented by Rust closures, used by e.g. `Python::add_path_hook`.
+
+use crate::helper_type::{get_or_create_helper_type, helper_contents, new_helper_object};
+use crate::once_cell::GILOnceCell;
+use crate::types::{PyDict, PyTuple, PyType};
+use crate::{ffi, IntoPyPointer, Py, PyObject, PyResult, Python};
+use std::os::raw::c_void;
+
+type Closure = Box<dyn Fn(Python, &PyTuple, Option<&PyDict>) -> PyResult<PyObject> + Send>;
+
+/// Wraps a Rust closure in a Python callable, which passes its `*args` and `**kwargs` on to it.
+pub(crate) fn wrap_closure<F>(py: Python, closure: F) -> PyResult<PyObject>
+where
+    F: Fn(Python, &PyTuple, Option<&PyDict>) -> PyResult<PyObject> + Send + 'static,
+{
+    let ty = closure_type(py)?;
+    unsafe {
+        let obj = new_helper_object(py, ty, Box::new(closure) as Closure)?;
+        Ok(PyObject::from_owned_ptr(py, obj))
+    }
+}
+
+fn closure_type(py: Python) -> PyResult<&PyType> {
+    static TYPE_OBJECT: GILOnceCell<Py<PyType>> = GILOnceCell::new();
+
+    get_or_create_helper_type::<Closure>(
+        py,
+        &TYPE_OBJECT,
+        "pyo3_runtime.RustClosure\0",
+        &[ffi::PyType_Slot {
+            slot: ffi::Py_tp_call,
+            pfunc: closure_call as *mut c_void,
+        }],
+    )
+}
+
+unsafe extern "C" fn closure_call(
+    slf: *mut ffi::PyObject,
+    args: *mut ffi::PyObject,
+    kwargs: *mut ffi::PyObject,
+) -> *mut ffi::PyObject {
+    crate::callback::handle_panic(|py| {
+        let args: &PyTuple = py.from_borrowed_ptr(args);
+        let kwargs: Option<&PyDict> = py.from_borrowed_ptr_or_opt(kwargs);
+        let closure = helper_contents::<Closure>(slf);
+        closure(py, args, kwargs).map(|obj| obj.into_ptr())
+    })
+}
@@ -3,9 +3,10 @@
 //! Conversions between various states of Rust and Python types and their wrappers.
 use crate::err::{self, PyDowncastError, PyResult};
 use crate::type_object::PyTypeInfo;
-use crate::types::PyTuple;
+use crate::types::{PyDict, PyTuple};
 use crate::{
-    ffi, gil, Py, PyAny, PyCell, PyClass, PyNativeType, PyObject, PyRef, PyRefMut, Python,
+    derive_utils, ffi, gil, Py, PyAny, PyCell, PyClass, PyNativeType, PyObject, PyRef, PyRefMut,
+    Python,
 };
 use std::ptr::NonNull;
 
@@ -344,6 +345,76 @@ where
     }
 }
 
+/// Extracts a single argument from the `*args` and `**kwargs` passed to a Python function, for
+/// hand-written `PyCFunctionWithKeywords` wrappers.
+///
+/// The argument is taken from `args[pos]` if it was passed positionally, otherwise from
+/// `kwargs[name]`. If it was passed neither way, `default` is returned, or `TypeError` is raised
+/// if there is no default. Passing the argument both ways also raises `TypeError`. The error
+/// messages name the function `func_name`, like those of `#[pyfunction]`.
+///
+/// Unlike the code generated by `#[pyfunction]`, this does not check for unexpected arguments.
+///
+/// # Examples
+/// ```rust
+/// use pyo3::conversion::extract_argument;
+/// use pyo3::prelude::*;
+/// use pyo3::types::{IntoPyDict, PyTuple};
+///
+/// Python::with_gil(|py| -> PyResult<()> {
+///     let args = PyTuple::new(py, &[1]);
+///     let kwargs = [("b", 2)].into_py_dict(py);
+///     let a: i32 = extract_argument("f", args, Some(kwargs), 0, "a", None)?;
+///     let b: i32 = extract_argument("f", args, Some(kwargs), 1, "b", None)?;
+///     let c: i32 = extract_argument("f", args, Some(kwargs), 2, "c", Some(3))?;
+///     assert_eq!((a, b, c), (1, 2, 3));
+///
+///     let err = extract_argument::<i32>("f", args, None, 3, "d", None).unwrap_err();
+///     assert_eq!(
+///         err.to_string(),
+///         "TypeError: f() missing 1 required positional argument: 'd'"
+///     );
+///     Ok(())
+/// })
+/// .unwrap();
+/// ```
+pub fn extract_argument<'p, T>(
+    func_name: &str,
+    args: &'p PyTuple,
+    kwargs: Option<&'p PyDict>,
+    pos: usize,
+    name: &str,
+    default: Option<T>,
+) -> PyResult<T>
+where
+    T: FromPyObject<'p>,
+{
+    let positional = if pos < args.len() {
+        Some(args.get_item(pos))
+    } else {
+        None
+    };
+    let keyword = kwargs.and_then(|kwargs| kwargs.get_item(name));
+    let full_name = || format!("{}()", func_name);
+    let value = match (positional, keyword) {
+        (Some(_), Some(_)) => {
+            return Err(derive_utils::multiple_values_for_argument(
+                &full_name(),
+                name,
+            ))
+        }
+        (Some(value), None) | (None, Some(value)) => value,
+        (None, None) => {
+            return default.ok_or_else(|| {
+                derive_utils::missing_required_arguments(&full_name(), "positional", &[name])
+            })
+        }
+    };
+    value
+        .extract()
+        .map_err(|e| derive_utils::argument_extraction_error(args.py(), name, e))
+}
+
 /// Trait implemented by Python object types that allow a checked downcast.
 /// If `T` implements `PyTryFrom`, we can convert `&PyAny` to `&T`.
 ///
@@ -544,10 +615,10 @@ where
 
 #[cfg(test)]
 mod test {
-    use crate::types::{IntoPyDict, PyAny, PyDict, PyList};
-    use crate::{Python, ToPyObject};
+    use crate::types::{IntoPyDict, PyAny, PyDict, PyList, PyTuple};
+    use crate::{PyResult, Python, ToPyObject};
 
-    use super::PyTryFrom;
+    use super::{extract_argument, PyTryFrom};
 
     #[test]
     fn test_try_from() {
@@ -585,4 +656,42 @@ mod test {
         let val = unsafe { <PyList as PyTryFrom>::try_from_unchecked(list.as_ref()) };
         assert_eq!(list, val);
     }
+
+    #[test]
+    fn test_extract_argument() {
+        Python::with_gil(|py| {
+            let args = PyTuple::new(py, &[1, 2]);
+            let kwargs = [("c", 3)].into_py_dict(py);
+            let extract = |pos, name| -> PyResult<i32> {
+                extract_argument("f", args, Some(kwargs), pos, name, None)
+            };
+            assert_eq!(extract(0, "a").unwrap(), 1);
+            assert_eq!(extract(2, "c").unwrap(), 3);
+            assert_eq!(
+                extract(3, "d").unwrap_err().to_string(),
+                "TypeError: f() missing 1 required positional argument: 'd'"
+            );
+            assert_eq!(
+                extract_argument::<i32>("f", args, None, 3, "d", Some(4)).unwrap(),
+                4
+            );
+
+            let kwargs = [("b", 3)].into_py_dict(py);
+            assert_eq!(
+                extract_argument::<i32>("f", args, Some(kwargs), 1, "b", None)
+                    .unwrap_err()
+                    .to_string(),
+                "TypeError: f() got multiple values for argument 'b'"
+            );
+        });
+    }
+
+    #[test]
+    fn test_extract_argument_wrong_type() {
+        Python::with_gil(|py| {
+            let args = PyTuple::new(py, &["foo"]);
+            let err = extract_argument::<i32>("f", args, None, 0, "a", None).unwrap_err();
+            assert!(err.to_string().starts_with("TypeError: argument 'a': "));
+        });
+    }
 }
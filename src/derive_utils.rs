@@ -225,11 +225,7 @@ impl FunctionDescription {
     }
 
     fn multiple_values_for_argument(&self, argument: &str) -> PyErr {
-        PyTypeError::new_err(format!(
-            "{} got multiple values for argument '{}'",
-            self.full_name(),
-            argument
-        ))
+        multiple_values_for_argument(&self.full_name(), argument)
     }
 
     fn unexpected_keyword_argument(&self, argument: &PyAny) -> PyErr {
@@ -250,23 +246,38 @@ impl FunctionDescription {
     }
 
     fn missing_required_arguments(&self, argument_type: &str, parameter_names: &[&str]) -> PyErr {
-        let arguments = if parameter_names.len() == 1 {
-            "argument"
-        } else {
-            "arguments"
-        };
-        let mut msg = format!(
-            "{} missing {} required {} {}: ",
-            self.full_name(),
-            parameter_names.len(),
-            argument_type,
-            arguments,
-        );
-        push_parameter_list(&mut msg, parameter_names);
-        PyTypeError::new_err(msg)
+        missing_required_arguments(&self.full_name(), argument_type, parameter_names)
     }
 }
 
+pub(crate) fn multiple_values_for_argument(full_name: &str, argument: &str) -> PyErr {
+    PyTypeError::new_err(format!(
+        "{} got multiple values for argument '{}'",
+        full_name, argument
+    ))
+}
+
+pub(crate) fn missing_required_arguments(
+    full_name: &str,
+    argument_type: &str,
+    parameter_names: &[&str],
+) -> PyErr {
+    let arguments = if parameter_names.len() == 1 {
+        "argument"
+    } else {
+        "arguments"
+    };
+    let mut msg = format!(
+        "{} missing {} required {} {}: ",
+        full_name,
+        parameter_names.len(),
+        argument_type,
+        arguments,
+    );
+    push_parameter_list(&mut msg, parameter_names);
+    PyTypeError::new_err(msg)
+}
+
 /// Add the argument name to the error message of an error which occurred during argument extraction
 pub fn argument_extraction_error(py: Python, arg_name: &str, error: PyErr) -> PyErr {
     if error.ptype(py) == py.get_type::<PyTypeError>() {
@@ -1,4 +1,7 @@
-use crate::{err::PyErrArguments, exceptions, IntoPy, PyErr, PyObject, Python};
+use crate::{
+    err::PyErrArguments, exceptions, ffi, AsPyPointer, IntoPy, IntoPyPointer, PyErr, PyObject,
+    Python, ToPyObject,
+};
 use std::io;
 
 /// Convert `PyErr` to `io::Error`
@@ -51,6 +54,22 @@ impl std::convert::From<std::convert::Infallible> for PyErr {
     }
 }
 
+/// Convert any Rust error to a Python `RuntimeError` instance.
+///
+/// The chain of [`source`](std::error::Error::source) errors is converted recursively and attached
+/// as the `__cause__` of each exception.
+impl<'a> ToPyObject for dyn std::error::Error + 'a {
+    fn to_object(&self, py: Python) -> PyObject {
+        let exc = exceptions::PyRuntimeError::new_err(self.to_string()).into_instance(py);
+        if let Some(source) = self.source() {
+            let cause = source.to_object(py);
+            // PyException_SetCause steals the reference to the cause
+            unsafe { ffi::PyException_SetCause(exc.as_ptr(), cause.into_ptr()) };
+        }
+        exc.into()
+    }
+}
+
 macro_rules! impl_to_pyerr {
     ($err: ty, $pyexc: ty) => {
         impl PyErrArguments for $err {
@@ -88,8 +107,8 @@ impl_to_pyerr!(std::net::AddrParseError, exceptions::PyValueError);
 
 #[cfg(test)]
 mod tests {
-    use crate::PyErr;
-    use std::io;
+    use crate::{PyErr, Python, ToPyObject};
+    use std::{fmt, io};
 
     #[test]
     fn io_errors() {
@@ -111,4 +130,47 @@ mod tests {
         check_err(io::ErrorKind::WouldBlock, "BlockingIOError");
         check_err(io::ErrorKind::TimedOut, "TimeoutError");
     }
+
+    #[derive(Debug)]
+    struct ChainedError {
+        message: &'static str,
+        source: Option<Box<ChainedError>>,
+    }
+
+    impl fmt::Display for ChainedError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str(self.message)
+        }
+    }
+
+    impl std::error::Error for ChainedError {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            self.source.as_ref().map(|e| e.as_ref() as _)
+        }
+    }
+
+    #[test]
+    fn error_to_object() {
+        Python::with_gil(|py| {
+            let err = ChainedError {
+                message: "outer",
+                source: Some(Box::new(ChainedError {
+                    message: "inner",
+                    source: None,
+                })),
+            };
+            let obj = (&err as &dyn std::error::Error).to_object(py);
+            crate::py_run!(
+                py,
+                obj,
+                r#"
+                assert isinstance(obj, RuntimeError)
+                assert str(obj) == "outer"
+                assert isinstance(obj.__cause__, RuntimeError)
+                assert str(obj.__cause__) == "inner"
+                assert obj.__cause__.__cause__ is None
+                "#
+            );
+        });
+    }
 }
@@ -2,7 +2,7 @@
 
 use crate::panic::PanicException;
 use crate::type_object::PyTypeObject;
-use crate::types::PyType;
+use crate::types::{PyTraceback, PyType};
 use crate::{
     exceptions::{self, PyBaseException},
     ffi,
@@ -203,6 +203,24 @@ impl PyErr {
             .map(|obj| obj.as_ref(py))
     }
 
+    /// Gets the traceback of this exception, if it has one.
+    ///
+    /// This is the same object as [`PyErr::ptraceback`], as a [`PyTraceback`].
+    ///
+    /// # Examples
+    /// ```rust
+    /// use pyo3::prelude::*;
+    /// Python::with_gil(|py| {
+    ///     let err = py.run("raise ValueError('oops')", None, None).unwrap_err();
+    ///     let traceback = err.traceback(py).unwrap();
+    ///     assert_eq!(traceback.filename().unwrap(), "<string>");
+    /// });
+    /// ```
+    pub fn traceback<'py>(&'py self, py: Python<'py>) -> Option<&'py PyTraceback> {
+        self.ptraceback(py)
+            .and_then(|traceback| traceback.downcast().ok())
+    }
+
     /// Gets whether an error is present in the Python interpreter's global state.
     #[inline]
     pub fn occurred(_: Python) -> bool {
@@ -310,6 +328,48 @@ impl PyErr {
         unsafe { ffi::PyErr_PrintEx(1) }
     }
 
+    /// Formats the exception and its traceback as Python would print it, using
+    /// `traceback.format_exception()`.
+    ///
+    /// Chained exceptions (`__cause__` and `__context__`) are included. If formatting fails, this
+    /// falls back to [`short_message`](PyErr::short_message).
+    ///
+    /// # Examples
+    /// ```rust
+    /// use pyo3::prelude::*;
+    ///
+    /// Python::with_gil(|py| {
+    ///     let err = py.run("raise ValueError('oops')", None, None).unwrap_err();
+    ///     let traceback = err.display_string(py);
+    ///     assert!(traceback.starts_with("Traceback (most recent call last):\n"));
+    ///     assert!(traceback.ends_with("ValueError: oops\n"));
+    /// });
+    /// ```
+    pub fn display_string(&self, py: Python) -> String {
+        let formatted = py.import("traceback").and_then(|traceback| {
+            join_lines(traceback.call_method1(
+                "format_exception",
+                (self.ptype(py), self.pvalue(py), self.ptraceback(py)),
+            )?)
+        });
+        formatted.unwrap_or_else(|_| self.short_message(py))
+    }
+
+    /// Formats the exception type and message without the traceback, using
+    /// `traceback.format_exception_only()`, e.g. `ValueError: oops`.
+    pub fn short_message(&self, py: Python) -> String {
+        let formatted = py.import("traceback").and_then(|traceback| {
+            join_lines(
+                traceback
+                    .call_method1("format_exception_only", (self.ptype(py), self.pvalue(py)))?,
+            )
+        });
+        match formatted {
+            Ok(message) => message.trim_end_matches('\n').to_owned(),
+            Err(_) => self.to_string(),
+        }
+    }
+
     /// Returns true if the current exception matches the exception in `exc`.
     ///
     /// If `exc` is a class object, this also returns `true` when `self` is an instance of a subclass.
@@ -333,6 +393,12 @@ impl PyErr {
         }
     }
 
+    /// Returns true if this is an exception group, i.e. an instance of `BaseExceptionGroup`.
+    #[cfg(Py_3_11)]
+    pub fn is_exception_group(&self, py: Python) -> bool {
+        self.is_instance::<exceptions::PyBaseExceptionGroup>(py)
+    }
+
     /// Retrieves the exception instance for this error.
     pub fn instance<'py>(&'py self, py: Python<'py>) -> &'py PyBaseException {
         self.normalized(py).pvalue.as_ref(py)
@@ -345,6 +411,47 @@ impl PyErr {
         out
     }
 
+    /// Sets `cause` as the `__cause__` of this error, like Python's `raise self from cause`.
+    ///
+    /// Returns `self` to allow chaining.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use pyo3::{Python, exceptions::{PyKeyError, PyValueError}};
+    /// Python::with_gil(|py| {
+    ///     let err = PyValueError::new_err("invalid config")
+    ///         .with_cause(py, PyKeyError::new_err("missing key"));
+    ///     assert_eq!(err.cause(py).unwrap().to_string(), "KeyError: 'missing key'");
+    /// });
+    /// ```
+    pub fn with_cause(self, py: Python, cause: PyErr) -> PyErr {
+        self.instance(py).set_cause(Some(cause.instance(py)));
+        self
+    }
+
+    /// Sets `context` as the `__context__` of this error, i.e. the exception which was being
+    /// handled when this error was raised.
+    ///
+    /// Returns `self` to allow chaining.
+    pub fn with_context(self, py: Python, context: PyErr) -> PyErr {
+        self.instance(py).set_context(Some(context.instance(py)));
+        self
+    }
+
+    /// Gets the `__cause__` of this error, if it has one.
+    pub fn cause(&self, py: Python) -> Option<PyErr> {
+        self.instance(py)
+            .cause()
+            .map(|cause| PyErr::from_instance(cause))
+    }
+
+    /// Gets the `__context__` of this error, if it has one.
+    pub fn context(&self, py: Python) -> Option<PyErr> {
+        self.instance(py)
+            .context()
+            .map(|context| PyErr::from_instance(context))
+    }
+
     /// Writes the error back to the Python interpreter's global state.
     /// This is the opposite of `PyErr::fetch()`.
     #[inline]
@@ -513,6 +620,15 @@ impl<'a> std::fmt::Display for PyDowncastError<'a> {
     }
 }
 
+/// Concatenates the strings produced by the iterable `lines`.
+fn join_lines(lines: &PyAny) -> PyResult<String> {
+    let mut joined = String::new();
+    for line in lines.iter()? {
+        joined.push_str(line?.extract()?);
+    }
+    Ok(joined)
+}
+
 pub fn panic_after_error(_py: Python) -> ! {
     unsafe {
         ffi::PyErr_Print();
@@ -616,6 +732,63 @@ mod tests {
         assert_eq!(err.to_string(), "Exception: banana");
     }
 
+    #[test]
+    fn err_display_string() {
+        Python::with_gil(|py| {
+            let err = py
+                .run(
+                    "try:\n    raise KeyError('inner')\nexcept KeyError as e:\n    raise ValueError('banana') from e",
+                    None,
+                    None,
+                )
+                .expect_err("raising should have given us an error");
+            let formatted = err.display_string(py);
+            assert!(formatted.starts_with("Traceback (most recent call last):\n"));
+            assert!(formatted.contains("KeyError: 'inner'\n"));
+            assert!(formatted.contains("direct cause"));
+            assert!(formatted.ends_with("ValueError: banana\n"));
+            assert_eq!(err.short_message(py), "ValueError: banana");
+
+            // Errors which were never raised have no traceback
+            let err = exceptions::PyTypeError::new_err("not raised");
+            assert_eq!(err.display_string(py), "TypeError: not raised\n");
+            assert_eq!(err.short_message(py), "TypeError: not raised");
+        });
+    }
+
+    #[test]
+    fn err_cause_and_context() {
+        Python::with_gil(|py| {
+            let err = exceptions::PyValueError::new_err("outer");
+            assert!(err.cause(py).is_none());
+            assert!(err.context(py).is_none());
+
+            let err = err
+                .with_context(py, exceptions::PyTypeError::new_err("context"))
+                .with_cause(py, exceptions::PyKeyError::new_err("cause"));
+            let cause = err.cause(py).unwrap();
+            assert!(cause.is_instance::<exceptions::PyKeyError>(py));
+            let context = err.context(py).unwrap();
+            assert!(context.is_instance::<exceptions::PyTypeError>(py));
+            assert!(err
+                .instance(py)
+                .getattr("__suppress_context__")
+                .unwrap()
+                .is_true()
+                .unwrap());
+
+            let err = py
+                .run(
+                    "try:\n    raise KeyError('inner')\nexcept KeyError as e:\n    raise ValueError('outer') from e",
+                    None,
+                    None,
+                )
+                .unwrap_err();
+            assert_eq!(err.cause(py).unwrap().to_string(), "KeyError: 'inner'");
+            assert_eq!(err.context(py).unwrap().to_string(), "KeyError: 'inner'");
+        });
+    }
+
     #[test]
     fn test_pyerr_send_sync() {
         fn is_send<T: Send>() {}
@@ -2,7 +2,7 @@
 
 //! Exception types defined by Python.
 
-use crate::{ffi, PyResult, Python};
+use crate::{ffi, PyClass, PyResult, PyTypeInfo, Python};
 use std::ffi::CStr;
 use std::ops;
 use std::os::raw::c_char;
@@ -24,20 +24,51 @@ macro_rules! impl_exception_boilerplate {
             {
                 $crate::PyErr::new::<$name, A>(args)
             }
-        }
 
-        impl std::error::Error for $name {
-            fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            /// Gets the exception's `__cause__`, as set by `raise ... from cause`.
+            pub fn cause(&self) -> Option<&$crate::exceptions::PyBaseException> {
+                use $crate::{AsPyPointer, PyNativeType};
+                unsafe {
+                    self.py()
+                        .from_owned_ptr_or_opt($crate::ffi::PyException_GetCause(self.as_ptr()))
+                }
+            }
+
+            /// Sets the exception's `__cause__`, like `raise ... from cause`.
+            ///
+            /// This also sets `__suppress_context__` to `True`.
+            pub fn set_cause(&self, cause: Option<&$crate::exceptions::PyBaseException>) {
+                use $crate::{AsPyPointer, IntoPyPointer};
+                unsafe {
+                    $crate::ffi::PyException_SetCause(self.as_ptr(), cause.into_ptr());
+                }
+            }
+
+            /// Gets the exception's `__context__`, the exception which was being handled when
+            /// this one was raised.
+            pub fn context(&self) -> Option<&$crate::exceptions::PyBaseException> {
+                use $crate::{AsPyPointer, PyNativeType};
                 unsafe {
-                    use $crate::{AsPyPointer, PyNativeType};
-                    let cause: &$crate::exceptions::PyBaseException = self
-                        .py()
-                        .from_owned_ptr_or_opt($crate::ffi::PyException_GetCause(self.as_ptr()))?;
+                    self.py()
+                        .from_owned_ptr_or_opt($crate::ffi::PyException_GetContext(self.as_ptr()))
+                }
+            }
 
-                    Some(cause)
+            /// Sets the exception's `__context__`.
+            pub fn set_context(&self, context: Option<&$crate::exceptions::PyBaseException>) {
+                use $crate::{AsPyPointer, IntoPyPointer};
+                unsafe {
+                    $crate::ffi::PyException_SetContext(self.as_ptr(), context.into_ptr());
                 }
             }
         }
+
+        impl std::error::Error for $name {
+            fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+                self.cause()
+                    .map(|cause| cause as &(dyn std::error::Error + 'static))
+            }
+        }
     };
 }
 
@@ -199,12 +230,70 @@ macro_rules! impl_native_exception (
 
         $crate::impl_exception_boilerplate!($name);
         $crate::pyobject_native_type!($name, $layout, *(ffi::$exc_name as *mut ffi::PyTypeObject));
+
+        impl PyNativeException for $name {}
     );
     ($name:ident, $exc_name:ident) => (
         impl_native_exception!($name, $exc_name, ffi::PyBaseExceptionObject);
     )
 );
 
+/// Marker trait for the built-in exception types, which `#[pyclass]` types can extend.
+pub trait PyNativeException: PyTypeInfo {}
+
+/// Constructors for `#[pyclass]` types which extend a built-in exception type.
+///
+/// This is implemented for any `#[pyclass(extends=...)]` whose closest native base is
+/// `BaseException` or one of its subclasses, and gives such classes the same `new_err`
+/// constructor as the built-in exceptions. The arguments are passed to the class's `#[new]`
+/// method when the exception is instantiated.
+///
+/// # Examples
+///
+/// ```
+/// use pyo3::exceptions::{PyClassException, PyException};
+/// use pyo3::prelude::*;
+///
+/// #[pyclass(extends=PyException)]
+/// struct HttpError {
+///     #[pyo3(get)]
+///     status: u16,
+/// }
+///
+/// #[pymethods]
+/// impl HttpError {
+///     #[new]
+///     fn new(status: u16) -> Self {
+///         HttpError { status }
+///     }
+/// }
+///
+/// Python::with_gil(|py| {
+///     let err = HttpError::new_err(404);
+///     assert!(err.is_instance::<HttpError>(py));
+///     let status: u16 = err.instance(py).getattr("status").unwrap().extract().unwrap();
+///     assert_eq!(status, 404);
+/// });
+/// ```
+pub trait PyClassException: PyClass {
+    /// Creates a new [`PyErr`](crate::PyErr) of this type.
+    ///
+    /// `args` is converted the same way as for [`PyErr::new`](crate::PyErr::new).
+    fn new_err<A>(args: A) -> crate::PyErr
+    where
+        A: crate::PyErrArguments + Send + Sync + 'static,
+    {
+        crate::PyErr::new::<Self, A>(args)
+    }
+}
+
+impl<T> PyClassException for T
+where
+    T: PyClass,
+    T::BaseNativeType: PyNativeException,
+{
+}
+
 impl_native_exception!(PyBaseException, PyExc_BaseException);
 impl_native_exception!(PyException, PyExc_Exception);
 impl_native_exception!(PyStopAsyncIteration, PyExc_StopAsyncIteration);
@@ -310,6 +399,126 @@ impl PyUnicodeDecodeError {
     }
 }
 
+#[cfg(Py_3_11)]
+impl_native_exception!(
+    PyBaseExceptionGroup,
+    PyExc_BaseExceptionGroup,
+    ffi::PyBaseExceptionGroupObject
+);
+
+/// Represents Python's `ExceptionGroup`, the subclass of `BaseExceptionGroup` which can only
+/// contain `Exception`s.
+///
+/// The C API does not export this type, so it is looked up from `builtins` when first used.
+#[cfg(Py_3_11)]
+#[repr(transparent)]
+pub struct PyExceptionGroup(crate::PyAny);
+
+#[cfg(Py_3_11)]
+impl_exception_boilerplate!(PyExceptionGroup);
+
+#[cfg(Py_3_11)]
+crate::pyobject_native_type_core!(
+    PyExceptionGroup,
+    *PyExceptionGroup::type_object_raw(Python::assume_gil_acquired())
+);
+
+#[cfg(Py_3_11)]
+impl PyExceptionGroup {
+    fn type_object_raw(py: Python) -> *mut ffi::PyTypeObject {
+        use crate::once_cell::GILOnceCell;
+        use crate::{types::PyType, AsPyPointer, Py};
+        static TYPE_OBJECT: GILOnceCell<Py<PyType>> = GILOnceCell::new();
+
+        TYPE_OBJECT
+            .get_or_init(py, || {
+                py.import("builtins")
+                    .and_then(|builtins| builtins.getattr("ExceptionGroup"))
+                    .and_then(|cls| cls.extract())
+                    .expect("Can not load exception class: builtins.ExceptionGroup")
+            })
+            .as_ptr() as *mut _
+    }
+}
+
+#[cfg(Py_3_11)]
+macro_rules! impl_exception_group {
+    ($name:ident) => {
+        impl $name {
+            /// Creates a new exception group from `message` and the exceptions in `excs`.
+            ///
+            /// This raises `TypeError` or `ValueError` if `excs` is empty or contains something
+            /// which is not an exception instance.
+            pub fn new<'p>(
+                py: Python<'p>,
+                message: &str,
+                excs: &crate::types::PyList,
+            ) -> PyResult<&'p $name> {
+                let group = py.get_type::<$name>().call1((message, excs))?;
+                Ok(group.downcast()?)
+            }
+
+            /// Gets the message which the group was created with.
+            pub fn message(&self) -> PyResult<&str> {
+                self.getattr("message")?.extract()
+            }
+
+            /// Gets the exceptions contained in the group.
+            pub fn exceptions(&self) -> PyResult<&crate::types::PyTuple> {
+                Ok(self.getattr("exceptions")?.downcast()?)
+            }
+
+            /// Splits the group into the exceptions for which `matcher` returns `true`, and the
+            /// rest.
+            ///
+            /// This is equivalent to Python's `group.split(matcher)`: `matcher` is called on the
+            /// group itself and then recursively on the nested exceptions, and the two parts
+            /// keep the structure, traceback, cause, context and notes of the original group.
+            /// Either part is `None` if it would be empty.
+            pub fn split(
+                &self,
+                matcher: impl Fn(&crate::PyAny) -> bool,
+            ) -> PyResult<(Option<&$name>, Option<&$name>)> {
+                use crate::PyNativeType;
+                let py = self.py();
+                let matched = crate::types::PyList::empty(py);
+                collect_matching_exceptions(self, &matcher, matched)?;
+                let condition = py
+                    .eval(
+                        "lambda matched: lambda exc: any(exc is m for m in matched)",
+                        None,
+                        None,
+                    )?
+                    .call1((matched,))?;
+                self.call_method1("split", (condition,))?.extract()
+            }
+        }
+    };
+}
+
+#[cfg(Py_3_11)]
+impl_exception_group!(PyBaseExceptionGroup);
+#[cfg(Py_3_11)]
+impl_exception_group!(PyExceptionGroup);
+
+/// Collects the exceptions which `split` puts into the matching part, in the same order as
+/// `BaseExceptionGroup.split` evaluates its condition.
+#[cfg(Py_3_11)]
+fn collect_matching_exceptions(
+    exc: &crate::PyAny,
+    matcher: &dyn Fn(&crate::PyAny) -> bool,
+    matched: &crate::types::PyList,
+) -> PyResult<()> {
+    if matcher(exc) {
+        matched.append(exc)?;
+    } else if let Ok(group) = exc.downcast::<PyBaseExceptionGroup>() {
+        for exc in group.exceptions()? {
+            collect_matching_exceptions(exc, matcher, matched)?;
+        }
+    }
+    Ok(())
+}
+
 /// Exceptions defined in `asyncio` module
 pub mod asyncio {
     import_exception!(asyncio, CancelledError);
@@ -497,4 +706,61 @@ mod test {
             );
         });
     }
+
+    #[test]
+    #[cfg(Py_3_11)]
+    fn exception_group() {
+        use super::{PyBaseExceptionGroup, PyExceptionGroup, PyKeyError, PyValueError};
+        use crate::types::PyList;
+        use crate::{AsPyPointer, IntoPy, PyAny};
+
+        Python::with_gil(|py| {
+            let inner = PyList::new(
+                py,
+                vec![
+                    PyKeyError::new_err("c").into_instance(py),
+                    PyValueError::new_err("d").into_instance(py),
+                ],
+            );
+            let excs = PyList::new(
+                py,
+                vec![
+                    PyValueError::new_err("a").into_py(py),
+                    PyExceptionGroup::new(py, "inner", inner)
+                        .unwrap()
+                        .into_py(py),
+                    PyKeyError::new_err("b").into_py(py),
+                ],
+            );
+            let group = PyExceptionGroup::new(py, "outer", excs).unwrap();
+            assert_eq!(group.message().unwrap(), "outer");
+            assert_eq!(group.exceptions().unwrap().len(), 3);
+
+            let err = PyErr::from_instance(group);
+            assert!(err.is_exception_group(py));
+            assert!(err.is_instance::<PyExceptionGroup>(py));
+            assert!(err.is_instance::<PyBaseExceptionGroup>(py));
+            assert!(!PyValueError::new_err("a").is_exception_group(py));
+
+            let is_value_error = |exc: &PyAny| exc.is_instance::<PyValueError>().unwrap();
+            let (matching, rest) = group.split(is_value_error).unwrap();
+            let repr =
+                |group: Option<&PyExceptionGroup>| group.unwrap().repr().unwrap().to_string();
+            assert_eq!(
+                repr(matching),
+                "ExceptionGroup('outer', [ValueError('a'), ExceptionGroup('inner', [ValueError('d')])])"
+            );
+            assert_eq!(
+                repr(rest),
+                "ExceptionGroup('outer', [ExceptionGroup('inner', [KeyError('c')]), KeyError('b')])"
+            );
+
+            let (matching, rest) = group.split(|_| true).unwrap();
+            assert_eq!(matching.unwrap().as_ptr(), group.as_ptr());
+            assert!(rest.is_none());
+
+            // Exception groups can't be empty
+            assert!(PyBaseExceptionGroup::new(py, "empty", PyList::empty(py)).is_err());
+        });
+    }
 }
@@ -60,12 +60,25 @@ extern "C" {
 #[repr(C)]
 #[derive(Copy, Clone)]
 pub struct _frozen {
-    name: *const c_char,
-    code: *const c_uchar,
-    size: c_int,
+    pub name: *const c_char,
+    pub code: *const c_uchar,
+    pub size: c_int,
+    #[cfg(Py_3_11)]
+    pub is_package: c_int,
+    #[cfg(all(Py_3_11, not(Py_3_13)))]
+    pub get_code: Option<unsafe extern "C" fn() -> *mut PyObject>,
 }
 
 #[cfg_attr(windows, link(name = "pythonXY"))]
 extern "C" {
     pub static mut PyImport_FrozenModules: *const _frozen;
 }
+
+// Private tables which are only declared in the public headers of Python 3.11, and which the
+// Windows DLL doesn't export.
+#[cfg(all(Py_3_11, not(Py_3_12), not(windows)))]
+extern "C" {
+    pub static mut _PyImport_FrozenBootstrap: *const _frozen;
+    pub static mut _PyImport_FrozenStdlib: *const _frozen;
+    pub static mut _PyImport_FrozenTest: *const _frozen;
+}
@@ -0,0 +1,30 @@
+use crate::ffi::cpython::object::Py_buffer;
+use crate::ffi::object::*;
+use crate::ffi::pyport::{Py_hash_t, Py_ssize_t};
+use std::os::raw::c_int;
+
+// skipped _PyManagedBufferObject
+
+#[repr(C)]
+pub struct PyMemoryViewObject {
+    pub ob_base: PyVarObject,
+    pub mbuf: *mut PyObject,
+    pub hash: Py_hash_t,
+    pub flags: c_int,
+    pub exports: Py_ssize_t,
+    pub view: Py_buffer,
+    pub weakreflist: *mut PyObject,
+    pub ob_array: [Py_ssize_t; 1],
+}
+
+/// Macro, trading safety for speed
+#[inline]
+pub unsafe fn PyMemoryView_GET_BUFFER(op: *mut PyObject) -> *mut Py_buffer {
+    &mut (*(op as *mut PyMemoryViewObject)).view
+}
+
+/// Macro, trading safety for speed
+#[inline]
+pub unsafe fn PyMemoryView_GET_BASE(op: *mut PyObject) -> *mut PyObject {
+    (*(op as *mut PyMemoryViewObject)).view.obj
+}
@@ -14,6 +14,8 @@ pub mod import;
 pub mod initconfig;
 // skipped interpreteridobject.h
 pub mod listobject;
+#[cfg(not(PyPy))]
+pub mod memoryobject;
 pub mod object;
 pub mod pydebug;
 #[cfg(all(Py_3_8, not(PyPy)))]
@@ -32,6 +34,8 @@ pub use self::import::*;
 #[cfg(all(Py_3_8, not(PyPy)))]
 pub use self::initconfig::*;
 pub use self::listobject::*;
+#[cfg(not(PyPy))]
+pub use self::memoryobject::*;
 pub use self::object::*;
 pub use self::pydebug::*;
 #[cfg(all(Py_3_8, not(PyPy)))]
@@ -35,7 +35,7 @@ extern "C" {
     // skipped _Py_gitidentifier
     // skipped _Py_getversion
 
-    // skipped _Py_IsFinalizing
+    pub fn _Py_IsFinalizing() -> c_int;
 
     // skipped _PyOS_URandom
     // skipped _PyOS_URandomNonblock
@@ -15,9 +15,6 @@ pub unsafe fn PyMemoryView_Check(op: *mut PyObject) -> c_int {
     (Py_TYPE(op) == &mut PyMemoryView_Type) as c_int
 }
 
-// skipped non-limited PyMemoryView_GET_BUFFER
-// skipped non-limited PyMemeryView_GET_BASE
-
 extern "C" {
     #[cfg_attr(PyPy, link_name = "PyPyMemoryView_FromObject")]
     pub fn PyMemoryView_FromObject(base: *mut PyObject) -> *mut PyObject;
@@ -38,6 +38,8 @@ pub use self::fileobject::*;
 pub use self::floatobject::*;
 #[cfg(not(Py_LIMITED_API))]
 pub use self::funcobject::*;
+#[cfg(Py_3_9)]
+pub use self::genericaliasobject::*;
 #[cfg(not(Py_LIMITED_API))]
 pub use self::genobject::*;
 pub use self::import::*;
@@ -115,7 +117,8 @@ mod floatobject; // TODO supports PEP-384 only
 // skipped empty frameobject.h
 #[cfg(not(Py_LIMITED_API))]
 pub(crate) mod funcobject;
-// skipped genericaliasobject.h
+#[cfg(Py_3_9)]
+mod genericaliasobject;
 #[cfg(not(Py_LIMITED_API))]
 mod genobject; // TODO: incomplete
 mod import; // TODO: incomplete
@@ -8,12 +8,23 @@ pub struct PyBaseExceptionObject {
     pub ob_base: PyObject,
     pub dict: *mut PyObject,
     pub args: *mut PyObject,
+    #[cfg(Py_3_11)]
+    pub notes: *mut PyObject,
     pub traceback: *mut PyObject,
     pub context: *mut PyObject,
     pub cause: *mut PyObject,
     pub suppress_context: char,
 }
 
+#[cfg(Py_3_11)]
+#[repr(C)]
+#[derive(Debug)]
+pub struct PyBaseExceptionGroupObject {
+    pub exception_base: PyBaseExceptionObject,
+    pub msg: *mut PyObject,
+    pub excs: *mut PyObject,
+}
+
 #[repr(C)]
 #[derive(Debug)]
 pub struct PySyntaxErrorObject {
@@ -177,6 +188,9 @@ extern "C" {
     pub static mut PyExc_BaseException: *mut PyObject;
     #[cfg_attr(PyPy, link_name = "PyPyExc_Exception")]
     pub static mut PyExc_Exception: *mut PyObject;
+    #[cfg(Py_3_11)]
+    #[cfg_attr(PyPy, link_name = "PyPyExc_BaseExceptionGroup")]
+    pub static mut PyExc_BaseExceptionGroup: *mut PyObject;
     #[cfg_attr(PyPy, link_name = "PyPyExc_StopAsyncIteration")]
     pub static mut PyExc_StopAsyncIteration: *mut PyObject;
 
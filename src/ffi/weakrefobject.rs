@@ -4,7 +4,8 @@ use std::os::raw::c_int;
 opaque_struct!(PyWeakReference);
 
 extern "C" {
-    static mut _PyWeakref_RefType: PyTypeObject;
+    #[cfg(not(PyPy))]
+    pub static mut _PyWeakref_RefType: PyTypeObject;
     static mut _PyWeakref_ProxyType: PyTypeObject;
     static mut _PyWeakref_CallableProxyType: PyTypeObject;
 
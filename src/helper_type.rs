@@ -0,0 +1,101 @@
+//! Type objects for PyO3's own helper objects, such as the iterators returned from
+//! `#[pyfunction]`s, which wrap a Rust value and are created lazily on first use.
+
+use crate::once_cell::GILOnceCell;
+use crate::pyclass::{fallback_new, get_type_free, tp_free_fallback};
+use crate::types::PyType;
+use crate::{ffi, Py, PyErr, PyResult, Python};
+use std::os::raw::{c_int, c_void};
+use std::ptr;
+
+/// The layout of a helper object, which owns a `T`.
+#[repr(C)]
+struct HelperObject<T> {
+    ob_base: ffi::PyObject,
+    contents: T,
+}
+
+/// Returns the helper type stored in `type_object`, creating it on first use.
+///
+/// Instances of the type own a `T`, which is dropped when they are deallocated. Besides `slots`,
+/// the type gets a `tp_new` which raises `TypeError`, so it can't be instantiated from Python.
+/// `name` must be NUL-terminated.
+pub(crate) fn get_or_create_helper_type<'py, T>(
+    py: Python<'py>,
+    type_object: &'py GILOnceCell<Py<PyType>>,
+    name: &'static str,
+    slots: &[ffi::PyType_Slot],
+) -> PyResult<&'py PyType> {
+    if let Some(ty) = type_object.get(py) {
+        return Ok(ty.as_ref(py));
+    }
+
+    let mut all_slots = vec![
+        ffi::PyType_Slot {
+            slot: ffi::Py_tp_new,
+            pfunc: fallback_new as *mut c_void,
+        },
+        ffi::PyType_Slot {
+            slot: ffi::Py_tp_dealloc,
+            pfunc: helper_dealloc::<T> as *mut c_void,
+        },
+    ];
+    all_slots.extend_from_slice(slots);
+    all_slots.push(ffi::PyType_Slot {
+        slot: 0,
+        pfunc: ptr::null_mut(),
+    });
+    let mut spec = ffi::PyType_Spec {
+        name: name.as_ptr() as _,
+        basicsize: std::mem::size_of::<HelperObject<T>>() as c_int,
+        itemsize: 0,
+        flags: ffi::Py_TPFLAGS_DEFAULT as _,
+        slots: all_slots.as_mut_ptr(),
+    };
+    let ty: Py<PyType> = unsafe { Py::from_owned_ptr_or_err(py, ffi::PyType_FromSpec(&mut spec))? };
+    let _ = type_object.set(py, ty);
+    Ok(type_object.get(py).unwrap().as_ref(py))
+}
+
+/// Creates an instance of the helper type `ty` which owns `contents`.
+///
+/// # Safety
+/// `ty` must have been created by `get_or_create_helper_type::<T>`.
+pub(crate) unsafe fn new_helper_object<T>(
+    py: Python,
+    ty: &PyType,
+    contents: T,
+) -> PyResult<*mut ffi::PyObject> {
+    let ty = ty.as_type_ptr();
+    #[cfg(not(Py_3_8))]
+    crate::pyclass::bpo_35810_workaround(py, ty);
+
+    let obj = ffi::PyType_GenericAlloc(ty, 0);
+    if obj.is_null() {
+        return Err(PyErr::fetch(py));
+    }
+    ptr::write(&mut (*(obj as *mut HelperObject<T>)).contents, contents);
+    Ok(obj)
+}
+
+/// Returns the value owned by a helper object.
+///
+/// # Safety
+/// `obj` must be an instance of a type created by `get_or_create_helper_type::<T>`, and the
+/// returned reference must not outlive it or alias another reference to its contents.
+pub(crate) unsafe fn helper_contents<'a, T>(obj: *mut ffi::PyObject) -> &'a mut T {
+    &mut (*(obj as *mut HelperObject<T>)).contents
+}
+
+unsafe extern "C" fn helper_dealloc<T>(obj: *mut ffi::PyObject) {
+    let _pool = crate::GILPool::new();
+    ptr::drop_in_place(helper_contents::<T>(obj));
+
+    let ty = ffi::Py_TYPE(obj);
+    let free = get_type_free(ty).unwrap_or_else(|| tp_free_fallback(ty));
+    free(obj as *mut c_void);
+
+    if cfg!(Py_3_8) {
+        ffi::Py_DECREF(ty as *mut ffi::PyObject);
+    }
+}
@@ -0,0 +1,148 @@
+//! Support for `#[pyfunction]`s which return `impl Iterator`.
+
+use crate::callback::IntoPyCallbackOutput;
+use crate::exceptions::PyValueError;
+use crate::helper_type::{get_or_create_helper_type, helper_contents, new_helper_object};
+use crate::once_cell::GILOnceCell;
+use crate::types::PyType;
+use crate::{ffi, IntoPyPointer, Py, PyObject, PyResult, Python};
+use std::os::raw::c_void;
+use std::ptr;
+
+/// Object-safe view of a Rust iterator whose items can be converted to Python objects.
+trait DynIterator: Send {
+    fn next_object(&mut self, py: Python) -> Option<PyResult<PyObject>>;
+}
+
+impl<I> DynIterator for I
+where
+    I: Iterator + Send,
+    I::Item: IntoPyCallbackOutput<PyObject>,
+{
+    fn next_object(&mut self, py: Python) -> Option<PyResult<PyObject>> {
+        self.next().map(|item| item.convert(py))
+    }
+}
+
+enum IteratorState {
+    Ready(Box<dyn DynIterator>),
+    /// The iterator has been temporarily taken out to call `next()` on it.
+    Running,
+    Exhausted,
+}
+
+/// Wraps a Rust iterator in a Python iterator object.
+///
+/// Each item is converted to a Python object as it is yielded. If the iterator yields
+/// `Err(...)`, the error is raised from `__next__` and the iteration can be resumed afterwards.
+pub fn wrap_iterator<I>(py: Python, iter: I) -> PyResult<PyObject>
+where
+    I: Iterator + Send + 'static,
+    I::Item: IntoPyCallbackOutput<PyObject>,
+{
+    let ty = iterator_type(py)?;
+    unsafe {
+        let obj = new_helper_object(py, ty, IteratorState::Ready(Box::new(iter)))?;
+        Ok(PyObject::from_owned_ptr(py, obj))
+    }
+}
+
+fn iterator_type(py: Python) -> PyResult<&PyType> {
+    static TYPE_OBJECT: GILOnceCell<Py<PyType>> = GILOnceCell::new();
+
+    get_or_create_helper_type::<IteratorState>(
+        py,
+        &TYPE_OBJECT,
+        "pyo3_runtime.RustIterator\0",
+        &[
+            ffi::PyType_Slot {
+                slot: ffi::Py_tp_iter,
+                pfunc: ffi::PyObject_SelfIter as *mut c_void,
+            },
+            ffi::PyType_Slot {
+                slot: ffi::Py_tp_iternext,
+                pfunc: iterator_next as *mut c_void,
+            },
+        ],
+    )
+}
+
+unsafe extern "C" fn iterator_next(slf: *mut ffi::PyObject) -> *mut ffi::PyObject {
+    crate::callback::handle_panic(|py| {
+        let state = helper_contents::<IteratorState>(slf);
+        let mut iter = match std::mem::replace(state, IteratorState::Running) {
+            IteratorState::Ready(iter) => iter,
+            IteratorState::Running => {
+                return Err(PyValueError::new_err("iterator already executing"));
+            }
+            IteratorState::Exhausted => {
+                *state = IteratorState::Exhausted;
+                return Ok(ptr::null_mut());
+            }
+        };
+        // The state must not be borrowed while calling into the iterator, as converting the item
+        // to a Python object may re-enter this function.
+        let next = iter.next_object(py);
+        let state = helper_contents::<IteratorState>(slf);
+        match next {
+            Some(item) => {
+                *state = IteratorState::Ready(iter);
+                item.map(|obj| obj.into_ptr())
+            }
+            None => {
+                *state = IteratorState::Exhausted;
+                // Returning NULL without an exception set signals the end of iteration.
+                Ok(ptr::null_mut())
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::wrap_iterator;
+    use crate::exceptions::PyValueError;
+    use crate::{py_run, PyResult, Python};
+
+    #[test]
+    fn test_wrap_iterator() {
+        Python::with_gil(|py| {
+            let it = wrap_iterator(py, 0..5u32).unwrap();
+            py_run!(py, it, "assert iter(it) is it");
+            py_run!(py, it, "assert list(it) == [0, 1, 2, 3, 4]");
+            py_run!(py, it, "assert list(it) == []");
+        });
+    }
+
+    #[test]
+    fn test_wrap_iterator_errors() {
+        Python::with_gil(|py| {
+            let items: Vec<PyResult<u32>> =
+                vec![Ok(1), Err(PyValueError::new_err("bad item")), Ok(2)];
+            let it = wrap_iterator(py, items.into_iter()).unwrap();
+            py_run!(
+                py,
+                it,
+                r#"
+                assert next(it) == 1
+                try:
+                    next(it)
+                except ValueError as e:
+                    assert str(e) == "bad item"
+                else:
+                    assert False
+                assert next(it) == 2
+                "#
+            );
+        });
+    }
+
+    #[test]
+    fn test_cannot_instantiate() {
+        Python::with_gil(|py| {
+            let it = wrap_iterator(py, std::iter::empty::<u32>()).unwrap();
+            let ty = it.as_ref(py).get_type();
+            assert!(ty.call0().is_err());
+        });
+    }
+}
@@ -137,10 +137,12 @@ pub use crate::err::{PyDowncastError, PyErr, PyErrArguments, PyResult};
 pub use crate::gil::{prepare_freethreaded_python, with_embedded_python_interpreter};
 pub use crate::gil::{GILGuard, GILPool};
 pub use crate::instance::{Py, PyNativeType, PyObject};
-pub use crate::pycell::{PyCell, PyRef, PyRefMut};
+pub use crate::pycell::{MappedPyRef, PyCell, PyRef, PyRefMut};
 pub use crate::pyclass::PyClass;
 pub use crate::pyclass_init::PyClassInitializer;
-pub use crate::python::{Python, PythonVersionInfo};
+#[cfg(not(any(Py_LIMITED_API, PyPy)))]
+pub use crate::python::FrozenModuleInfo;
+pub use crate::python::{MissingModule, Python, PythonVersionInfo, ScopedRedirect};
 pub use crate::type_object::PyTypeInfo;
 // Since PyAny is as important as PyObject, we expose it to the top level.
 pub use crate::types::PyAny;
@@ -167,6 +169,7 @@ pub mod buffer;
 #[doc(hidden)]
 pub mod callback;
 pub mod class;
+mod closure;
 pub mod conversion;
 mod conversions;
 #[macro_use]
@@ -177,6 +180,7 @@ pub mod exceptions;
 pub mod ffi;
 pub mod freelist;
 mod gil;
+mod helper_type;
 pub mod impl_;
 mod instance;
 
@@ -192,9 +196,12 @@ pub mod pyclass;
 pub mod pyclass_init;
 pub mod pyclass_slots;
 mod python;
+pub mod test_utils;
 pub mod type_object;
 pub mod types;
 
+#[cfg(feature = "log")]
+pub mod log;
 #[cfg(feature = "serde")]
 pub mod serde;
 
@@ -0,0 +1,342 @@
+//! Bridges between Python's `logging` module and Rust's [`log`](https://docs.rs/log) facade.
+//!
+//! [`setup_logging`] routes records logged from Python to the Rust logger, and [`PythonLogger`]
+//! routes records logged from Rust to Python's `logging` module. Both can be used at the same
+//! time: records are never passed back in the direction they came from.
+//!
+//! Python logger names such as `package.module` correspond to Rust log targets such as
+//! `package::module`.
+//!
+//! # Examples
+//!
+//! ```
+//! use pyo3::prelude::*;
+//!
+//! Python::with_gil(|py| -> PyResult<()> {
+//!     // Records logged by Python code at `INFO` and above now go to the Rust logger
+//!     pyo3::log::setup_logging(py, log::LevelFilter::Info)?;
+//!     py.run("import logging; logging.getLogger('app').info('hello from Python')", None, None)
+//! })
+//! .unwrap();
+//! ```
+use crate::types::{PyDict, PyModule, PyTuple, PyType};
+use crate::{ffi, PyAny, PyResult, Python};
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::cell::Cell;
+
+thread_local! {
+    /// Set while `PythonLogger` is passing a record to Python, so that a handler installed by
+    /// `setup_logging` doesn't send it straight back to Rust.
+    static FORWARDING_TO_PYTHON: Cell<bool> = Cell::new(false);
+}
+
+// Python's logging levels; `TRACE` has no Python equivalent and uses a level below `DEBUG`.
+const PY_CRITICAL: u32 = 50;
+const PY_ERROR: u32 = 40;
+const PY_WARNING: u32 = 30;
+const PY_INFO: u32 = 20;
+const PY_DEBUG: u32 = 10;
+const PY_TRACE: u32 = 5;
+
+fn level_from_python(levelno: u32) -> Level {
+    if levelno >= PY_ERROR {
+        Level::Error
+    } else if levelno >= PY_WARNING {
+        Level::Warn
+    } else if levelno >= PY_INFO {
+        Level::Info
+    } else if levelno >= PY_DEBUG {
+        Level::Debug
+    } else {
+        Level::Trace
+    }
+}
+
+fn level_to_python(level: Level) -> u32 {
+    match level {
+        Level::Error => PY_ERROR,
+        Level::Warn => PY_WARNING,
+        Level::Info => PY_INFO,
+        Level::Debug => PY_DEBUG,
+        Level::Trace => PY_TRACE,
+    }
+}
+
+fn level_filter_to_python(filter: LevelFilter) -> u32 {
+    match filter.to_level() {
+        Some(level) => level_to_python(level),
+        None => PY_CRITICAL + 1,
+    }
+}
+
+/// Installs a handler on Python's root logger which passes every record to the Rust logger.
+///
+/// The root logger's level is set from `level`, so that Python doesn't create records which
+/// would be filtered out anyway. The Rust logger applies its own filtering as usual.
+///
+/// The handler formats the record's message with `record.getMessage()`, and uses the Python
+/// logger's name as the log target.
+pub fn setup_logging(py: Python, level: LevelFilter) -> PyResult<()> {
+    let logging = PyModule::import(py, "logging")?;
+    let emit = crate::types::PyCFunction::new_with_keywords(
+        emit_record,
+        "emit",
+        "Passes the record to the Rust logger.\0",
+        py.into(),
+    )?;
+    // Builtin functions don't bind `self`, so `handler.emit(record)` calls `emit(record)`
+    let dict = PyDict::new(py);
+    dict.set_item("emit", emit)?;
+    dict.set_item("__module__", "pyo3")?;
+    let handler_type =
+        py.get_type::<PyType>()
+            .call1(("RustLogHandler", (logging.getattr("Handler")?,), dict))?;
+    let handler = handler_type.call0()?;
+
+    let root = logging.call_method0("getLogger")?;
+    root.call_method1("setLevel", (level_filter_to_python(level),))?;
+    root.call_method1("addHandler", (handler,))?;
+    Ok(())
+}
+
+unsafe extern "C" fn emit_record(
+    _slf: *mut ffi::PyObject,
+    args: *mut ffi::PyObject,
+    _kwargs: *mut ffi::PyObject,
+) -> *mut ffi::PyObject {
+    crate::callback_body!(py, {
+        let args: &PyTuple = py.from_borrowed_ptr(args);
+        log_python_record(args.get_item(0))
+    })
+}
+
+fn log_python_record(record: &PyAny) -> PyResult<()> {
+    if FORWARDING_TO_PYTHON.with(Cell::get) {
+        return Ok(());
+    }
+    let level = level_from_python(record.getattr("levelno")?.extract()?);
+    let target = record
+        .getattr("name")?
+        .extract::<&str>()?
+        .replace('.', "::");
+    let metadata = Metadata::builder().level(level).target(&target).build();
+    let logger = log::logger();
+    if !logger.enabled(&metadata) {
+        return Ok(());
+    }
+
+    let message: &str = record.call_method0("getMessage")?.extract()?;
+    let file: Option<&str> = record.getattr("pathname")?.extract()?;
+    let line: Option<u32> = record.getattr("lineno")?.extract()?;
+    logger.log(
+        &Record::builder()
+            .metadata(metadata)
+            .args(format_args!("{}", message))
+            .file(file)
+            .line(line)
+            .build(),
+    );
+    Ok(())
+}
+
+/// A Rust logger which passes every record to Python's `logging` module.
+///
+/// Records are logged with `logging.getLogger(target).log(level, message)`, where `::` in the
+/// target is replaced by `.`. `TRACE` records use level 5, which is below Python's `DEBUG`.
+///
+/// Logging acquires the GIL, so the Python interpreter must be initialized before anything is
+/// logged. Exceptions raised by Python are printed to `sys.stderr`.
+///
+/// # Examples
+///
+/// ```
+/// use pyo3::prelude::*;
+///
+/// pyo3::log::PythonLogger::init(log::LevelFilter::Info).unwrap();
+/// Python::with_gil(|py| {
+///     py.run("import logging; logging.basicConfig(level=logging.INFO)", None, None)
+///         .unwrap();
+/// });
+/// log::info!("hello from Rust");
+/// ```
+#[derive(Debug)]
+pub struct PythonLogger {
+    _private: (),
+}
+
+static PYTHON_LOGGER: PythonLogger = PythonLogger { _private: () };
+
+impl PythonLogger {
+    /// Installs the `PythonLogger` as the Rust logger, with the maximum level `level`.
+    ///
+    /// Fails if a Rust logger has already been installed.
+    pub fn init(level: LevelFilter) -> Result<(), log::SetLoggerError> {
+        log::set_logger(&PYTHON_LOGGER)?;
+        log::set_max_level(level);
+        Ok(())
+    }
+
+    fn log_to_python(py: Python, record: &Record) -> PyResult<()> {
+        let name = record.target().replace("::", ".");
+        let logger = PyModule::import(py, "logging")?.call_method1("getLogger", (name,))?;
+        logger.call_method1(
+            "log",
+            (level_to_python(record.level()), record.args().to_string()),
+        )?;
+        Ok(())
+    }
+}
+
+impl Log for PythonLogger {
+    fn enabled(&self, _metadata: &Metadata) -> bool {
+        // Python's loggers do their own filtering
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        if FORWARDING_TO_PYTHON.with(Cell::get) {
+            return;
+        }
+        Python::with_gil(|py| {
+            FORWARDING_TO_PYTHON.with(|forwarding| forwarding.set(true));
+            let result = Self::log_to_python(py, record);
+            FORWARDING_TO_PYTHON.with(|forwarding| forwarding.set(false));
+            if let Err(err) = result {
+                err.print(py);
+            }
+        });
+    }
+
+    fn flush(&self) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Captures the records passed to the Rust logger as `(level, target, message)`.
+    struct CapturingLogger {
+        records: Mutex<Vec<(Level, String, String)>>,
+    }
+
+    impl Log for CapturingLogger {
+        fn enabled(&self, metadata: &Metadata) -> bool {
+            metadata.target().starts_with("pyo3_log_test")
+        }
+
+        fn log(&self, record: &Record) {
+            if self.enabled(record.metadata()) {
+                self.records.lock().unwrap().push((
+                    record.level(),
+                    record.target().to_owned(),
+                    record.args().to_string(),
+                ));
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    static CAPTURING_LOGGER: CapturingLogger = CapturingLogger {
+        records: Mutex::new(Vec::new()),
+    };
+
+    #[test]
+    fn test_python_to_rust_and_back() {
+        log::set_logger(&CAPTURING_LOGGER).unwrap();
+        log::set_max_level(LevelFilter::Trace);
+
+        Python::with_gil(|py| {
+            setup_logging(py, LevelFilter::Info).unwrap();
+            crate::py_run!(
+                py,
+                *PyDict::new(py),
+                r#"
+                import logging
+                logger = logging.getLogger("pyo3_log_test.module")
+                logger.debug("filtered out")
+                logger.info("hello %s", "world")
+                logger.error("oops")
+                "#
+            );
+            assert_eq!(
+                *CAPTURING_LOGGER.records.lock().unwrap(),
+                vec![
+                    (
+                        Level::Info,
+                        "pyo3_log_test::module".to_owned(),
+                        "hello world".to_owned()
+                    ),
+                    (
+                        Level::Error,
+                        "pyo3_log_test::module".to_owned(),
+                        "oops".to_owned()
+                    ),
+                ]
+            );
+            CAPTURING_LOGGER.records.lock().unwrap().clear();
+
+            // Records from `PythonLogger` reach Python handlers, but are not sent back to Rust
+            let messages = crate::types::PyList::empty(py);
+            let handler = py
+                .eval(
+                    "type('Handler', (__import__('logging').Handler,), {})()",
+                    None,
+                    None,
+                )
+                .unwrap();
+            handler
+                .setattr("emit", messages.getattr("append").unwrap())
+                .unwrap();
+            let python_logger = PyModule::import(py, "logging")
+                .unwrap()
+                .call_method1("getLogger", ("pyo3_log_test.rust",))
+                .unwrap();
+            python_logger
+                .call_method1("addHandler", (handler,))
+                .unwrap();
+            PYTHON_LOGGER.log(
+                &Record::builder()
+                    .level(Level::Warn)
+                    .target("pyo3_log_test::rust")
+                    .args(format_args!("from {}", "Rust"))
+                    .build(),
+            );
+            assert_eq!(messages.len(), 1);
+            let record = messages.get_item(0);
+            assert_eq!(
+                record
+                    .call_method0("getMessage")
+                    .unwrap()
+                    .extract::<&str>()
+                    .unwrap(),
+                "from Rust"
+            );
+            assert_eq!(
+                record.getattr("levelno").unwrap().extract::<u32>().unwrap(),
+                PY_WARNING
+            );
+            assert!(CAPTURING_LOGGER.records.lock().unwrap().is_empty());
+        });
+    }
+
+    #[test]
+    fn test_level_conversions() {
+        assert_eq!(level_from_python(PY_CRITICAL), Level::Error);
+        assert_eq!(level_from_python(PY_WARNING), Level::Warn);
+        assert_eq!(level_from_python(15), Level::Debug);
+        assert_eq!(level_from_python(0), Level::Trace);
+        assert_eq!(level_filter_to_python(LevelFilter::Off), PY_CRITICAL + 1);
+        assert_eq!(level_filter_to_python(LevelFilter::Trace), PY_TRACE);
+        for &level in &[
+            Level::Error,
+            Level::Warn,
+            Level::Info,
+            Level::Debug,
+            Level::Trace,
+        ] {
+            assert_eq!(level_from_python(level_to_python(level)), level);
+        }
+    }
+}
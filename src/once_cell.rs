@@ -1,4 +1,4 @@
-use crate::Python;
+use crate::{ffi, AsPyPointer, PyAny, PyNativeType, PyObject, PyResult, Python};
 use std::cell::UnsafeCell;
 
 /// A write-once cell similar to [`once_cell::OnceCell`](https://docs.rs/once_cell/1.4.0/once_cell/).
@@ -101,3 +101,93 @@ impl<T> GILOnceCell<T> {
         Ok(())
     }
 }
+
+/// A cache for the bound method looked up by [`PyAny::call_method_cached`].
+///
+/// Looking up a method creates a new bound method object on each call. When the same method is
+/// called on the same object repeatedly, e.g. a plugin callback in a hot loop, `CachedMethod`
+/// keeps the bound method from the previous lookup and reuses it as long as it is called on the
+/// same object and the object's type has not changed.
+///
+/// The cache holds a reference to the last object it was used with, keeping it alive until the
+/// method is called on a different object. Rebinding the attribute on the instance itself (e.g.
+/// `obj.method = other`) is not detected.
+///
+/// # Examples
+///
+/// ```
+/// use pyo3::prelude::*;
+/// use pyo3::once_cell::CachedMethod;
+/// use pyo3::types::PyTuple;
+///
+/// static APPEND: CachedMethod = CachedMethod::new("append");
+///
+/// Python::with_gil(|py| -> PyResult<()> {
+///     let list = py.eval("[]", None, None)?;
+///     for i in 0..3 {
+///         list.call_method_cached(&APPEND, PyTuple::new(py, &[i]))?;
+///     }
+///     assert_eq!(list.extract::<Vec<i32>>()?, vec![0, 1, 2]);
+///     Ok(())
+/// })
+/// .unwrap();
+/// ```
+pub struct CachedMethod {
+    name: &'static str,
+    cache: UnsafeCell<Option<BoundMethod>>,
+}
+
+struct BoundMethod {
+    object: PyObject,
+    ty: *mut ffi::PyTypeObject,
+    method: PyObject,
+}
+
+// The cache is only accessed while holding the GIL, and `ty` is only used for comparison.
+unsafe impl Sync for CachedMethod {}
+unsafe impl Send for CachedMethod {}
+
+impl CachedMethod {
+    /// Creates an empty cache for the method `name`.
+    pub const fn new(name: &'static str) -> Self {
+        CachedMethod {
+            name,
+            cache: UnsafeCell::new(None),
+        }
+    }
+
+    /// Returns the name of the cached method.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Gets the method bound to `obj`, reusing the cached lookup if possible.
+    pub fn get<'py>(&self, obj: &'py PyAny) -> PyResult<&'py PyAny> {
+        let py = obj.py();
+        let ty = unsafe { ffi::Py_TYPE(obj.as_ptr()) };
+        // Safe because the GIL is held, and no reference into the cache outlives this block.
+        if let Some(cached) = unsafe { &*self.cache.get() } {
+            if cached.object.as_ptr() == obj.as_ptr() && cached.ty == ty {
+                return Ok(cached.method.clone_ref(py).into_ref(py));
+            }
+        }
+
+        // The lookup can run arbitrary Python code, which may use this cache reentrantly, so the
+        // cache is only written once it has finished.
+        let method = obj.getattr(self.name)?;
+        let old = unsafe { &mut *self.cache.get() }.replace(BoundMethod {
+            object: obj.into(),
+            ty,
+            method: method.into(),
+        });
+        // Dropping the previous entry can also run Python code, so do it after the write.
+        drop(old);
+        Ok(method)
+    }
+
+    /// Clears the cache, releasing the cached object and method.
+    pub fn clear(&self, _py: Python) {
+        let old = unsafe { &mut *self.cache.get() }.take();
+        drop(old);
+    }
+}
@@ -193,6 +193,56 @@ impl<T: PyClass> PyCell<T> {
         self.try_borrow().expect("Already mutably borrowed")
     }
 
+    /// Immutably borrows a part of the value `T`, as selected by `f`. This borrow lasts until the
+    /// returned `MappedPyRef` exists.
+    ///
+    /// This is the `PyCell` equivalent of [`std::cell::Ref::map`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is currently mutably borrowed. For a non-panicking variant, use
+    /// [`try_map_borrow`](#method.try_map_borrow).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use pyo3::prelude::*;
+    /// #[pyclass]
+    /// struct Class {
+    ///     data: Vec<u8>,
+    /// }
+    /// Python::with_gil(|py| {
+    ///     let c = PyCell::new(py, Class { data: vec![1, 2, 3] }).unwrap();
+    ///     let data = c.map_borrow(|c| c.data.as_slice());
+    ///     assert_eq!(&*data, &[1, 2, 3]);
+    ///     assert!(c.try_borrow_mut().is_err());
+    /// });
+    /// ```
+    pub fn map_borrow<U: ?Sized, F>(&self, f: F) -> MappedPyRef<'_, T, U>
+    where
+        F: FnOnce(&T) -> &U,
+    {
+        self.try_map_borrow(f).expect("Already mutably borrowed")
+    }
+
+    /// Immutably borrows a part of the value `T`, as selected by `f`, returning an error if the
+    /// value is currently mutably borrowed. This borrow lasts until the returned `MappedPyRef`
+    /// exists.
+    ///
+    /// This is the non-panicking variant of [`map_borrow`](#method.map_borrow).
+    pub fn try_map_borrow<U: ?Sized, F>(&self, f: F) -> Result<MappedPyRef<'_, T, U>, PyBorrowError>
+    where
+        F: FnOnce(&T) -> &U,
+    {
+        let borrow = self.try_borrow()?;
+        // The value lives as long as the cell, and stays immutably borrowed while `borrow` exists.
+        let value = f(unsafe { &*self.inner.get_ptr() });
+        Ok(MappedPyRef {
+            value,
+            _borrow: borrow,
+        })
+    }
+
     /// Mutably borrows the value `T`. This borrow lasts untill the returned `PyRefMut` exists.
     ///
     /// # Panics
@@ -269,6 +319,40 @@ impl<T: PyClass> PyCell<T> {
         }
     }
 
+    /// Mutably borrows the value `T` for the duration of `f`, returning an error if the value is
+    /// currently borrowed.
+    ///
+    /// The borrow is released when `f` returns, so Python code called afterwards can borrow the
+    /// value again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use pyo3::prelude::*;
+    /// #[pyclass]
+    /// struct Counter {
+    ///     count: u32,
+    /// }
+    /// Python::with_gil(|py| {
+    ///     let c = PyCell::new(py, Counter { count: 0 }).unwrap();
+    ///     let count = c.try_borrow_mut_scoped(|counter| {
+    ///         counter.count += 1;
+    ///         counter.count
+    ///     });
+    ///     assert_eq!(count.unwrap(), 1);
+    ///
+    ///     let guard = c.borrow();
+    ///     assert!(c.try_borrow_mut_scoped(|counter| counter.count += 1).is_err());
+    /// });
+    /// ```
+    pub fn try_borrow_mut_scoped<R>(
+        &self,
+        f: impl FnOnce(&mut T) -> R,
+    ) -> Result<R, PyBorrowMutError> {
+        let mut borrow = self.try_borrow_mut()?;
+        Ok(f(&mut borrow))
+    }
+
     /// Immutably borrows the value `T`, returning an error if the value is
     /// currently mutably borrowed.
     ///
@@ -328,6 +412,48 @@ impl<T: PyClass> PyCell<T> {
         std::mem::replace(mut_borrow, replacement)
     }
 
+    /// Conditionally replaces the wrapped value with one computed from `f`.
+    ///
+    /// `f` is given the current value; if it returns `Some`, the wrapped value is replaced and
+    /// `Ok(true)` is returned. If it returns `None`, the value is left untouched and `Ok(false)`
+    /// is returned. As the value is mutably borrowed for the whole call, no other code can observe
+    /// or modify it in between.
+    ///
+    /// Returns an error if the value is currently borrowed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use pyo3::prelude::*;
+    /// #[pyclass]
+    /// struct Counter {
+    ///     count: u32,
+    /// }
+    /// Python::with_gil(|py| {
+    ///     let c = PyCell::new(py, Counter { count: 9 }).unwrap();
+    ///     let increment = |counter: &Counter| {
+    ///         if counter.count < 10 {
+    ///             Some(Counter { count: counter.count + 1 })
+    ///         } else {
+    ///             None
+    ///         }
+    ///     };
+    ///     assert!(c.try_replace_with(increment).unwrap());
+    ///     assert!(!c.try_replace_with(increment).unwrap());
+    ///     assert_eq!(c.borrow().count, 10);
+    /// });
+    /// ```
+    pub fn try_replace_with<F: FnOnce(&T) -> Option<T>>(&self, f: F) -> PyResult<bool> {
+        let mut borrow = self.try_borrow_mut()?;
+        match f(&borrow) {
+            Some(replacement) => {
+                *borrow = replacement;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
     /// Swaps the wrapped value of `self` with the wrapped value of `other`.
     ///
     /// # Panics
@@ -462,6 +588,15 @@ impl<'p, T: PyClass> PyRef<'p, T> {
     pub fn py(&self) -> Python {
         unsafe { Python::assume_gil_acquired() }
     }
+
+    /// Releases a borrow of `cell` which was kept alive by `std::mem::forget`-ing a `PyRef`.
+    ///
+    /// # Safety
+    /// There must be a forgotten `PyRef` borrowing `cell`, which is not released again.
+    #[cfg(not(Py_LIMITED_API))]
+    pub(crate) unsafe fn release_forgotten(cell: &'p PyCell<T>) {
+        drop(PyRef { inner: &cell.inner })
+    }
 }
 
 impl<'p, T, U> AsRef<U> for PyRef<'p, T>
@@ -555,6 +690,9 @@ impl<'a, T: PyClass> std::convert::TryFrom<&'a PyCell<T>> for crate::PyRef<'a, T
 }
 
 impl<'a, T: PyClass> AsPyPointer for PyRef<'a, T> {
+    /// Gets the underlying `PyCell`'s object pointer, e.g. for passing to C extension APIs.
+    ///
+    /// The pointer is borrowed: it is only guaranteed to be valid while this `PyRef` is alive.
     fn as_ptr(&self) -> *mut ffi::PyObject {
         self.inner.as_ptr()
     }
@@ -566,6 +704,30 @@ impl<T: PyClass + fmt::Debug> fmt::Debug for PyRef<'_, T> {
     }
 }
 
+/// Wraps a borrowed reference to a part of a value in a `PyCell<T>`.
+///
+/// Created by [`PyCell::map_borrow`](struct.PyCell.html#method.map_borrow). The `PyCell` stays
+/// immutably borrowed as long as this exists.
+pub struct MappedPyRef<'p, T: PyClass, U: ?Sized> {
+    value: &'p U,
+    _borrow: PyRef<'p, T>,
+}
+
+impl<'p, T: PyClass, U: ?Sized> Deref for MappedPyRef<'p, T, U> {
+    type Target = U;
+
+    #[inline]
+    fn deref(&self) -> &U {
+        self.value
+    }
+}
+
+impl<T: PyClass, U: ?Sized + fmt::Debug> fmt::Debug for MappedPyRef<'_, T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.value, f)
+    }
+}
+
 /// Wraps a mutable borrowed reference to a value in a `PyCell<T>`.
 ///
 /// See the [`PyCell`](struct.PyCell.html) and [`PyRef`](struct.PyRef.html) documentations for more.
@@ -579,6 +741,42 @@ impl<'p, T: PyClass> PyRefMut<'p, T> {
     pub fn py(&self) -> Python {
         unsafe { Python::assume_gil_acquired() }
     }
+
+    /// Releases the mutable borrow and returns the object it was borrowed from.
+    ///
+    /// This is useful to call Python code which may borrow the value again, for example a callback
+    /// passed to a method taking `PyRefMut<Self>`. The value can be borrowed again afterwards
+    /// through the returned `PyCell`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use pyo3::prelude::*;
+    /// #[pyclass]
+    /// struct Counter {
+    ///     count: u32,
+    /// }
+    /// #[pymethods]
+    /// impl Counter {
+    ///     fn increment_and_notify(mut slf: PyRefMut<Self>, callback: &PyAny) -> PyResult<u32> {
+    ///         slf.count += 1;
+    ///         let cell = slf.release();
+    ///         // The callback may borrow the counter again
+    ///         callback.call1((cell,))?;
+    ///         Ok(cell.borrow().count)
+    ///     }
+    /// }
+    /// # Python::with_gil(|py| {
+    /// #     let counter = PyCell::new(py, Counter { count: 0 }).unwrap();
+    /// #     pyo3::py_run!(py, counter, "assert counter.increment_and_notify(lambda c: c.increment_and_notify(lambda c: None)) == 2");
+    /// # });
+    /// ```
+    pub fn release(self) -> &'p PyCell<T> {
+        let py: Python<'p> = unsafe { Python::assume_gil_acquired() };
+        let cell = unsafe { py.from_borrowed_ptr(self.inner.as_ptr()) };
+        drop(self);
+        cell
+    }
 }
 
 impl<'p, T, U> AsRef<U> for PyRefMut<'p, T>
@@ -646,6 +844,9 @@ impl<T: PyClass> IntoPy<PyObject> for PyRefMut<'_, T> {
 }
 
 impl<'a, T: PyClass> AsPyPointer for PyRefMut<'a, T> {
+    /// Gets the underlying `PyCell`'s object pointer, e.g. for passing to C extension APIs.
+    ///
+    /// The pointer is borrowed: it is only guaranteed to be valid while this `PyRefMut` is alive.
     fn as_ptr(&self) -> *mut ffi::PyObject {
         self.inner.as_ptr()
     }
@@ -102,7 +102,7 @@ pub trait PyClassAlloc: PyTypeInfo + PyClassImpl {
 
 // Default new implementation
 
-unsafe extern "C" fn fallback_new(
+pub(crate) unsafe extern "C" fn fallback_new(
     _subtype: *mut ffi::PyTypeObject,
     _args: *mut ffi::PyObject,
     _kwds: *mut ffi::PyObject,
@@ -120,6 +120,21 @@ where
 {
     let pool = crate::GILPool::new();
     let py = pool.python();
+
+    // Run `tp_finalize`, unless the object is an instance of a subclass defined in Python, whose
+    // dealloc has already done so before calling this one.
+    #[cfg(not(Py_LIMITED_API))]
+    {
+        let ty = ffi::Py_TYPE(obj);
+        if (*ty).tp_finalize.is_some()
+            && ty == T::type_object_raw(py)
+            && ffi::PyObject_CallFinalizerFromDealloc(obj) < 0
+        {
+            // The finalizer resurrected the object
+            return;
+        }
+    }
+
     <T as PyClassAlloc>::dealloc(py, (obj as *mut T::Layout) as _)
 }
 
@@ -136,6 +151,14 @@ pub(crate) unsafe fn tp_free_fallback(ty: *mut ffi::PyTypeObject) -> ffi::freefu
 ///
 /// The `#[pyclass]` attribute automatically implements this trait for your Rust struct,
 /// so you don't have to use this trait directly.
+#[cfg_attr(
+    diagnostic_namespace,
+    diagnostic::on_unimplemented(
+        message = "`{Self}` is not a `#[pyclass]`",
+        label = "add `#[pyclass]` to the definition of `{Self}`",
+        note = "`#[pymethods]` and `PyCell` can only be used with `#[pyclass]` types; to expose a type from another crate, wrap it in a `#[pyclass]` newtype"
+    )
+)]
 pub trait PyClass:
     PyTypeInfo<AsRefTarget = PyCell<Self>> + Sized + PyClassAlloc + PyClassImpl<Layout = PyCell<Self>>
 {
@@ -148,6 +171,64 @@ pub trait PyClass:
     type BaseNativeType: PyTypeInfo + PyNativeType;
 }
 
+/// Marker trait for `#[pyclass(unsafe_lifetime)]` types which borrow non-`'static` data.
+///
+/// Python objects can be kept alive for arbitrarily long, so a `#[pyclass]` normally cannot hold
+/// references. `#[pyclass(unsafe_lifetime)]` allows a struct with a single lifetime parameter, and
+/// implements `PyClass` for its `'static` instantiation. The struct must then be created from
+/// data with a shorter lifetime by transmuting it to `'static`, which the compiler cannot check.
+/// The macro requires this trait to be implemented to acknowledge that contract.
+///
+/// As for any `#[pyclass]`, the struct must be `Send` unless the class is `unsendable`, so it can
+/// only borrow data which is `Sync`:
+///
+/// ```compile_fail
+/// use pyo3::prelude::*;
+/// use std::cell::Cell;
+///
+/// #[pyclass(unsafe_lifetime)]
+/// struct Counter<'a> {
+///     count: &'a Cell<u32>, // `Cell` isn't `Sync`, so `Counter` isn't `Send`
+/// }
+///
+/// unsafe impl pyo3::pyclass::PyClassUnsafeLifetime for Counter<'static> {}
+/// ```
+///
+/// # Safety
+///
+/// The data borrowed by the struct must outlive every Python reference to the class instance,
+/// including references held by Python code after control returns to it.
+///
+/// # Examples
+///
+/// ```
+/// use pyo3::prelude::*;
+/// use pyo3::pyclass::PyClassUnsafeLifetime;
+///
+/// #[pyclass(unsafe_lifetime)]
+/// struct Word<'a> {
+///     #[pyo3(get)]
+///     text: &'a str,
+/// }
+///
+/// // SAFETY: every `Word` below is dropped before the text it borrows.
+/// unsafe impl PyClassUnsafeLifetime for Word<'static> {}
+///
+/// let text = String::from("hello");
+/// Python::with_gil(|py| -> PyResult<()> {
+///     let word = Word { text: &text };
+///     let word: Word<'static> = unsafe { std::mem::transmute(word) };
+///     let word = PyCell::new(py, word)?;
+///     assert_eq!(word.getattr("text")?.extract::<&str>()?, "hello");
+///     Ok(())
+/// })
+/// .unwrap();
+/// // The only Python reference to `word` was released at the end of `with_gil`, so it is now
+/// // safe to drop `text`.
+/// drop(text);
+/// ```
+pub unsafe trait PyClassUnsafeLifetime: PyClass {}
+
 /// For collecting slot items.
 #[derive(Default)]
 struct TypeSlots(Vec<ffi::PyType_Slot>);
@@ -197,6 +278,12 @@ where
     if let Some(call_meth) = T::get_call() {
         slots.push(ffi::Py_tp_call, call_meth as _);
     }
+    if let Some(descr_get) = T::get_descr_get() {
+        slots.push(ffi::Py_tp_descr_get, descr_get as _);
+    }
+    if let Some(descr_set) = T::get_descr_set() {
+        slots.push(ffi::Py_tp_descr_set, descr_set as _);
+    }
 
     if cfg!(Py_3_9) {
         let members = py_class_members::<T>();
@@ -219,18 +306,27 @@ where
 
     // protocol methods
     let mut has_gc_methods = false;
+    let mut has_richcompare = false;
+    let mut has_finalizer = false;
     T::for_each_proto_slot(&mut |slot| {
+        has_finalizer |= slot.slot == ffi::Py_tp_finalize;
         has_gc_methods |= slot.slot == ffi::Py_tp_clear;
         has_gc_methods |= slot.slot == ffi::Py_tp_traverse;
+        has_richcompare |= slot.slot == ffi::Py_tp_richcompare;
         slots.0.push(*slot);
     });
 
+    // comparison methods from #[pymethods], unless #[pyproto] already defines __richcmp__
+    if let (Some(richcompare), false) = (T::get_richcompare(), has_richcompare) {
+        slots.push(ffi::Py_tp_richcompare, richcompare as _);
+    }
+
     slots.push(0, ptr::null_mut());
     let mut spec = ffi::PyType_Spec {
         name: get_type_name::<T>(module_name)?,
         basicsize: std::mem::size_of::<T::Layout>() as c_int,
         itemsize: 0,
-        flags: py_class_flags(has_gc_methods, T::IS_GC, T::IS_BASETYPE),
+        flags: py_class_flags(has_gc_methods, T::IS_GC, T::IS_BASETYPE, has_finalizer),
         slots: slots.0.as_mut_ptr(),
     };
 
@@ -299,7 +395,12 @@ fn tp_init_additional<T: PyClass>(type_object: *mut ffi::PyTypeObject) {
 #[cfg(any(Py_LIMITED_API, Py_3_10))]
 fn tp_init_additional<T: PyClass>(_type_object: *mut ffi::PyTypeObject) {}
 
-fn py_class_flags(has_gc_methods: bool, is_gc: bool, is_basetype: bool) -> c_uint {
+fn py_class_flags(
+    has_gc_methods: bool,
+    is_gc: bool,
+    is_basetype: bool,
+    has_finalizer: bool,
+) -> c_uint {
     let mut flags = if has_gc_methods || is_gc {
         ffi::Py_TPFLAGS_DEFAULT | ffi::Py_TPFLAGS_HAVE_GC
     } else {
@@ -308,6 +409,10 @@ fn py_class_flags(has_gc_methods: bool, is_gc: bool, is_basetype: bool) -> c_uin
     if is_basetype {
         flags |= ffi::Py_TPFLAGS_BASETYPE;
     }
+    // Only needed before Python 3.8, where `tp_finalize` is ignored without this flag
+    if has_finalizer {
+        flags |= ffi::Py_TPFLAGS_HAVE_FINALIZE;
+    }
     flags.try_into().unwrap()
 }
 
@@ -316,15 +421,33 @@ fn py_class_method_defs(
 ) -> Vec<ffi::PyMethodDef> {
     let mut defs = Vec::new();
 
+    #[cfg(Py_3_9)]
+    let mut has_class_getitem = false;
+
     for_each_method_def(&mut |def| match def {
         PyMethodDefType::Method(def)
         | PyMethodDefType::Class(def)
         | PyMethodDefType::Static(def) => {
+            #[cfg(Py_3_9)]
+            {
+                has_class_getitem |= def.ml_name.trim_end_matches('\0') == "__class_getitem__";
+            }
             defs.push(def.as_method_def().unwrap());
         }
         _ => (),
     });
 
+    // Support `MyClass[int]` type hints unless the class defines its own `__class_getitem__`
+    #[cfg(Py_3_9)]
+    if !has_class_getitem {
+        defs.push(ffi::PyMethodDef {
+            ml_name: "__class_getitem__\0".as_ptr() as _,
+            ml_meth: Some(class_getitem),
+            ml_flags: ffi::METH_O | ffi::METH_CLASS,
+            ml_doc: "See PEP 585\0".as_ptr() as _,
+        });
+    }
+
     if !defs.is_empty() {
         defs.push(unsafe { std::mem::zeroed() });
     }
@@ -332,6 +455,15 @@ fn py_class_method_defs(
     defs
 }
 
+/// Default `__class_getitem__`, which returns a `types.GenericAlias` like builtin containers do.
+#[cfg(Py_3_9)]
+unsafe extern "C" fn class_getitem(
+    cls: *mut ffi::PyObject,
+    params: *mut ffi::PyObject,
+) -> *mut ffi::PyObject {
+    ffi::Py_GenericAlias(cls, params)
+}
+
 /// Generates the __dictoffset__ and __weaklistoffset__ members, to set tp_dictoffset and
 /// tp_weaklistoffset.
 ///
@@ -426,3 +558,42 @@ fn push_dict_getset(props: &mut Vec<ffi::PyGetSetDef>, is_dummy: bool) {
 
 #[cfg(any(PyPy, all(Py_LIMITED_API, not(Py_3_10))))]
 fn push_dict_getset(_: &mut Vec<ffi::PyGetSetDef>, _is_dummy: bool) {}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    #[cfg(Py_3_9)]
+    fn test_class_getitem_override_is_not_duplicated() {
+        use super::py_class_method_defs;
+        use crate::class::methods::{PyCFunction, PyMethodDef, PyMethodDefType};
+        use crate::ffi;
+        use std::ffi::CStr;
+
+        unsafe extern "C" fn custom_class_getitem(
+            _cls: *mut ffi::PyObject,
+            _params: *mut ffi::PyObject,
+        ) -> *mut ffi::PyObject {
+            std::ptr::null_mut()
+        }
+
+        let def = PyMethodDefType::Class(
+            PyMethodDef::onearg(
+                "__class_getitem__\0",
+                PyCFunction(custom_class_getitem),
+                "\0",
+            )
+            .flags(ffi::METH_CLASS),
+        );
+        let defs = py_class_method_defs(&|f| f(&def));
+
+        // The override followed by the null sentinel
+        assert_eq!(defs.len(), 2);
+        let name = unsafe { CStr::from_ptr(defs[0].ml_name) };
+        assert_eq!(name.to_str().unwrap(), "__class_getitem__");
+        assert_eq!(
+            defs[0].ml_meth.map(|meth| meth as usize),
+            Some(custom_class_getitem as ffi::PyCFunction as usize)
+        );
+        assert!(defs[1].ml_name.is_null());
+    }
+}
@@ -3,10 +3,16 @@
 // based on Daniel Grunwald's https://github.com/dgrunwald/rust-cpython
 
 use crate::err::{PyDowncastError, PyErr, PyResult};
+use crate::exceptions::{PyImportError, PyModuleNotFoundError, PyTypeError};
 use crate::gil::{self, GILGuard, GILPool};
 use crate::type_object::{PyTypeInfo, PyTypeObject};
+#[cfg(not(any(Py_LIMITED_API, PyPy)))]
+use crate::types::PyCode;
 use crate::types::{PyAny, PyDict, PyModule, PyType};
-use crate::{ffi, AsPyPointer, FromPyPointer, IntoPyPointer, PyNativeType, PyObject, PyTryFrom};
+use crate::{
+    ffi, AsPyPointer, FromPyObject, FromPyPointer, IntoPy, IntoPyPointer, Py, PyClass,
+    PyClassInitializer, PyNativeType, PyObject, PyTryFrom,
+};
 use std::ffi::{CStr, CString};
 use std::marker::PhantomData;
 use std::os::raw::{c_char, c_int};
@@ -95,6 +101,82 @@ impl PartialOrd<(u8, u8, u8)> for PythonVersionInfo<'_> {
     }
 }
 
+/// Describes a module frozen into the interpreter, as returned by [Python::frozen_modules].
+#[cfg(not(any(Py_LIMITED_API, PyPy)))]
+#[derive(Debug)]
+pub struct FrozenModuleInfo<'p> {
+    /// The fully qualified module name, e.g. `os.path`.
+    pub name: &'p str,
+    /// Whether the module is a package.
+    pub is_package: bool,
+    /// The size in bytes of the module's marshalled code object.
+    ///
+    /// This is 0 for modules which Python 3.11 and up "deep-freezes" into static code objects.
+    pub size: usize,
+}
+
+/// Restores `sys.stdout` or `sys.stderr` when dropped, as returned by [Python::redirect_stdout]
+/// and [Python::redirect_stderr].
+#[must_use = "the stream is restored as soon as the guard is dropped"]
+pub struct ScopedRedirect<'p> {
+    py: Python<'p>,
+    stream: &'static str,
+    previous: PyObject,
+}
+
+impl Drop for ScopedRedirect<'_> {
+    fn drop(&mut self) {
+        let result = self
+            .py
+            .import("sys")
+            .and_then(|sys| sys.setattr(self.stream, &self.previous));
+        if let Err(err) = result {
+            err.print(self.py);
+        }
+    }
+}
+
+/// A module which could not be imported because it isn't installed, as returned by
+/// [Python::try_import].
+#[derive(Debug)]
+pub struct MissingModule {
+    name: String,
+    err: PyErr,
+}
+
+impl MissingModule {
+    /// Returns the name of the module which could not be found.
+    ///
+    /// This is the module which was imported, or a package containing it.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns a suggestion for installing the missing module.
+    ///
+    /// The suggestion assumes that the distribution package has the same name as the top-level
+    /// package, which is not true of all packages.
+    pub fn suggestion(&self) -> String {
+        let package = self.name.split('.').next().unwrap_or(&self.name);
+        format!("try installing it with `pip install {}`", package)
+    }
+}
+
+/// Converts `MissingModule` back to the original `ModuleNotFoundError`.
+impl std::convert::From<MissingModule> for PyErr {
+    fn from(err: MissingModule) -> PyErr {
+        err.err
+    }
+}
+
+impl std::error::Error for MissingModule {}
+
+impl std::fmt::Display for MissingModule {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        write!(f, "No module named '{}'; {}", self.name, self.suggestion())
+    }
+}
+
 /// Marker type that indicates that the GIL is currently held.
 ///
 /// The `Python` struct is a zero-sized marker struct that is required for most Python operations.
@@ -156,6 +238,77 @@ impl Python<'_> {
     {
         f(unsafe { gil::ensure_gil().python() })
     }
+
+    /// Sets the program name used by the interpreter to compute `sys.executable`, `sys.prefix`
+    /// and the default module search path.
+    ///
+    /// This must be called before the interpreter is initialized, i.e. before the first call to
+    /// [`Python::with_gil`] or [`prepare_freethreaded_python`](crate::prepare_freethreaded_python),
+    /// otherwise it has no effect on the running interpreter.
+    ///
+    /// The name is copied into a buffer which is intentionally leaked, as Python requires it to
+    /// stay valid for the rest of the program.
+    ///
+    /// This is a wrapper around the ffi call `Py_SetProgramName`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use pyo3::prelude::*;
+    ///
+    /// Python::set_program_name("my-embedded-app");
+    /// Python::with_gil(|py| {
+    ///     py.run("import sys; print(sys.executable)", None, None).unwrap();
+    /// #
+    /// #   // The interpreter was initialized with the new name
+    /// #   let ptr = unsafe { pyo3::ffi::Py_GetProgramName() };
+    /// #   let name: String = (0..)
+    /// #       .map(|i| unsafe { *ptr.add(i) } as u32)
+    /// #       .take_while(|&c| c != 0)
+    /// #       .filter_map(std::char::from_u32)
+    /// #       .collect();
+    /// #   assert_eq!(name, "my-embedded-app");
+    /// });
+    /// ```
+    #[cfg(not(PyPy))]
+    #[cfg_attr(docsrs, doc(cfg(not(PyPy))))]
+    pub fn set_program_name(name: &str) {
+        #[cfg(windows)]
+        let wide: Vec<libc::wchar_t> = name.encode_utf16().chain(Some(0)).collect();
+        #[cfg(not(windows))]
+        let wide: Vec<libc::wchar_t> = name
+            .chars()
+            .map(|c| c as libc::wchar_t)
+            .chain(Some(0))
+            .collect();
+        let wide: &'static [libc::wchar_t] = Box::leak(wide.into_boxed_slice());
+        unsafe { ffi::Py_SetProgramName(wide.as_ptr()) }
+    }
+
+    /// Returns whether the Python interpreter is initialized.
+    ///
+    /// This does not require the GIL, so it can be used to check whether it is safe to call
+    /// [`Python::with_gil`] from contexts such as `atexit` or signal handlers, where the
+    /// interpreter may not have been started or may already have been torn down.
+    ///
+    /// This is a wrapper around the ffi call `Py_IsInitialized`.
+    #[inline]
+    pub fn is_initialized() -> bool {
+        unsafe { ffi::Py_IsInitialized() != 0 }
+    }
+
+    /// Returns whether the Python interpreter is in the process of shutting down.
+    ///
+    /// Attempting to acquire the GIL while the interpreter is finalizing is undefined behavior, so
+    /// embedders with complex shutdown sequences should check this together with
+    /// [`Python::is_initialized`] before calling [`Python::with_gil`].
+    ///
+    /// This is a wrapper around the ffi call `_Py_IsFinalizing`.
+    #[cfg(all(Py_3_8, not(any(PyPy, Py_LIMITED_API))))]
+    #[cfg_attr(docsrs, doc(cfg(all(Py_3_8, not(any(PyPy, Py_LIMITED_API))))))]
+    #[inline]
+    pub fn is_finalizing() -> bool {
+        unsafe { ffi::_Py_IsFinalizing() != 0 }
+    }
 }
 
 impl<'p> Python<'p> {
@@ -296,6 +449,43 @@ impl<'p> Python<'p> {
         self.run_code(code, ffi::Py_eval_input, globals, locals)
     }
 
+    /// Evaluates a Python expression and extracts the result as `T`.
+    ///
+    /// The expression is evaluated in the `__main__` module, as with `eval(expr, None, None)`.
+    /// Errors raised by the expression itself are returned unchanged, as are errors other than
+    /// `TypeError` from extracting the result, such as the `OverflowError` from extracting a large
+    /// integer as `u8`. If the result has the wrong type, the returned `TypeError` names the
+    /// expression and has the extraction error as its `__cause__`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use pyo3::prelude::*;
+    /// Python::with_gil(|py| {
+    ///     let squares: Vec<i64> = py.eval_as("[i * i for i in range(4)]").unwrap();
+    ///     assert_eq!(squares, vec![0, 1, 4, 9]);
+    ///
+    ///     let err = py.eval_as::<i64>("'not a number'").unwrap_err();
+    ///     assert!(err.to_string().contains("'not a number'"));
+    /// });
+    /// ```
+    pub fn eval_as<T>(self, expr: &str) -> PyResult<T>
+    where
+        T: FromPyObject<'p>,
+    {
+        let result = self.eval(expr, None, None)?;
+        result.extract().map_err(|err| {
+            if !err.is_instance::<PyTypeError>(self) {
+                return err;
+            }
+            PyTypeError::new_err(format!(
+                "cannot extract the result of `{}` as `{}`",
+                expr,
+                std::any::type_name::<T>()
+            ))
+            .with_cause(self, err)
+        })
+    }
+
     /// Executes one or more Python statements in the given context.
     ///
     /// If `globals` is `None`, it defaults to Python module `__main__`.
@@ -336,6 +526,75 @@ impl<'p> Python<'p> {
         })
     }
 
+    /// Executes a sequence of Python statements and evaluates a final expression, like a cell in
+    /// an interactive session.
+    ///
+    /// All entries of `stmts` except the last are executed as statements, and the last is
+    /// evaluated as an expression whose value is returned. Returns `None` if `stmts` is empty.
+    ///
+    /// If `globals` is `None`, it defaults to Python module `__main__`.
+    /// If `locals` is `None`, it defaults to the value of `globals`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use pyo3::prelude::*;
+    /// # Python::with_gil(|py| -> PyResult<()> {
+    /// let locals = pyo3::types::PyDict::new(py);
+    /// let result = py.eval_multi(&["import math", "x = math.sqrt(16)", "x + 1"], None, Some(locals))?;
+    /// assert_eq!(result.extract::<f64>()?, 5.0);
+    /// # Ok(())
+    /// # }).unwrap();
+    /// ```
+    pub fn eval_multi(
+        self,
+        stmts: &[&str],
+        globals: Option<&PyDict>,
+        locals: Option<&PyDict>,
+    ) -> PyResult<&'p PyAny> {
+        match stmts.split_last() {
+            Some((expr, stmts)) => {
+                for stmt in stmts {
+                    self.run(stmt, globals, locals)?;
+                }
+                self.eval(expr, globals, locals)
+            }
+            None => Ok(self.None().into_ref(self)),
+        }
+    }
+
+    /// Executes a compiled code object with the given globals and locals.
+    ///
+    /// Together with Python's `compile()`, this allows code which is run many times to be
+    /// compiled only once. This is a wrapper around the FFI call `PyEval_EvalCode`.
+    ///
+    /// # Examples
+    /// ```
+    /// use pyo3::prelude::*;
+    /// use pyo3::types::{PyCode, PyDict};
+    ///
+    /// Python::with_gil(|py| -> PyResult<()> {
+    ///     let code: &PyCode = py
+    ///         .eval("compile('total += x', '<string>', 'exec')", None, None)?
+    ///         .downcast()?;
+    ///     let globals = PyDict::new(py);
+    ///     globals.set_item("total", 0)?;
+    ///     for x in 1..=3 {
+    ///         globals.set_item("x", x)?;
+    ///         py.exec_code(code, globals, globals)?;
+    ///     }
+    ///     assert_eq!(globals.get_item("total").unwrap().extract::<i32>()?, 6);
+    ///     Ok(())
+    /// })
+    /// .unwrap();
+    /// ```
+    #[cfg(not(any(Py_LIMITED_API, PyPy)))]
+    pub fn exec_code(self, code: &PyCode, globals: &PyDict, locals: &PyDict) -> PyResult<()> {
+        unsafe {
+            let res_ptr = ffi::PyEval_EvalCode(code.as_ptr(), globals.as_ptr(), locals.as_ptr());
+            self.from_owned_ptr_or_err::<PyAny>(res_ptr).map(drop)
+        }
+    }
+
     /// Runs code in the given context.
     ///
     /// `start` indicates the type of input expected: one of `Py_single_input`,
@@ -385,6 +644,331 @@ impl<'p> Python<'p> {
         PyModule::import(self, name)
     }
 
+    /// Imports the Python module with the specified name, reporting a module which isn't installed
+    /// as a [MissingModule].
+    ///
+    /// `Ok(Err(_))` is only returned if the module itself, or a package containing it, could not
+    /// be found. If the module is found but fails to import, for example because one of its own
+    /// dependencies is missing, the exception is returned as `Err(_)`.
+    ///
+    /// # Examples
+    /// ```
+    /// use pyo3::prelude::*;
+    ///
+    /// Python::with_gil(|py| -> PyResult<()> {
+    ///     assert!(py.try_import("json")?.is_ok());
+    ///
+    ///     let missing = py.try_import("no_such_module")?.unwrap_err();
+    ///     assert_eq!(missing.name(), "no_such_module");
+    ///     assert_eq!(
+    ///         missing.to_string(),
+    ///         "No module named 'no_such_module'; try installing it with `pip install no_such_module`"
+    ///     );
+    ///     Ok(())
+    /// })
+    /// .unwrap();
+    /// ```
+    pub fn try_import(self, name: &str) -> PyResult<Result<&'p PyModule, MissingModule>> {
+        match PyModule::import(self, name) {
+            Ok(module) => Ok(Ok(module)),
+            Err(err) if err.is_instance::<PyModuleNotFoundError>(self) => {
+                let missing: Option<String> = err
+                    .pvalue(self)
+                    .getattr("name")
+                    .and_then(PyAny::extract)
+                    .unwrap_or(None);
+                match missing {
+                    // `missing` is `name` or one of the packages containing it
+                    Some(missing)
+                        if name == missing || name.starts_with(&format!("{}.", missing)) =>
+                    {
+                        Ok(Err(MissingModule { name: missing, err }))
+                    }
+                    _ => Err(err),
+                }
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Registers a Rust closure as an import path hook by prepending it to `sys.path_hooks`.
+    ///
+    /// The hook is called with each `sys.path` entry which is not already present in
+    /// `sys.path_importer_cache`. It should return a path entry finder for the entries it handles
+    /// and raise `ImportError` for all others, so that the import system moves on to the next hook.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use pyo3::prelude::*;
+    /// # use pyo3::exceptions::PyImportError;
+    /// Python::with_gil(|py| -> PyResult<()> {
+    ///     py.add_path_hook(|path| {
+    ///         Python::with_gil(|py| {
+    ///             if path == "<my-archive>" {
+    ///                 // Create a path entry finder for the archive here
+    ///                 Ok(py.None())
+    ///             } else {
+    ///                 Err(PyImportError::new_err(()))
+    ///             }
+    ///         })
+    ///     })
+    /// })
+    /// .unwrap();
+    /// ```
+    pub fn add_path_hook<F>(self, hook: F) -> PyResult<()>
+    where
+        F: Fn(&str) -> PyResult<PyObject> + Send + 'static,
+    {
+        let hook = crate::closure::wrap_closure(self, move |_py, args, kwargs| {
+            if args.len() != 1 || kwargs.map_or(0, PyDict::len) != 0 {
+                return Err(PyTypeError::new_err(
+                    "path hook takes exactly one positional argument",
+                ));
+            }
+            // Raising `ImportError` tells the import system to try the next hook.
+            let path: &str = args
+                .get_item(0)
+                .extract()
+                .map_err(|_| PyImportError::new_err("only str path entries are supported"))?;
+            hook(path)
+        })?;
+        self.import("sys")?
+            .getattr("path_hooks")?
+            .call_method1("insert", (0, hook))?;
+        Ok(())
+    }
+
+    /// Registers an instance of a `#[pyclass]` as an import hook by prepending it to
+    /// `sys.meta_path`.
+    ///
+    /// Finders on `sys.meta_path` are consulted before `sys.path` is searched. The class should
+    /// implement the [`importlib.abc.MetaPathFinder`] protocol, i.e. a `find_spec(fullname, path,
+    /// target=None)` method which returns a module spec for the modules it handles and `None` for
+    /// all others. This allows modules to be served from memory, archives, or other sources.
+    ///
+    /// [`importlib.abc.MetaPathFinder`]: https://docs.python.org/3/library/importlib.html#importlib.abc.MetaPathFinder
+    ///
+    /// # Examples
+    /// ```rust
+    /// use pyo3::prelude::*;
+    ///
+    /// #[pyclass]
+    /// struct VirtualFinder {}
+    ///
+    /// #[pymethods]
+    /// impl VirtualFinder {
+    ///     #[args(target = "None")]
+    ///     fn find_spec(
+    ///         &self,
+    ///         fullname: &str,
+    ///         path: &PyAny,
+    ///         target: Option<&PyAny>,
+    ///     ) -> Option<PyObject> {
+    ///         // Return a module spec here for the modules this finder provides
+    ///         None
+    ///     }
+    /// }
+    ///
+    /// Python::with_gil(|py| -> PyResult<()> {
+    ///     py.add_meta_path_finder(VirtualFinder {})?;
+    ///     # py.import("sys")?.getattr("meta_path")?.call_method1("pop", (0,))?;
+    ///     Ok(())
+    /// })
+    /// .unwrap();
+    /// ```
+    pub fn add_meta_path_finder<T>(self, finder: impl Into<PyClassInitializer<T>>) -> PyResult<()>
+    where
+        T: PyClass,
+    {
+        let finder = Py::new(self, finder)?;
+        self.import("sys")?
+            .getattr("meta_path")?
+            .call_method1("insert", (0, finder))?;
+        Ok(())
+    }
+
+    /// Registers a Rust closure to be called when the interpreter shuts down, like Python's
+    /// `atexit.register`.
+    ///
+    /// The closure is called at the start of `Py_FinalizeEx`, while the interpreter is still fully
+    /// functional, in reverse order of registration along with the other `atexit` callbacks.
+    /// Note that when embedding Python with the `auto-initialize` feature the interpreter is never
+    /// finalized, so the closure will only run if the interpreter is explicitly shut down, e.g. by
+    /// [`with_embedded_python_interpreter`](crate::with_embedded_python_interpreter).
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use pyo3::prelude::*;
+    /// Python::with_gil(|py| {
+    ///     py.add_at_exit(|_py| println!("interpreter shutting down"))
+    ///         .unwrap();
+    /// });
+    /// ```
+    ///
+    /// The closure runs when the interpreter is finalized:
+    /// ```rust
+    /// # #[cfg(not(PyPy))]
+    /// # {
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    ///
+    /// static CALLED: AtomicBool = AtomicBool::new(false);
+    ///
+    /// unsafe {
+    ///     pyo3::with_embedded_python_interpreter(|py| {
+    ///         py.add_at_exit(|_py| CALLED.store(true, Ordering::SeqCst))
+    ///             .unwrap();
+    ///     });
+    /// }
+    /// assert!(CALLED.load(Ordering::SeqCst));
+    /// # }
+    /// ```
+    pub fn add_at_exit<F>(self, f: F) -> PyResult<()>
+    where
+        F: Fn(Python) + Send + 'static,
+    {
+        let callback = crate::closure::wrap_closure(self, move |py, _args, _kwargs| {
+            f(py);
+            Ok(py.None())
+        })?;
+        self.import("atexit")?
+            .call_method1("register", (callback,))?;
+        Ok(())
+    }
+
+    /// Replaces `sys.stdout` with a file-like object which writes to `writer`, until the returned
+    /// guard is dropped.
+    ///
+    /// The object's `write()` method passes the UTF-8 encoding of its argument to `writer`, and
+    /// its `flush()` method flushes `writer`. I/O errors are raised as `OSError`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use pyo3::prelude::*;
+    /// use std::io::Write;
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// #[derive(Clone)]
+    /// struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+    ///
+    /// impl Write for SharedBuffer {
+    ///     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+    ///         self.0.lock().unwrap().write(buf)
+    ///     }
+    ///
+    ///     fn flush(&mut self) -> std::io::Result<()> {
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// Python::with_gil(|py| -> PyResult<()> {
+    ///     let output = SharedBuffer(Arc::new(Mutex::new(Vec::new())));
+    ///     {
+    ///         let _redirect = py.redirect_stdout(output.clone())?;
+    ///         py.run("print('hello')", None, None)?;
+    ///     }
+    ///     assert_eq!(*output.0.lock().unwrap(), b"hello\n");
+    ///     Ok(())
+    /// })
+    /// .unwrap();
+    /// ```
+    pub fn redirect_stdout<W>(self, writer: W) -> PyResult<ScopedRedirect<'p>>
+    where
+        W: std::io::Write + Send + 'static,
+    {
+        self.redirect_stream("stdout", writer)
+    }
+
+    /// Replaces `sys.stderr` with a file-like object which writes to `writer`, until the returned
+    /// guard is dropped.
+    ///
+    /// See [Python::redirect_stdout] for details.
+    pub fn redirect_stderr<W>(self, writer: W) -> PyResult<ScopedRedirect<'p>>
+    where
+        W: std::io::Write + Send + 'static,
+    {
+        self.redirect_stream("stderr", writer)
+    }
+
+    fn redirect_stream<W>(self, stream: &'static str, writer: W) -> PyResult<ScopedRedirect<'p>>
+    where
+        W: std::io::Write + Send + 'static,
+    {
+        let writer = std::sync::Arc::new(std::sync::Mutex::new(writer));
+        let flush_writer = writer.clone();
+        let write = crate::closure::wrap_closure(self, move |py, args, _kwargs| {
+            let text: &str = args.get_item(0).extract()?;
+            writer.lock().unwrap().write_all(text.as_bytes())?;
+            Ok(text.chars().count().into_py(py))
+        })?;
+        let flush = crate::closure::wrap_closure(self, move |py, _args, _kwargs| {
+            flush_writer.lock().unwrap().flush()?;
+            Ok(py.None())
+        })?;
+
+        let file = self.import("types")?.getattr("SimpleNamespace")?.call0()?;
+        file.setattr("write", write)?;
+        file.setattr("flush", flush)?;
+        let sys = self.import("sys")?;
+        let previous = sys.getattr(stream)?.into();
+        sys.setattr(stream, file)?;
+        Ok(ScopedRedirect {
+            py: self,
+            stream,
+            previous,
+        })
+    }
+
+    /// Returns whether the Python error indicator is set.
+    ///
+    /// This is equivalent to [`PyErr::occurred`], and is a wrapper around the ffi call
+    /// `PyErr_Occurred`.
+    #[inline]
+    pub fn error_occurred(self) -> bool {
+        PyErr::occurred(self)
+    }
+
+    /// Clears the Python error indicator, discarding the current exception if there is one.
+    ///
+    /// Use [`PyErr::fetch`] instead to retrieve the exception while clearing it.
+    ///
+    /// This is a wrapper around the ffi call `PyErr_Clear`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use pyo3::prelude::*;
+    /// # use pyo3::exceptions::PyValueError;
+    /// Python::with_gil(|py| {
+    ///     PyValueError::new_err("recoverable").restore(py);
+    ///     assert!(py.error_occurred());
+    ///     py.clear_error();
+    ///     assert!(!py.error_occurred());
+    /// });
+    /// ```
+    #[inline]
+    pub fn clear_error(self) {
+        unsafe { ffi::PyErr_Clear() }
+    }
+
+    /// Gets the namespace of Python builtins such as `len`, `range` and `print`.
+    ///
+    /// If a Python frame is currently executing, this is the builtins namespace of that frame,
+    /// otherwise it is the namespace of the `builtins` module of the current interpreter.
+    ///
+    /// This is a wrapper around the ffi call `PyEval_GetBuiltins`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use pyo3::Python;
+    /// Python::with_gil(|py| {
+    ///     let len = py.builtins().get_item("len").unwrap();
+    ///     let n: usize = len.call1(("hello",)).unwrap().extract().unwrap();
+    ///     assert_eq!(n, 5);
+    /// });
+    /// ```
+    pub fn builtins(self) -> &'p PyDict {
+        unsafe { self.from_borrowed_ptr(ffi::PyEval_GetBuiltins()) }
+    }
+
     /// Gets the Python builtin value `None`.
     #[allow(non_snake_case)] // the Python keyword starts with uppercase
     #[inline]
@@ -442,6 +1026,65 @@ impl<'p> Python<'p> {
         PythonVersionInfo::from_str(version_number_str)
     }
 
+    /// Lists the modules frozen into the interpreter, which are imported from data compiled into
+    /// the executable rather than loaded from disk.
+    ///
+    /// This includes the modules in `PyImport_FrozenModules`, which an embedding application may
+    /// set before initializing Python. On Python 3.11, except on Windows, it also includes the
+    /// standard library modules frozen for faster startup, although Python may still load those
+    /// from disk depending on the `-X frozen_modules` option. Later versions keep those in private
+    /// tables which can't be listed. Modules whose names aren't valid UTF-8 are skipped.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use pyo3::Python;
+    /// Python::with_gil(|py| {
+    ///     for module in py.frozen_modules() {
+    ///         println!("{} ({} bytes)", module.name, module.size);
+    ///     }
+    /// });
+    /// ```
+    #[cfg(not(any(Py_LIMITED_API, PyPy)))]
+    pub fn frozen_modules(self) -> Vec<FrozenModuleInfo<'p>> {
+        unsafe fn collect<'p>(
+            mut frozen: *const ffi::_frozen,
+            modules: &mut Vec<FrozenModuleInfo<'p>>,
+        ) {
+            if frozen.is_null() {
+                return;
+            }
+            // The table is terminated by an entry with a null name
+            while !(*frozen).name.is_null() {
+                let name = CStr::from_ptr((*frozen).name).to_str();
+                // Before Python 3.11 packages are marked by a negative size
+                #[cfg(Py_3_11)]
+                let (is_package, size) = ((*frozen).is_package != 0, (*frozen).size);
+                #[cfg(not(Py_3_11))]
+                let (is_package, size) = ((*frozen).size < 0, (*frozen).size.abs());
+                if let Ok(name) = name {
+                    modules.push(FrozenModuleInfo {
+                        name,
+                        is_package,
+                        size: size as usize,
+                    });
+                }
+                frozen = frozen.offset(1);
+            }
+        }
+
+        let mut modules = Vec::new();
+        unsafe {
+            collect(ffi::PyImport_FrozenModules, &mut modules);
+            #[cfg(all(Py_3_11, not(Py_3_12), not(windows)))]
+            {
+                collect(ffi::_PyImport_FrozenBootstrap, &mut modules);
+                collect(ffi::_PyImport_FrozenStdlib, &mut modules);
+                collect(ffi::_PyImport_FrozenTest, &mut modules);
+            }
+        }
+        modules
+    }
+
     /// Registers the object in the release pool, and tries to downcast to specific type.
     pub fn checked_cast_as<T>(self, obj: PyObject) -> Result<&'p T, PyDowncastError<'p>>
     where
@@ -600,6 +1243,35 @@ impl<'p> Python<'p> {
         }
     }
 
+    /// Returns the interpreter state of the current thread.
+    ///
+    /// This is intended for embedding Python, e.g. to integrate a custom event loop which
+    /// releases and re-acquires the GIL itself using `ffi::PyEval_SaveThread` and
+    /// `ffi::PyEval_RestoreThread`. Prefer [`Python::allow_threads`] where it is sufficient.
+    ///
+    /// The pointer is only valid as long as the current thread's state exists, and using it
+    /// incorrectly is undefined behavior: for example, restoring it on another thread or while
+    /// another thread holds the GIL.
+    ///
+    /// This is a wrapper around the ffi call `PyThreadState_Get`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use pyo3::{ffi, Python};
+    /// Python::with_gil(|py| {
+    ///     let thread_state = py.thread_state();
+    ///     // Release the GIL while waiting for events...
+    ///     let saved = unsafe { ffi::PyEval_SaveThread() };
+    ///     assert_eq!(saved, thread_state);
+    ///     // ...and re-acquire it before using Python again.
+    ///     unsafe { ffi::PyEval_RestoreThread(saved) };
+    /// });
+    /// ```
+    #[inline]
+    pub fn thread_state(self) -> *mut ffi::PyThreadState {
+        unsafe { ffi::PyThreadState_Get() }
+    }
+
     /// Retrieves a Python instance under the assumption that the GIL is already
     /// acquired at this point, and stays acquired for the lifetime `'p`.
     ///
@@ -678,6 +1350,274 @@ mod test {
     use super::*;
     use crate::types::{IntoPyDict, PyList};
 
+    #[test]
+    fn test_builtins() {
+        Python::with_gil(|py| {
+            let builtins = py.builtins();
+            assert!(builtins.get_item("print").is_some());
+            let len = py.import("builtins").unwrap().getattr("len").unwrap();
+            assert_eq!(builtins.get_item("len").unwrap(), len);
+        });
+    }
+
+    #[test]
+    fn test_eval_multi() {
+        Python::with_gil(|py| {
+            let globals = PyDict::new(py);
+            let stmts = ["x = 1", "def f(y):\n    return x + y", "f(2)"];
+            let result = py.eval_multi(&stmts, Some(globals), None).unwrap();
+            assert_eq!(result.extract::<i32>().unwrap(), 3);
+            assert!(globals.get_item("f").is_some());
+
+            assert!(py.eval_multi(&[], None, None).unwrap().is_none());
+            // The last entry must be an expression
+            assert!(py.eval_multi(&["x = 1"], Some(globals), None).is_err());
+        });
+    }
+
+    #[test]
+    fn test_eval_as() {
+        Python::with_gil(|py| {
+            assert_eq!(py.eval_as::<i32>("min(1, 2)").unwrap(), 1);
+            assert_eq!(py.eval_as::<&str>("'a' + 'b'").unwrap(), "ab");
+
+            // Errors from the expression are returned as they are
+            let err = py.eval_as::<i32>("1 / 0").unwrap_err();
+            assert!(err.is_instance::<crate::exceptions::PyZeroDivisionError>(py));
+
+            let err = py.eval_as::<i32>("[1, 2]").unwrap_err();
+            assert!(err.is_instance::<PyTypeError>(py));
+            assert!(err.to_string().contains("`[1, 2]` as `i32`"));
+            assert!(err.cause(py).unwrap().is_instance::<PyTypeError>(py));
+
+            // Other extraction errors keep their type
+            let err = py.eval_as::<u8>("300").unwrap_err();
+            assert!(err.is_instance::<crate::exceptions::PyOverflowError>(py));
+            assert!(err.cause(py).is_none());
+        });
+    }
+
+    #[test]
+    fn test_try_import() {
+        Python::with_gil(|py| {
+            let json = py.try_import("json").unwrap().unwrap();
+            assert_eq!(json.name().unwrap(), "json");
+
+            let missing = py.try_import("no_such_module").unwrap().unwrap_err();
+            assert_eq!(missing.name(), "no_such_module");
+            let missing = py.try_import("json.no_such_module").unwrap().unwrap_err();
+            assert_eq!(missing.name(), "json.no_such_module");
+            assert_eq!(
+                missing.suggestion(),
+                "try installing it with `pip install json`"
+            );
+            let missing = py
+                .try_import("no_such_package.module")
+                .unwrap()
+                .unwrap_err();
+            assert_eq!(missing.name(), "no_such_package");
+            assert_eq!(
+                missing.to_string(),
+                "No module named 'no_such_package'; try installing it with `pip install no_such_package`"
+            );
+            let err: PyErr = missing.into();
+            assert!(err.is_instance::<PyModuleNotFoundError>(py));
+
+            // A module which exists but is missing one of its dependencies is an error
+            let dir = std::env::temp_dir().join("pyo3_test_try_import");
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(
+                dir.join("pyo3_missing_dependency.py"),
+                "import no_such_dependency\n",
+            )
+            .unwrap();
+            let sys_path = py.import("sys").unwrap().getattr("path").unwrap();
+            sys_path
+                .call_method1("insert", (0, dir.to_str().unwrap()))
+                .unwrap();
+            let err = py.try_import("pyo3_missing_dependency").unwrap_err();
+            sys_path.call_method1("pop", (0,)).unwrap();
+            assert!(err.is_instance::<PyModuleNotFoundError>(py));
+
+            // Other import errors are not converted
+            let err = py.try_import("").unwrap_err();
+            assert!(err.is_instance::<crate::exceptions::PyValueError>(py));
+            assert!(!py.error_occurred());
+        });
+    }
+
+    #[test]
+    fn test_add_path_hook() {
+        Python::with_gil(|py| {
+            let finder: PyObject = py
+                .eval(
+                    r#"type("Finder", (), {
+                        "find_spec": lambda self, name, target=None: (
+                            __import__("importlib.util").util.spec_from_loader(
+                                name, __import__("importlib.machinery").machinery.SourceFileLoader(
+                                    name, "<virtual>"
+                                )
+                            ) if name == "pyo3_virtual_module" else None
+                        ),
+                        "invalidate_caches": lambda self: None,
+                    })()"#,
+                    None,
+                    None,
+                )
+                .unwrap()
+                .into();
+            py.add_path_hook(move |path| {
+                Python::with_gil(|py| {
+                    if path == "<pyo3-path-hook-test>" {
+                        Ok(finder.clone_ref(py))
+                    } else {
+                        Err(crate::exceptions::PyImportError::new_err(()))
+                    }
+                })
+            })
+            .unwrap();
+
+            let sys = py.import("sys").unwrap();
+            let hook = sys.getattr("path_hooks").unwrap().get_item(0).unwrap();
+            assert!(hook.call1(("<pyo3-path-hook-test>",)).is_ok());
+            assert!(hook
+                .call1(("elsewhere",))
+                .unwrap_err()
+                .is_instance::<crate::exceptions::PyImportError>(py));
+            assert!(hook
+                .call1((1,))
+                .unwrap_err()
+                .is_instance::<crate::exceptions::PyImportError>(py));
+
+            let locals = [("sys", sys)].into_py_dict(py);
+            py.run(
+                r#"
+sys.path.insert(0, "<pyo3-path-hook-test>")
+try:
+    import importlib.util
+    assert importlib.util.find_spec("pyo3_virtual_module") is not None
+finally:
+    sys.path.remove("<pyo3-path-hook-test>")
+    sys.path_importer_cache.pop("<pyo3-path-hook-test>", None)
+    sys.path_hooks.pop(0)
+"#,
+                None,
+                Some(locals),
+            )
+            .unwrap();
+        });
+    }
+
+    #[test]
+    fn test_add_at_exit() {
+        Python::with_gil(|py| {
+            // Running the handlers with `atexit._run_exitfuncs()` would also run and clear those
+            // registered by the rest of the process, so only check that one more is registered.
+            let atexit = py.import("atexit").unwrap();
+            let count = || -> usize {
+                atexit
+                    .call_method0("_ncallbacks")
+                    .unwrap()
+                    .extract()
+                    .unwrap()
+            };
+            let before = count();
+            py.add_at_exit(|_py| {}).unwrap();
+            assert_eq!(count(), before + 1);
+        });
+    }
+
+    #[test]
+    #[cfg(not(any(Py_LIMITED_API, PyPy)))]
+    fn test_exec_code() {
+        Python::with_gil(|py| {
+            let code: &PyCode = py
+                .eval("compile('y = x * 2', '<string>', 'exec')", None, None)
+                .unwrap()
+                .downcast()
+                .unwrap();
+            let globals = PyDict::new(py);
+            for x in 0..2 {
+                let locals = [("x", x)].into_py_dict(py);
+                py.exec_code(code, globals, locals).unwrap();
+                assert_eq!(
+                    locals.get_item("y").unwrap().extract::<i32>().unwrap(),
+                    x * 2
+                );
+            }
+            assert!(globals.get_item("y").is_none());
+
+            let raising: &PyCode = py
+                .eval("compile('1 / 0', '<string>', 'exec')", None, None)
+                .unwrap()
+                .downcast()
+                .unwrap();
+            assert!(py
+                .exec_code(raising, globals, globals)
+                .unwrap_err()
+                .is_instance::<crate::exceptions::PyZeroDivisionError>(py));
+        });
+    }
+
+    #[test]
+    fn test_redirect_stdout() {
+        use std::sync::{Arc, Mutex};
+
+        struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+        impl std::io::Write for SharedBuffer {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0.lock().unwrap().write(buf)
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Err(std::io::ErrorKind::BrokenPipe.into())
+            }
+        }
+
+        Python::with_gil(|py| {
+            let sys = py.import("sys").unwrap();
+            let stdout: PyObject = sys.getattr("stdout").unwrap().into();
+            let output = Arc::new(Mutex::new(Vec::new()));
+            {
+                let _redirect = py.redirect_stdout(SharedBuffer(output.clone())).unwrap();
+                py.run("print('hello', 'wörld')", None, None).unwrap();
+                let err = py
+                    .run("import sys; sys.stdout.flush()", None, None)
+                    .unwrap_err();
+                assert!(err.is_instance::<crate::exceptions::PyOSError>(py));
+            }
+            assert_eq!(
+                String::from_utf8(output.lock().unwrap().clone()).unwrap(),
+                "hello wörld\n"
+            );
+            assert_eq!(sys.getattr("stdout").unwrap().as_ptr(), stdout.as_ptr());
+        });
+    }
+
+    #[test]
+    fn test_clear_error() {
+        Python::with_gil(|py| {
+            assert!(!py.error_occurred());
+            crate::exceptions::PyValueError::new_err("error").restore(py);
+            assert!(py.error_occurred());
+            py.clear_error();
+            assert!(!py.error_occurred());
+            // Clearing without an error set is a no-op
+            py.clear_error();
+            assert!(!py.error_occurred());
+        });
+    }
+
+    #[test]
+    fn test_is_initialized() {
+        Python::with_gil(|_py| {
+            assert!(Python::is_initialized());
+            #[cfg(all(Py_3_8, not(any(PyPy, Py_LIMITED_API))))]
+            assert!(!Python::is_finalizing());
+        });
+    }
+
     #[test]
     fn test_eval() {
         let gil = Python::acquire_gil();
@@ -740,6 +1680,24 @@ mod test {
         assert_eq!(list.extract::<Vec<i32>>().unwrap(), vec![1, 2, 3, 4]);
     }
 
+    #[test]
+    fn test_thread_state() {
+        Python::with_gil(|py| {
+            let thread_state = py.thread_state();
+            assert!(!thread_state.is_null());
+            assert_eq!(py.thread_state(), thread_state);
+
+            // Another thread has its own state
+            let other = py.allow_threads(|| {
+                std::thread::spawn(|| Python::with_gil(|py| py.thread_state() as usize))
+                    .join()
+                    .unwrap()
+            });
+            assert_ne!(other, thread_state as usize);
+            assert_eq!(py.thread_state(), thread_state);
+        });
+    }
+
     #[test]
     fn test_python_version_info() {
         Python::with_gil(|py| {
@@ -763,6 +1721,19 @@ mod test {
         });
     }
 
+    #[test]
+    // Python 3.11 moved the standard frozen modules out of `PyImport_FrozenModules`
+    #[cfg(not(any(Py_LIMITED_API, PyPy, all(Py_3_11, any(Py_3_12, windows)))))]
+    fn test_frozen_modules() {
+        Python::with_gil(|py| {
+            let modules = py.frozen_modules();
+            let find = |name| modules.iter().find(|m| m.name == name).unwrap();
+            let importlib = find("_frozen_importlib");
+            assert!(!importlib.is_package);
+            assert!(find("__phello__").is_package);
+        });
+    }
+
     #[test]
     fn test_python_version_info_parse() {
         assert!(PythonVersionInfo::from_str("3.5.0a1") >= (3, 5, 0));
@@ -0,0 +1,119 @@
+// Copyright (c) 2017-present PyO3 Project and Contributors
+
+//! Helpers for testing `#[pyclass]` types from Rust.
+//!
+//! # Examples
+//!
+//! ```
+//! use pyo3::prelude::*;
+//! use pyo3::test_utils::{assert_python_eq, call_method, create_instance};
+//!
+//! #[pyclass]
+//! struct Counter {
+//!     count: u64,
+//! }
+//!
+//! #[pymethods]
+//! impl Counter {
+//!     #[new]
+//!     fn new(count: u64) -> Self {
+//!         Counter { count }
+//!     }
+//!
+//!     fn increment(&mut self, by: u64) -> u64 {
+//!         self.count += by;
+//!         self.count
+//!     }
+//! }
+//!
+//! Python::with_gil(|py| -> PyResult<()> {
+//!     let counter = create_instance::<Counter>(py, (1,))?;
+//!     let count = call_method(counter, "increment", (2,))?;
+//!     assert_python_eq(py, "1 + 2", count);
+//!     assert_eq!(counter.borrow().count, 3);
+//!     Ok(())
+//! })
+//! .unwrap();
+//! ```
+
+use crate::class::basic::CompareOp;
+use crate::type_object::PyTypeObject;
+use crate::types::PyTuple;
+use crate::{IntoPy, Py, PyAny, PyCell, PyClass, PyResult, PyTryFrom, Python};
+
+/// Creates an instance of `T` by calling its Python type with `args`.
+///
+/// Unlike [`PyCell::new`], this goes through the `#[new]` constructor of the class, as Python code
+/// instantiating the class would.
+pub fn create_instance<T>(py: Python, args: impl IntoPy<Py<PyTuple>>) -> PyResult<&PyCell<T>>
+where
+    T: PyClass,
+{
+    let instance = T::type_object(py).call1(args)?;
+    Ok(<PyCell<T> as PyTryFrom>::try_from(instance)?)
+}
+
+/// Calls the Python method `name` on `instance` with the positional arguments `args`.
+///
+/// This calls the method as Python code would, so argument conversions and error handling of the
+/// `#[pymethods]` wrapper are exercised.
+pub fn call_method<'p, T>(
+    instance: &'p PyCell<T>,
+    name: &str,
+    args: impl IntoPy<Py<PyTuple>>,
+) -> PyResult<&'p PyAny>
+where
+    T: PyClass,
+{
+    instance.call_method1(name, args)
+}
+
+/// Asserts that the Python expression `expr` evaluates to an object equal to `value`.
+///
+/// Equality is tested with Python's `==` operator. Panics with the `repr()` of both objects if they
+/// are not equal, or if evaluating or comparing them raises an exception.
+pub fn assert_python_eq(py: Python, expr: &str, value: &PyAny) {
+    let expected = py
+        .eval(expr, None, None)
+        .unwrap_or_else(|e| panic!("failed to evaluate `{}`: {}", expr, e));
+    let equal = expected
+        .rich_compare(value, CompareOp::Eq)
+        .and_then(PyAny::is_true)
+        .unwrap_or_else(|e| panic!("failed to compare `{}` with {}: {}", expr, repr(value), e));
+    assert!(
+        equal,
+        "assertion failed: `{}` == {}\n  expected: {}\n     value: {}",
+        expr,
+        repr(value),
+        repr(expected),
+        repr(value)
+    );
+}
+
+fn repr(obj: &PyAny) -> String {
+    obj.repr().map_or_else(
+        |_| "<unprintable object>".to_owned(),
+        |repr| repr.to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::assert_python_eq;
+    use crate::{Python, ToPyObject};
+
+    #[test]
+    fn test_assert_python_eq() {
+        Python::with_gil(|py| {
+            assert_python_eq(py, "[1, 2]", vec![1, 2].to_object(py).as_ref(py));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion failed: `1 + 1` == 3")]
+    fn test_assert_python_eq_fails() {
+        Python::with_gil(|py| {
+            assert_python_eq(py, "1 + 1", 3.to_object(py).as_ref(py));
+        });
+    }
+}
@@ -3,7 +3,8 @@ use crate::conversion::{
     AsPyPointer, FromPyObject, IntoPy, IntoPyPointer, PyTryFrom, ToBorrowedObject, ToPyObject,
 };
 use crate::err::{PyDowncastError, PyErr, PyResult};
-use crate::exceptions::PyTypeError;
+use crate::exceptions::{PyAttributeError, PyTypeError};
+use crate::once_cell::CachedMethod;
 use crate::type_object::PyTypeObject;
 use crate::types::{PyDict, PyIterator, PyList, PyString, PyTuple, PyType};
 use crate::{err, ffi, Py, PyNativeType, PyObject};
@@ -142,6 +143,53 @@ impl PyAny {
         })
     }
 
+    /// Returns the `__annotations__` dictionary of this object, or `None` if it has none.
+    ///
+    /// This is equivalent to the Python expression `getattr(self, "__annotations__", None)`, except
+    /// that `TypeError` is raised if the annotations are not a `dict`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pyo3::prelude::*;
+    ///
+    /// Python::with_gil(|py| -> PyResult<()> {
+    ///     let locals = pyo3::types::PyDict::new(py);
+    ///     py.run("def f(x: int) -> str: pass", None, Some(locals))?;
+    ///     let f = locals.get_item("f").unwrap();
+    ///     let annotations = f.annotations()?.unwrap();
+    ///     assert_eq!(annotations.len(), 2);
+    ///     assert!(py.eval("1", None, None)?.annotations()?.is_none());
+    ///     Ok(())
+    /// })
+    /// .unwrap();
+    /// ```
+    pub fn annotations(&self) -> PyResult<Option<&PyDict>> {
+        match self.getattr("__annotations__") {
+            Ok(annotations) => Ok(Some(annotations.downcast()?)),
+            Err(err) if err.is_instance::<PyAttributeError>(self.py()) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Sets the type hint for `name` in the `__annotations__` dictionary of this object, creating
+    /// the dictionary if it does not exist.
+    ///
+    /// This is equivalent to the Python statement `self.__annotations__[name] = type_hint`. Note
+    /// that as in Python, instances share the annotations of their class unless they have their
+    /// own.
+    pub fn set_annotation(&self, name: &str, type_hint: &PyAny) -> PyResult<()> {
+        let annotations = match self.annotations()? {
+            Some(annotations) => annotations,
+            None => {
+                let annotations = PyDict::new(self.py());
+                self.setattr("__annotations__", annotations)?;
+                annotations
+            }
+        };
+        annotations.set_item(name, type_hint)
+    }
+
     /// Returns an [`Ordering`] between `self` and `other`.
     ///
     /// This is equivalent to the following Python code:
@@ -383,6 +431,73 @@ impl PyAny {
         self.call(args, None)
     }
 
+    /// Calls the object with a single positional argument, without wrapping it in a tuple.
+    ///
+    /// This is equivalent to the Python expression `self(arg)`. Use
+    /// [`call_zero`](#method.call_zero) to call the object without any arguments.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pyo3::prelude::*;
+    ///
+    /// Python::with_gil(|py| -> PyResult<()> {
+    ///     let abs = py.eval("abs", None, None)?;
+    ///     assert_eq!(abs.call_one(-3)?.extract::<i32>()?, 3);
+    ///     Ok(())
+    /// })
+    /// .unwrap();
+    /// ```
+    pub fn call_one(&self, arg: impl ToPyObject) -> PyResult<&PyAny> {
+        let arg = arg.to_object(self.py());
+        unsafe {
+            self.py()
+                .from_owned_ptr_or_err(ffi::PyObject_CallFunctionObjArgs(
+                    self.as_ptr(),
+                    arg.as_ptr(),
+                    std::ptr::null_mut::<ffi::PyObject>(),
+                ))
+        }
+    }
+
+    /// Calls the object without arguments, without building an empty argument tuple.
+    ///
+    /// This is equivalent to the Python expression `self()`. Unlike [`call0`](#method.call0),
+    /// which builds an empty tuple before Python 3.9 and with the limited API, this uses
+    /// `PyObject_CallNoArgs` where it is available and passes no arguments otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pyo3::prelude::*;
+    ///
+    /// Python::with_gil(|py| -> PyResult<()> {
+    ///     let list = py.eval("list", None, None)?;
+    ///     assert!(list.call_zero()?.extract::<Vec<i32>>()?.is_empty());
+    ///     Ok(())
+    /// })
+    /// .unwrap();
+    /// ```
+    pub fn call_zero(&self) -> PyResult<&PyAny> {
+        cfg_if::cfg_if! {
+            // `PyObject_CallNoArgs` is missing from the limited API of Python 3.9 on Windows,
+            // see https://bugs.python.org/issue42415.
+            if #[cfg(all(Py_3_9, not(PyPy), any(not(Py_LIMITED_API), Py_3_10)))] {
+                unsafe {
+                    self.py().from_owned_ptr_or_err(ffi::PyObject_CallNoArgs(self.as_ptr()))
+                }
+            } else {
+                unsafe {
+                    self.py()
+                        .from_owned_ptr_or_err(ffi::PyObject_CallFunctionObjArgs(
+                            self.as_ptr(),
+                            std::ptr::null_mut::<ffi::PyObject>(),
+                        ))
+                }
+            }
+        }
+    }
+
     /// Calls a method on the object.
     ///
     /// This is equivalent to the Python expression `self.name(*args, **kwargs)`.
@@ -511,6 +626,64 @@ impl PyAny {
         self.call_method(name, args, None)
     }
 
+    /// Retrieves a method of the object without calling it.
+    ///
+    /// This is equivalent to the Python expression `self.name`, but raises `TypeError` if the
+    /// attribute is not callable. The returned bound method can be stored and called later,
+    /// possibly with different arguments each time.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pyo3::prelude::*;
+    /// use pyo3::types::PyList;
+    ///
+    /// Python::with_gil(|py| -> PyResult<()> {
+    ///     let list = PyList::empty(py);
+    ///     let append = list.get_method("append")?;
+    ///     append.call1((1,))?;
+    ///     append.call1((2,))?;
+    ///     assert_eq!(list.extract::<Vec<i32>>()?, [1, 2]);
+    ///     Ok(())
+    /// })
+    /// .unwrap();
+    /// ```
+    pub fn get_method(&self, name: &str) -> PyResult<&PyAny> {
+        let method = self.getattr(name)?;
+        if method.is_callable() {
+            Ok(method)
+        } else {
+            Err(PyTypeError::new_err(format!(
+                "'{}' object attribute '{}' is not callable",
+                self.get_type().name()?,
+                name
+            )))
+        }
+    }
+
+    /// Calls a method on the object, caching the method lookup in `method`.
+    ///
+    /// This is equivalent to the Python expression `self.name(*args)`, but reuses the bound
+    /// method from the previous call if it was made on the same object. See [`CachedMethod`]
+    /// for when the cache is invalidated.
+    ///
+    /// [`CachedMethod`]: crate::once_cell::CachedMethod
+    pub fn call_method_cached<'py>(
+        &'py self,
+        method: &CachedMethod,
+        args: &PyTuple,
+    ) -> PyResult<&'py PyAny> {
+        let py = self.py();
+        let bound = method.get(self)?;
+        unsafe {
+            py.from_owned_ptr_or_err(ffi::PyObject_Call(
+                bound.as_ptr(),
+                args.as_ptr(),
+                std::ptr::null_mut(),
+            ))
+        }
+    }
+
     /// Returns whether the object is considered to be true.
     ///
     /// This is equivalent to the Python expression `bool(self)`.
@@ -585,6 +758,24 @@ impl PyAny {
         PyIterator::from_object(self.py(), self)
     }
 
+    /// Like [`PyAny::iter`], but returns `None` instead of raising `TypeError` if this object
+    /// is not iterable.
+    ///
+    /// An object is considered iterable if it has an `__iter__` attribute or supports the
+    /// sequence protocol. Errors raised while creating the iterator are still returned as
+    /// `Some(Err(..))`.
+    pub fn try_iter(&self) -> Option<PyResult<&PyIterator>> {
+        let iterable = unsafe {
+            ffi::PyObject_HasAttrString(self.as_ptr(), "__iter__\0".as_ptr() as *const _) != 0
+                || ffi::PySequence_Check(self.as_ptr()) != 0
+        };
+        if iterable {
+            Some(self.iter())
+        } else {
+            None
+        }
+    }
+
     /// Returns the Python type object for this object's type.
     pub fn get_type(&self) -> &PyType {
         unsafe { PyType::from_type_ptr(self.py(), ffi::Py_TYPE(self.as_ptr())) }
@@ -684,8 +875,9 @@ impl PyAny {
 #[cfg(test)]
 mod test {
     use crate::{
-        types::{IntoPyDict, PyList, PyLong, PyModule},
-        Python, ToPyObject,
+        once_cell::CachedMethod,
+        types::{IntoPyDict, PyList, PyLong, PyModule, PyTuple},
+        PyAny, Python, ToPyObject,
     };
 
     macro_rules! test_module {
@@ -706,6 +898,62 @@ mod test {
         assert!(a.call_method1("nonexistent_method", (1,)).is_err());
     }
 
+    #[test]
+    fn test_call_one() {
+        Python::with_gil(|py| {
+            let list = py.eval("list", None, None).unwrap();
+            let tuple = (1, 2).to_object(py);
+            // The tuple is passed as the only argument rather than being unpacked
+            let result = list.call_one(&tuple).unwrap();
+            assert_eq!(result.extract::<Vec<i32>>().unwrap(), vec![1, 2]);
+
+            let int = py.eval("int", None, None).unwrap();
+            assert!(int
+                .call_one("abc")
+                .unwrap_err()
+                .is_instance::<crate::exceptions::PyValueError>(py));
+        });
+    }
+
+    #[test]
+    fn test_call_zero() {
+        Python::with_gil(|py| {
+            let tuple = py.eval("tuple", None, None).unwrap();
+            let result = tuple.call_zero().unwrap();
+            assert!(result.extract::<Vec<i32>>().unwrap().is_empty());
+
+            let abs = py.eval("abs", None, None).unwrap();
+            assert!(abs
+                .call_zero()
+                .unwrap_err()
+                .is_instance::<crate::exceptions::PyTypeError>(py));
+        });
+    }
+
+    #[test]
+    fn test_annotations() {
+        Python::with_gil(|py| {
+            let f = py.eval("lambda x: x", None, None).unwrap();
+            assert!(f.annotations().unwrap().unwrap().is_empty());
+            let int = py.get_type::<crate::types::PyLong>();
+            f.set_annotation("x", int).unwrap();
+            crate::py_run!(py, f, "assert f.__annotations__ == {'x': int}");
+
+            let obj = py
+                .eval("__import__('types').SimpleNamespace()", None, None)
+                .unwrap();
+            assert!(obj.annotations().unwrap().is_none());
+            obj.set_annotation("y", int).unwrap();
+            crate::py_run!(py, obj, "assert obj.__annotations__ == {'y': int}");
+
+            obj.setattr("__annotations__", 1).unwrap();
+            assert!(obj
+                .annotations()
+                .unwrap_err()
+                .is_instance::<crate::exceptions::PyTypeError>(py));
+        });
+    }
+
     #[test]
     fn test_call_with_kwargs() {
         let gil = Python::acquire_gil();
@@ -740,6 +988,114 @@ mod test {
         })
     }
 
+    #[test]
+    fn test_call_method_cached() {
+        Python::with_gil(|py| {
+            let module = test_module!(
+                py,
+                r#"
+                class A:
+                    def name(self, suffix):
+                        return "a" + suffix
+
+                class B:
+                    def name(self, suffix):
+                        return "b" + suffix
+            "#
+            );
+
+            let method = CachedMethod::new("name");
+            let args = PyTuple::new(py, vec!["!"]);
+            let call = |obj: &PyAny| -> String {
+                obj.call_method_cached(&method, args)
+                    .unwrap()
+                    .extract()
+                    .unwrap()
+            };
+
+            let a = module.getattr("A").unwrap().call0().unwrap();
+            assert_eq!(call(a), "a!");
+            assert_eq!(call(a), "a!");
+
+            // Changing the type of the object invalidates the cache
+            a.setattr("__class__", module.getattr("B").unwrap())
+                .unwrap();
+            assert_eq!(call(a), "b!");
+
+            let other = module.getattr("A").unwrap().call0().unwrap();
+            assert_eq!(call(other), "a!");
+
+            method.clear(py);
+            assert!(1
+                .to_object(py)
+                .as_ref(py)
+                .call_method_cached(&method, args)
+                .is_err());
+        })
+    }
+
+    #[test]
+    fn test_get_method() {
+        Python::with_gil(|py| {
+            let list = PyList::new(py, vec![3, 1, 2]);
+            let sort = list.get_method("sort").unwrap();
+            sort.call0().unwrap();
+            assert_eq!(list.extract::<Vec<i32>>().unwrap(), [1, 2, 3]);
+            sort.call((), Some(vec![("reverse", true)].into_py_dict(py)))
+                .unwrap();
+            assert_eq!(list.extract::<Vec<i32>>().unwrap(), [3, 2, 1]);
+
+            let complex = py.eval("1j", None, None).unwrap();
+            let err = complex.get_method("imag").unwrap_err();
+            assert!(err.is_instance::<crate::exceptions::PyTypeError>(py));
+            assert_eq!(
+                err.to_string(),
+                "TypeError: 'complex' object attribute 'imag' is not callable"
+            );
+            assert!(list
+                .get_method("missing")
+                .unwrap_err()
+                .is_instance::<crate::exceptions::PyAttributeError>(py));
+        })
+    }
+
+    #[test]
+    fn test_mapping_protocol() {
+        Python::with_gil(|py| {
+            let module = test_module!(
+                py,
+                r#"
+                class Storage:
+                    def __init__(self):
+                        self.data = {}
+                    def __getitem__(self, key):
+                        return self.data[key]
+                    def __setitem__(self, key, value):
+                        self.data[key] = value
+                    def __delitem__(self, key):
+                        del self.data[key]
+            "#
+            );
+
+            let storage = module.getattr("Storage").unwrap().call0().unwrap();
+            storage.set_item("a", 1).unwrap();
+            assert_eq!(storage.get_item("a").unwrap().extract::<i32>().unwrap(), 1);
+            storage.del_item("a").unwrap();
+            assert!(storage
+                .get_item("a")
+                .unwrap_err()
+                .is_instance::<crate::exceptions::PyKeyError>(py));
+            assert!(storage.del_item("a").is_err());
+
+            // objects without `__setitem__` raise TypeError
+            let x = py.eval("42", None, None).unwrap();
+            assert!(x
+                .set_item(0, 1)
+                .unwrap_err()
+                .is_instance::<crate::exceptions::PyTypeError>(py));
+        })
+    }
+
     #[test]
     fn test_type() {
         let gil = Python::acquire_gil();
@@ -766,6 +1122,24 @@ mod test {
         assert!(a.eq(b));
     }
 
+    #[test]
+    fn test_try_iter() {
+        Python::with_gil(|py| {
+            let list = vec![1, 2, 3].to_object(py).into_ref(py);
+            let items: Vec<i32> = list
+                .try_iter()
+                .unwrap()
+                .unwrap()
+                .map(|x| x.unwrap().extract().unwrap())
+                .collect();
+            assert_eq!(items, vec![1, 2, 3]);
+
+            let int = 5.to_object(py).into_ref(py);
+            assert!(int.try_iter().is_none());
+            assert!(int.iter().is_err());
+        });
+    }
+
     #[test]
     fn test_nan_eq() {
         let gil = Python::acquire_gil();
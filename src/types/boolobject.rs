@@ -12,6 +12,22 @@ pyobject_native_type!(PyBool, ffi::PyObject, ffi::PyBool_Type, #checkfunction=ff
 
 impl PyBool {
     /// Depending on `val`, returns `true` or `false`.
+    ///
+    /// The returned reference points to one of Python's `True` / `False` singletons.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pyo3::prelude::*;
+    /// use pyo3::types::PyBool;
+    ///
+    /// Python::with_gil(|py| {
+    ///     let t = PyBool::new(py, true);
+    ///     assert!(t.is_true());
+    ///     assert!(!PyBool::new(py, false).is_true());
+    ///     assert_eq!(t.to_string(), "True");
+    /// });
+    /// ```
     #[inline]
     pub fn new(py: Python, val: bool) -> &PyBool {
         unsafe { py.from_borrowed_ptr(if val { ffi::Py_True() } else { ffi::Py_False() }) }
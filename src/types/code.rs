@@ -0,0 +1,11 @@
+// Copyright (c) 2017-present PyO3 Project and Contributors
+use crate::{ffi, PyAny};
+
+/// Represents a Python code object.
+///
+/// Code objects hold the compiled bytecode of Python functions and modules, and can be obtained
+/// from a Python function using [`PyFunction::code`](crate::types::PyFunction::code).
+#[repr(transparent)]
+pub struct PyCode(PyAny);
+
+pyobject_native_type_core!(PyCode, ffi::PyCode_Type, #checkfunction=ffi::PyCode_Check);
@@ -0,0 +1,573 @@
+// Copyright (c) 2017-present PyO3 Project and Contributors
+
+//! Python coroutines driving Rust futures, used for `async fn` in `#[pymethods]`.
+
+use crate::callback::IntoPyCallbackOutput;
+use crate::closure::wrap_closure;
+use crate::exceptions::{PyRuntimeError, PyStopIteration, PyTypeError, PyValueError};
+use crate::helper_type::{get_or_create_helper_type, helper_contents, new_helper_object};
+use crate::once_cell::GILOnceCell;
+use crate::type_object::PyTypeObject;
+use crate::types::{PyTraceback, PyTuple, PyType};
+use crate::{
+    ffi, AsPyPointer, IntoPyPointer, Py, PyAny, PyErr, PyNativeType, PyObject, PyResult, Python,
+};
+use std::future::Future;
+use std::mem::ManuallyDrop;
+use std::os::raw::c_void;
+use std::ptr;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+type PollFn = Box<dyn FnMut(Python, &mut Context) -> Poll<PyResult<PyObject>> + Send>;
+
+enum CoroutineState {
+    Pending(PollFn, Arc<CoroutineWaker>),
+    Running,
+    Finished,
+}
+
+/// A Python coroutine which drives a Rust future.
+///
+/// `async fn` methods in `#[pymethods]` return a `PyCoroutine`, so they can be awaited from
+/// Python like any other coroutine, e.g. with `asyncio.run(obj.method())`.
+///
+/// The future is polled whenever the coroutine is resumed. While the future is pending inside a
+/// running asyncio event loop, the coroutine waits on an asyncio `Future`, which the future's
+/// waker resolves from any thread, so the future is only polled again once it has been woken.
+/// Without a running asyncio event loop, for example when the coroutine is resumed by hand, it
+/// yields `None` instead, leaving it to the caller to decide when to resume it.
+///
+/// The future is polled with the GIL held, on whichever thread resumes the coroutine.
+///
+/// # Examples
+///
+/// ```
+/// use pyo3::prelude::*;
+/// use pyo3::types::PyCoroutine;
+///
+/// Python::with_gil(|py| -> PyResult<()> {
+///     let coroutine = PyCoroutine::new(py, async { Ok::<_, PyErr>(42) })?;
+///     let asyncio = py.import("asyncio")?;
+///     let result: i32 = asyncio.call_method1("run", (coroutine,))?.extract()?;
+///     assert_eq!(result, 42);
+///     Ok(())
+/// })
+/// .unwrap();
+/// ```
+#[repr(transparent)]
+pub struct PyCoroutine(PyAny);
+
+pyobject_native_type_core!(
+    PyCoroutine,
+    *coroutine_type_object(),
+    #module=Some("pyo3_runtime")
+);
+
+impl PyCoroutine {
+    /// Wraps `future` in a Python coroutine.
+    ///
+    /// The future's output is converted to a Python object in the same way as the return values
+    /// of `#[pyfunction]`s, so it may be a `PyResult`.
+    pub fn new<F, T>(py: Python, future: F) -> PyResult<&PyCoroutine>
+    where
+        F: Future<Output = T> + Send + 'static,
+        T: IntoPyCallbackOutput<PyObject>,
+    {
+        let mut future = Box::pin(future);
+        let poll: PollFn = Box::new(move |py, cx| match future.as_mut().poll(cx) {
+            Poll::Ready(output) => Poll::Ready(output.convert(py)),
+            Poll::Pending => Poll::Pending,
+        });
+        unsafe {
+            let ty = PyCoroutine::type_object(py);
+            let waker = Arc::new(CoroutineWaker(Mutex::new(WakerState::Polling)));
+            let obj = new_helper_object(py, ty, CoroutineState::Pending(poll, waker))?;
+            py.from_owned_ptr_or_err(obj)
+        }
+    }
+
+    /// Polls the future once, raising `StopIteration` with its result when it completes.
+    fn resume(&self) -> PyResult<PyObject> {
+        let py = self.py();
+        let state = unsafe { helper_contents::<CoroutineState>(self.as_ptr()) };
+        let (mut poll, waker) = match std::mem::replace(state, CoroutineState::Running) {
+            CoroutineState::Pending(poll, waker) => (poll, waker),
+            CoroutineState::Running => {
+                *state = CoroutineState::Running;
+                return Err(PyValueError::new_err("coroutine already executing"));
+            }
+            CoroutineState::Finished => {
+                *state = CoroutineState::Finished;
+                return Err(PyRuntimeError::new_err(
+                    "cannot reuse already awaited coroutine",
+                ));
+            }
+        };
+
+        waker.set(WakerState::Polling);
+        let result = poll(py, &mut Context::from_waker(&waker.to_waker()));
+        let yielded = match result {
+            Poll::Pending => waker.wait(py),
+            Poll::Ready(_) => Ok(py.None()),
+        };
+        // Polling may have run Python code, but `state` is only touched while `Running`.
+        let state = unsafe { helper_contents::<CoroutineState>(self.as_ptr()) };
+        match result {
+            Poll::Pending => {
+                *state = CoroutineState::Pending(poll, waker);
+                yielded
+            }
+            Poll::Ready(result) => {
+                *state = CoroutineState::Finished;
+                Err(PyStopIteration::new_err((result?,)))
+            }
+        }
+    }
+
+    /// Drops the future, so that the coroutine finishes without completing it.
+    fn finish(&self) {
+        let state = unsafe { helper_contents::<CoroutineState>(self.as_ptr()) };
+        if let CoroutineState::Pending(..) = state {
+            *state = CoroutineState::Finished;
+        }
+    }
+}
+
+/// What waking a coroutine's future does, depending on what the coroutine is doing.
+enum WakerState {
+    /// The future is being polled, or the coroutine isn't run by an asyncio event loop.
+    Polling,
+    /// The future was woken while it was being polled, so it should be polled again right away.
+    Woken,
+    /// The coroutine is waiting for `future` to be resolved on `event_loop`.
+    Waiting {
+        event_loop: PyObject,
+        future: PyObject,
+    },
+}
+
+/// The waker of a coroutine's future, which is shared by all the wakers cloned from it.
+struct CoroutineWaker(Mutex<WakerState>);
+
+impl CoroutineWaker {
+    fn set(&self, state: WakerState) -> WakerState {
+        std::mem::replace(&mut *self.0.lock().unwrap(), state)
+    }
+
+    /// Returns the object for the coroutine to yield while its future is pending.
+    ///
+    /// The lock is never held while calling into Python, as a thread waking the future may hold
+    /// the GIL while it waits for the lock.
+    fn wait(&self, py: Python) -> PyResult<PyObject> {
+        let event_loop = match running_event_loop(py)? {
+            Some(event_loop) => event_loop,
+            None => return Ok(py.None()),
+        };
+        let future = event_loop.call_method0("create_future")?;
+        // Tells asyncio's `Task` to wait for `future` before resuming, as `Future.__await__` does.
+        future.setattr("_asyncio_future_blocking", true)?;
+
+        let waiting = WakerState::Waiting {
+            event_loop: event_loop.into(),
+            future: future.into(),
+        };
+        let mut state = self.0.lock().unwrap();
+        if let WakerState::Woken = *state {
+            *state = WakerState::Polling;
+            Ok(py.None())
+        } else {
+            *state = waiting;
+            Ok(future.into())
+        }
+    }
+
+    fn wake(&self) {
+        if let WakerState::Waiting { event_loop, future } = self.set(WakerState::Woken) {
+            Python::with_gil(|py| {
+                // If the event loop has been closed there is nothing left to resume.
+                let _ = release_waiter(py).and_then(|release| {
+                    event_loop.call_method1(py, "call_soon_threadsafe", (release, future))
+                });
+            })
+        }
+    }
+
+    fn to_waker(self: &Arc<Self>) -> Waker {
+        unsafe { Waker::from_raw(raw_waker(Arc::clone(self))) }
+    }
+}
+
+fn raw_waker(waker: Arc<CoroutineWaker>) -> RawWaker {
+    unsafe fn clone(data: *const ()) -> RawWaker {
+        let waker = ManuallyDrop::new(Arc::from_raw(data as *const CoroutineWaker));
+        raw_waker(Arc::clone(&waker))
+    }
+    unsafe fn wake(data: *const ()) {
+        Arc::from_raw(data as *const CoroutineWaker).wake();
+    }
+    unsafe fn wake_by_ref(data: *const ()) {
+        (*(data as *const CoroutineWaker)).wake();
+    }
+    unsafe fn drop(data: *const ()) {
+        std::mem::drop(Arc::from_raw(data as *const CoroutineWaker));
+    }
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, wake, wake_by_ref, drop);
+    RawWaker::new(Arc::into_raw(waker) as *const (), &VTABLE)
+}
+
+/// Returns the running asyncio event loop of this thread, if there is one.
+fn running_event_loop(py: Python) -> PyResult<Option<&PyAny>> {
+    match py.import("asyncio")?.call_method0("get_running_loop") {
+        Ok(event_loop) => Ok(Some(event_loop)),
+        Err(err) if err.is_instance::<PyRuntimeError>(py) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// Returns a callable which resolves the future a coroutine is waiting for, unless it has already
+/// been cancelled.
+fn release_waiter(py: Python) -> PyResult<&PyAny> {
+    static RELEASE_WAITER: GILOnceCell<PyObject> = GILOnceCell::new();
+
+    if RELEASE_WAITER.get(py).is_none() {
+        let release = wrap_closure(py, |py, args, _kwargs| {
+            let future = args.get_item(0);
+            if !future.call_method0("done")?.is_true()? {
+                future.call_method1("set_result", (py.None(),))?;
+            }
+            Ok(py.None())
+        })?;
+        let _ = RELEASE_WAITER.set(py, release);
+    }
+    Ok(RELEASE_WAITER.get(py).unwrap().as_ref(py))
+}
+
+fn coroutine_type_object() -> *mut ffi::PyTypeObject {
+    static TYPE_OBJECT: GILOnceCell<Py<PyType>> = GILOnceCell::new();
+
+    // Only called through `PyTypeInfo::type_object_raw` and `PyCoroutine::new`, which hold the GIL.
+    let py = unsafe { Python::assume_gil_acquired() };
+    if let Some(ty) = TYPE_OBJECT.get(py) {
+        return ty.as_ptr() as *mut ffi::PyTypeObject;
+    }
+
+    // The type object keeps pointers to the method definitions for as long as it exists.
+    let methods: &'static mut [ffi::PyMethodDef] = Box::leak(Box::new([
+        ffi::PyMethodDef {
+            ml_name: "send\0".as_ptr() as _,
+            ml_meth: Some(coroutine_send),
+            ml_flags: ffi::METH_O,
+            ml_doc: "send(arg) -> resume the coroutine; arg is ignored.\0".as_ptr() as _,
+        },
+        ffi::PyMethodDef {
+            ml_name: "throw\0".as_ptr() as _,
+            ml_meth: Some(coroutine_throw),
+            ml_flags: ffi::METH_VARARGS,
+            ml_doc: "throw(typ[,val[,tb]]) -> raise exception in coroutine.\0".as_ptr() as _,
+        },
+        ffi::PyMethodDef {
+            ml_name: "close\0".as_ptr() as _,
+            ml_meth: Some(coroutine_close),
+            ml_flags: ffi::METH_NOARGS,
+            ml_doc: "close() -> drop the Rust future.\0".as_ptr() as _,
+        },
+        ffi::PyMethodDef::default(),
+    ]));
+
+    get_or_create_helper_type::<CoroutineState>(
+        py,
+        &TYPE_OBJECT,
+        "pyo3_runtime.Coroutine\0",
+        &[
+            ffi::PyType_Slot {
+                slot: ffi::Py_tp_iter,
+                pfunc: coroutine_self as *mut c_void,
+            },
+            ffi::PyType_Slot {
+                slot: ffi::Py_tp_iternext,
+                pfunc: coroutine_next as *mut c_void,
+            },
+            ffi::PyType_Slot {
+                slot: ffi::Py_am_await,
+                pfunc: coroutine_self as *mut c_void,
+            },
+            ffi::PyType_Slot {
+                slot: ffi::Py_tp_methods,
+                pfunc: methods.as_mut_ptr() as *mut c_void,
+            },
+        ],
+    )
+    .expect("failed to create the PyO3 coroutine type")
+    .as_type_ptr()
+}
+
+unsafe extern "C" fn coroutine_self(slf: *mut ffi::PyObject) -> *mut ffi::PyObject {
+    ffi::Py_INCREF(slf);
+    slf
+}
+
+unsafe extern "C" fn coroutine_next(slf: *mut ffi::PyObject) -> *mut ffi::PyObject {
+    crate::callback::handle_panic(|py| {
+        let coroutine: &PyCoroutine = py.from_borrowed_ptr(slf);
+        coroutine.resume().map(IntoPyPointer::into_ptr)
+    })
+}
+
+unsafe extern "C" fn coroutine_send(
+    slf: *mut ffi::PyObject,
+    _value: *mut ffi::PyObject,
+) -> *mut ffi::PyObject {
+    // Rust futures can't receive values, so like `__next__` this ignores the value sent
+    coroutine_next(slf)
+}
+
+unsafe extern "C" fn coroutine_throw(
+    slf: *mut ffi::PyObject,
+    args: *mut ffi::PyObject,
+) -> *mut ffi::PyObject {
+    crate::callback::handle_panic(|py| {
+        let coroutine: &PyCoroutine = py.from_borrowed_ptr(slf);
+        let args: &PyTuple = py.from_borrowed_ptr(args);
+        match args.len() {
+            0 => return Err(PyTypeError::new_err("throw expected at least 1 argument")),
+            1..=3 => (),
+            n => {
+                return Err(PyTypeError::new_err(format!(
+                    "throw expected at most 3 arguments, got {}",
+                    n
+                )))
+            }
+        }
+        let exc = args.get_item(0);
+        let value = if args.len() > 1 {
+            args.get_item(1)
+        } else {
+            py.None().into_ref(py)
+        };
+        let traceback = if args.len() > 2 && !args.get_item(2).is_none() {
+            let traceback = args.get_item(2);
+            if traceback.downcast::<PyTraceback>().is_err() {
+                return Err(PyTypeError::new_err(
+                    "throw() third argument must be a traceback object",
+                ));
+            }
+            Some(traceback)
+        } else {
+            None
+        };
+        let (ptype, pvalue): (&PyAny, &PyAny) = match exc.downcast::<PyType>() {
+            Ok(ty) => (ty, value),
+            Err(_) if value.is_none() => (exc.get_type(), exc),
+            Err(_) => {
+                return Err(PyTypeError::new_err(
+                    "instance exception may not have a separate value",
+                ))
+            }
+        };
+
+        coroutine.finish();
+        // `PyErr_Restore` normalizes the exception lazily, like raising it with the traceback.
+        ffi::PyErr_Restore(
+            ptype.into_ptr(),
+            pvalue.into_ptr(),
+            traceback.map_or(ptr::null_mut(), IntoPyPointer::into_ptr),
+        );
+        Err(PyErr::fetch(py))
+    })
+}
+
+unsafe extern "C" fn coroutine_close(
+    slf: *mut ffi::PyObject,
+    _args: *mut ffi::PyObject,
+) -> *mut ffi::PyObject {
+    crate::callback::handle_panic(|py| {
+        let coroutine: &PyCoroutine = py.from_borrowed_ptr(slf);
+        coroutine.finish();
+        Ok(py.None().into_ptr())
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::types::PyCoroutine;
+    use crate::{py_run, PyResult, Python};
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::task::{Context, Poll};
+    use std::time::Duration;
+
+    /// A future which is pending for the given number of polls, waking itself each time.
+    struct Countdown(u32);
+
+    impl Future for Countdown {
+        type Output = PyResult<u32>;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+            if self.0 == 0 {
+                Poll::Ready(Ok(42))
+            } else {
+                self.0 -= 1;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+
+    /// A future which is ready once another thread wakes it after a delay, counting how often it's
+    /// polled.
+    struct Delayed {
+        polls: Arc<AtomicUsize>,
+        ready: Option<Arc<AtomicBool>>,
+    }
+
+    impl Future for Delayed {
+        type Output = PyResult<u32>;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+            self.polls.fetch_add(1, Ordering::SeqCst);
+            match &self.ready {
+                Some(ready) if ready.load(Ordering::SeqCst) => Poll::Ready(Ok(42)),
+                Some(_) => Poll::Pending,
+                None => {
+                    let ready = Arc::new(AtomicBool::new(false));
+                    self.ready = Some(ready.clone());
+                    let waker = cx.waker().clone();
+                    std::thread::spawn(move || {
+                        std::thread::sleep(Duration::from_millis(100));
+                        ready.store(true, Ordering::SeqCst);
+                        waker.wake();
+                    });
+                    Poll::Pending
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_coroutine() {
+        Python::with_gil(|py| {
+            let coroutine = PyCoroutine::new(py, Countdown(2)).unwrap();
+            py_run!(
+                py,
+                coroutine,
+                r#"
+                import asyncio
+                assert asyncio.iscoroutine(coroutine)
+                assert asyncio.run(coroutine) == 42
+                try:
+                    coroutine.send(None)
+                except RuntimeError:
+                    pass
+                else:
+                    assert False
+                "#
+            );
+
+            let coroutine = PyCoroutine::new(py, Countdown(1)).unwrap();
+            py_run!(
+                py,
+                coroutine,
+                r#"
+                assert coroutine.send(None) is None
+                try:
+                    next(coroutine)
+                except StopIteration as e:
+                    assert e.value == 42
+                else:
+                    assert False
+                "#
+            );
+        });
+    }
+
+    #[test]
+    fn test_coroutine_waits_for_waker() {
+        Python::with_gil(|py| {
+            let polls = Arc::new(AtomicUsize::new(0));
+            let delayed = Delayed {
+                polls: polls.clone(),
+                ready: None,
+            };
+            let coroutine = PyCoroutine::new(py, delayed).unwrap();
+            py_run!(
+                py,
+                coroutine,
+                r#"
+                import asyncio
+                assert asyncio.run(coroutine) == 42
+                "#
+            );
+            // Polled once to start the future and once more after it's woken, without spinning.
+            assert_eq!(polls.load(Ordering::SeqCst), 2);
+        });
+    }
+
+    #[test]
+    fn test_coroutine_errors() {
+        Python::with_gil(|py| {
+            let failing = PyCoroutine::new(py, async {
+                Err::<(), _>(crate::exceptions::PyValueError::new_err("failed"))
+            })
+            .unwrap();
+            py_run!(
+                py,
+                failing,
+                r#"
+                try:
+                    failing.send(None)
+                except ValueError as e:
+                    assert str(e) == "failed"
+                else:
+                    assert False
+                "#
+            );
+
+            let cancelled = PyCoroutine::new(py, Countdown(10)).unwrap();
+            py_run!(
+                py,
+                cancelled,
+                r#"
+                import asyncio
+                try:
+                    cancelled.throw(asyncio.CancelledError())
+                except asyncio.CancelledError:
+                    pass
+                else:
+                    assert False
+                cancelled.close()
+                "#
+            );
+
+            let traced = PyCoroutine::new(py, Countdown(10)).unwrap();
+            py_run!(
+                py,
+                traced,
+                r#"
+                try:
+                    raise KeyError("original")
+                except KeyError as e:
+                    tb = e.__traceback__
+                try:
+                    traced.throw(ValueError, ValueError("thrown"), tb)
+                except ValueError as e:
+                    assert str(e) == "thrown"
+                    frames = []
+                    t = e.__traceback__
+                    while t is not None:
+                        frames.append(t)
+                        t = t.tb_next
+                    assert tb in frames
+                else:
+                    assert False
+                "#
+            );
+            assert!(cancelled
+                .call_method1("send", (py.None(),))
+                .unwrap_err()
+                .is_instance::<crate::exceptions::PyRuntimeError>(py));
+        });
+    }
+}
@@ -1,6 +1,7 @@
 // Copyright (c) 2017-present PyO3 Project and Contributors
 
 use crate::err::{self, PyErr, PyResult};
+use crate::exceptions::PyTypeError;
 use crate::types::{PyAny, PyList};
 #[cfg(not(PyPy))]
 use crate::IntoPyPointer;
@@ -9,6 +10,7 @@ use crate::{
     ToBorrowedObject, ToPyObject,
 };
 use std::collections::{BTreeMap, HashMap};
+use std::marker::PhantomData;
 use std::ptr::NonNull;
 use std::{cmp, collections, hash};
 
@@ -111,6 +113,41 @@ impl PyDict {
         })
     }
 
+    /// Gets an item from the dictionary, propagating any error raised during the lookup.
+    ///
+    /// Returns `Ok(None)` if the item is not present. Unlike [`PyDict::get_item`], errors such as
+    /// an unhashable key or an exception raised by the key's `__eq__` are returned as `Err`
+    /// rather than being treated as a missing key.
+    pub fn get_item_with_error<K>(&self, key: K) -> PyResult<Option<&PyAny>>
+    where
+        K: ToBorrowedObject,
+    {
+        key.with_borrowed_ptr(self.py(), |key| unsafe {
+            let ptr = ffi::PyDict_GetItemWithError(self.as_ptr(), key);
+            match NonNull::new(ptr) {
+                Some(p) => {
+                    // Returns a borrowed ptr, must make it owned for safety (see #890).
+                    ffi::Py_INCREF(p.as_ptr());
+                    Ok(Some(self.py().from_owned_ptr(p.as_ptr())))
+                }
+                None if PyErr::occurred(self.py()) => Err(PyErr::fetch(self.py())),
+                None => Ok(None),
+            }
+        })
+    }
+
+    /// Gets an item from the dictionary, or `default` if the item is not present.
+    ///
+    /// This is equivalent to the Python expression `self.get(key, default)`. Errors raised during
+    /// the lookup are propagated as for [`PyDict::get_item_with_error`], which can be used
+    /// instead to get `None` for a missing key.
+    pub fn get_item_or<'a, K>(&'a self, key: K, default: &'a PyAny) -> PyResult<&'a PyAny>
+    where
+        K: ToBorrowedObject,
+    {
+        Ok(self.get_item_with_error(key)?.unwrap_or(default))
+    }
+
     /// Sets an item value.
     ///
     /// This is equivalent to the Python statement `self[key] = value`.
@@ -168,6 +205,53 @@ impl PyDict {
         }
     }
 
+    /// Extracts every key and value of this dictionary into a `HashMap`.
+    ///
+    /// This is equivalent to `dict.extract::<HashMap<K, V>>()`, but makes the conversion step
+    /// explicit. As `K` and `V` may borrow from the dictionary, types such as `&str` and `&PyAny`
+    /// can be extracted without copying.
+    pub fn to_hashmap_ref<'py, K, V>(&'py self) -> PyResult<HashMap<K, V>>
+    where
+        K: FromPyObject<'py> + cmp::Eq + hash::Hash,
+        V: FromPyObject<'py>,
+    {
+        FromPyObject::extract(self.as_ref())
+    }
+
+    /// Extracts this dictionary into a `HashMap` with string keys borrowed from the dictionary.
+    ///
+    /// Unlike extracting `HashMap<String, V>`, this does not copy the keys. Raises `TypeError` if
+    /// any key is not a `str`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pyo3::prelude::*;
+    /// use pyo3::types::IntoPyDict;
+    ///
+    /// Python::with_gil(|py| {
+    ///     let dict = [("a", 1), ("b", 2)].into_py_dict(py);
+    ///     let map = dict.to_borrowed_hashmap::<i32>().unwrap();
+    ///     assert_eq!(map["b"], 2);
+    /// });
+    /// ```
+    pub fn to_borrowed_hashmap<'py, V>(&'py self) -> PyResult<HashMap<&'py str, V>>
+    where
+        V: FromPyObject<'py>,
+    {
+        self.to_hashmap_ref()
+    }
+
+    /// Returns a view of this dictionary that converts keys to `K` and values to `V`.
+    ///
+    /// No conversion happens up front; see [`PyTypedDict`] for details.
+    pub fn typed<K, V>(&self) -> PyTypedDict<K, V> {
+        PyTypedDict {
+            dict: self,
+            _marker: PhantomData,
+        }
+    }
+
     /// Returns an iterator of `(key, value)` pairs in this dictionary.
     ///
     /// Note that it's unsafe to use when the dictionary might be changed by
@@ -215,6 +299,172 @@ impl<'a> std::iter::IntoIterator for &'a PyDict {
     }
 }
 
+/// A view of a [`PyDict`] whose keys and values are converted to `K` and `V`.
+///
+/// This is created by [`PyDict::typed`], or by extracting it from a Python object. The dictionary
+/// is not copied: keys and values are only converted as they are accessed. A key or value of the
+/// wrong type raises a `TypeError` naming the offending key, while other extraction errors, such
+/// as the `OverflowError` from a value too large for `V`, are raised unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use pyo3::prelude::*;
+/// use pyo3::types::PyTypedDict;
+///
+/// Python::with_gil(|py| -> PyResult<()> {
+///     let dict = py.eval("{'a': 1, 'b': 2}", None, None)?;
+///     let typed: PyTypedDict<String, i64> = dict.extract()?;
+///     typed.set_item("c".to_owned(), 3)?;
+///
+///     let mut total = 0;
+///     for item in typed.iter() {
+///         let (_key, value) = item?;
+///         total += value;
+///     }
+///     assert_eq!(total, 6);
+///     Ok(())
+/// })
+/// .unwrap();
+/// ```
+pub struct PyTypedDict<'py, K, V> {
+    dict: &'py PyDict,
+    _marker: PhantomData<fn() -> (K, V)>,
+}
+
+impl<'py, K, V> PyTypedDict<'py, K, V> {
+    /// Returns the underlying untyped dictionary.
+    pub fn as_dict(&self) -> &'py PyDict {
+        self.dict
+    }
+
+    /// Returns the number of items in the dictionary.
+    pub fn len(&self) -> usize {
+        self.dict.len()
+    }
+
+    /// Checks if the dictionary is empty.
+    pub fn is_empty(&self) -> bool {
+        self.dict.is_empty()
+    }
+
+    /// Gets the value for `key`, converted to `V`.
+    ///
+    /// Returns `Ok(None)` if the key is not present.
+    pub fn get_item(&self, key: K) -> PyResult<Option<V>>
+    where
+        K: ToPyObject,
+        V: FromPyObject<'py>,
+    {
+        let key = key.to_object(self.dict.py());
+        match self.dict.get_item_with_error(&key)? {
+            Some(value) => extract_typed_value(key.as_ref(self.dict.py()), value).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Sets an item value.
+    ///
+    /// This is equivalent to the Python expression `self[key] = value`.
+    pub fn set_item(&self, key: K, value: V) -> PyResult<()>
+    where
+        K: ToPyObject,
+        V: ToPyObject,
+    {
+        self.dict.set_item(key, value)
+    }
+
+    /// Returns an iterator of converted `(key, value)` pairs.
+    ///
+    /// Each item is converted when it is reached; the iterator yields an error for any key or
+    /// value of the wrong type. As with [`PyDict::iter`], the dictionary must not be changed
+    /// while it is being iterated.
+    pub fn iter(&self) -> PyTypedDictIterator<'py, K, V> {
+        PyTypedDictIterator {
+            inner: self.dict.iter(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Converts every item of the dictionary and collects them into a `HashMap`.
+    pub fn to_hashmap(&self) -> PyResult<HashMap<K, V>>
+    where
+        K: FromPyObject<'py> + cmp::Eq + hash::Hash,
+        V: FromPyObject<'py>,
+    {
+        self.iter().collect()
+    }
+}
+
+impl<'py, K, V> Clone for PyTypedDict<'py, K, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'py, K, V> Copy for PyTypedDict<'py, K, V> {}
+
+impl<'py, K, V> FromPyObject<'py> for PyTypedDict<'py, K, V> {
+    fn extract(ob: &'py PyAny) -> PyResult<Self> {
+        Ok(<PyDict as PyTryFrom>::try_from(ob)?.typed())
+    }
+}
+
+impl<'py, K, V> IntoIterator for PyTypedDict<'py, K, V>
+where
+    K: FromPyObject<'py>,
+    V: FromPyObject<'py>,
+{
+    type Item = PyResult<(K, V)>;
+    type IntoIter = PyTypedDictIterator<'py, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the converted `(key, value)` pairs of a [`PyTypedDict`].
+///
+/// This is created by [`PyTypedDict::iter`].
+pub struct PyTypedDictIterator<'py, K, V> {
+    inner: PyDictIterator<'py>,
+    _marker: PhantomData<fn() -> (K, V)>,
+}
+
+impl<'py, K, V> Iterator for PyTypedDictIterator<'py, K, V>
+where
+    K: FromPyObject<'py>,
+    V: FromPyObject<'py>,
+{
+    type Item = PyResult<(K, V)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(key, value)| {
+            let k = K::extract(key).map_err(|err| typed_dict_error(key, "key", err))?;
+            Ok((k, extract_typed_value(key, value)?))
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+fn extract_typed_value<'py, V: FromPyObject<'py>>(key: &PyAny, value: &'py PyAny) -> PyResult<V> {
+    V::extract(value).map_err(|err| typed_dict_error(key, "value for key", err))
+}
+
+fn typed_dict_error(key: &PyAny, what: &str, err: PyErr) -> PyErr {
+    if !err.is_instance::<PyTypeError>(key.py()) {
+        return err;
+    }
+    let key = match key.repr() {
+        Ok(repr) => repr.to_string_lossy().into_owned(),
+        Err(_) => "<unprintable>".to_owned(),
+    };
+    PyTypeError::new_err(format!("invalid dict {} {}: {}", what, key, err))
+}
+
 impl<K, V, H> ToPyObject for collections::HashMap<K, V, H>
 where
     K: hash::Hash + cmp::Eq + ToPyObject,
@@ -449,10 +699,12 @@ mod hashbrown_hashmap_conversion {
 #[cfg(test)]
 mod test {
     use crate::conversion::IntoPy;
+    use crate::exceptions::PyTypeError;
     use crate::types::dict::IntoPyDict;
     #[cfg(not(PyPy))]
     use crate::types::PyList;
-    use crate::types::{PyDict, PyTuple};
+    use crate::types::{PyAny, PyDict, PyTuple, PyTypedDict};
+    use crate::AsPyPointer;
     use crate::PyObject;
     use crate::Python;
     use crate::{PyTryFrom, ToPyObject};
@@ -544,6 +796,50 @@ mod test {
         assert_eq!(None, dict.get_item(8i32));
     }
 
+    #[test]
+    fn test_get_item_with_error() {
+        Python::with_gil(|py| {
+            let mut v = HashMap::new();
+            v.insert(7, 32);
+            let ob = v.to_object(py);
+            let dict = <PyDict as PyTryFrom>::try_from(ob.as_ref(py)).unwrap();
+            assert_eq!(
+                32,
+                dict.get_item_with_error(7i32)
+                    .unwrap()
+                    .unwrap()
+                    .extract::<i32>()
+                    .unwrap()
+            );
+            assert!(dict.get_item_with_error(8i32).unwrap().is_none());
+            let unhashable = py.eval("[]", None, None).unwrap();
+            let err = dict.get_item_with_error(unhashable).unwrap_err();
+            assert!(err.is_instance::<crate::exceptions::PyTypeError>(py));
+        });
+    }
+
+    #[test]
+    fn test_get_item_or() {
+        Python::with_gil(|py| {
+            let dict = vec![(7, 32)].into_py_dict(py);
+            let default = "default".to_object(py);
+            let default = default.as_ref(py);
+            assert_eq!(
+                dict.get_item_or(7, default)
+                    .unwrap()
+                    .extract::<i32>()
+                    .unwrap(),
+                32
+            );
+            assert_eq!(
+                dict.get_item_or(8, default).unwrap().as_ptr(),
+                default.as_ptr()
+            );
+            let unhashable = py.eval("[]", None, None).unwrap();
+            assert!(dict.get_item_or(unhashable, default).is_err());
+        });
+    }
+
     #[test]
     fn test_set_item() {
         let gil = Python::acquire_gil();
@@ -820,6 +1116,99 @@ mod test {
         assert_eq!(py_map.get_item("b").unwrap().extract::<i32>().unwrap(), 2);
     }
 
+    #[test]
+    fn test_to_hashmap_ref() {
+        Python::with_gil(|py| {
+            let dict = [("a", 1), ("b", 2)].into_py_dict(py);
+            let map: HashMap<String, i32> = dict.to_hashmap_ref().unwrap();
+            assert_eq!(map.len(), 2);
+            assert_eq!(map["a"], 1);
+
+            let map: HashMap<&str, &PyAny> = dict.to_hashmap_ref().unwrap();
+            assert_eq!(map["b"].extract::<i32>().unwrap(), 2);
+
+            assert!(dict.to_hashmap_ref::<String, String>().is_err());
+        });
+    }
+
+    #[test]
+    fn test_to_borrowed_hashmap() {
+        Python::with_gil(|py| {
+            let dict = [("a", 1), ("b", 2)].into_py_dict(py);
+            let map = dict.to_borrowed_hashmap::<i32>().unwrap();
+            assert_eq!(map.len(), 2);
+            assert_eq!(map["a"], 1);
+
+            let dict = [(1, 1)].into_py_dict(py);
+            assert!(dict.to_borrowed_hashmap::<i32>().is_err());
+        });
+    }
+
+    #[test]
+    fn test_typed_dict() {
+        Python::with_gil(|py| {
+            let dict = [("a", 1), ("b", 2)].into_py_dict(py);
+            let typed = dict.typed::<String, i64>();
+            typed.set_item("c".to_owned(), 3).unwrap();
+            assert_eq!(typed.len(), 3);
+            assert_eq!(typed.get_item("c".to_owned()).unwrap(), Some(3));
+            assert_eq!(typed.get_item("d".to_owned()).unwrap(), None);
+
+            let mut items: Vec<(String, i64)> = typed.iter().map(Result::unwrap).collect();
+            items.sort();
+            assert_eq!(
+                items,
+                vec![
+                    ("a".to_owned(), 1),
+                    ("b".to_owned(), 2),
+                    ("c".to_owned(), 3)
+                ]
+            );
+            assert_eq!(typed.to_hashmap().unwrap()["b"], 2);
+
+            let extracted: PyTypedDict<&str, i64> = dict.as_ref().extract().unwrap();
+            assert_eq!(extracted.as_dict().as_ptr(), dict.as_ptr());
+            let not_dict = py.eval("[]", None, None).unwrap();
+            assert!(not_dict.extract::<PyTypedDict<&str, i64>>().is_err());
+
+            let empty = PyDict::new(py).typed::<String, i64>();
+            assert!(empty.is_empty());
+            assert!(empty.iter().next().is_none());
+            assert!(empty.to_hashmap().unwrap().is_empty());
+        });
+    }
+
+    #[test]
+    fn test_typed_dict_type_mismatch() {
+        Python::with_gil(|py| {
+            let dict = [("a", 1.to_object(py)), ("b", "x".to_object(py))].into_py_dict(py);
+            let err = dict.typed::<String, i64>().to_hashmap().unwrap_err();
+            assert!(err.is_instance::<PyTypeError>(py));
+            assert!(err.to_string().contains("value for key 'b'"));
+
+            let err = dict
+                .typed::<String, i64>()
+                .get_item("b".to_owned())
+                .unwrap_err();
+            assert!(err.to_string().contains("value for key 'b'"));
+
+            let dict = [(1, 1)].into_py_dict(py);
+            let err = dict
+                .typed::<String, i64>()
+                .iter()
+                .next()
+                .unwrap()
+                .unwrap_err();
+            assert!(err.is_instance::<PyTypeError>(py));
+            assert!(err.to_string().contains("dict key 1"));
+
+            // Other extraction errors keep their type
+            let dict = [("a", 300)].into_py_dict(py);
+            let err = dict.typed::<String, u8>().to_hashmap().unwrap_err();
+            assert!(err.is_instance::<crate::exceptions::PyOverflowError>(py));
+        });
+    }
+
     #[test]
     fn test_slice_into_dict() {
         let gil = Python::acquire_gil();
@@ -0,0 +1,103 @@
+// Copyright (c) 2017-present PyO3 Project and Contributors
+use crate::{ffi, AsPyPointer, PyAny, PyErr, PyNativeType, PyResult, Python};
+
+/// Represents a Python `enumerate` object.
+///
+/// Iterating over a `&PyEnumerate` yields the index and the item as a Rust tuple.
+///
+/// # Examples
+///
+/// ```
+/// use pyo3::prelude::*;
+/// use pyo3::types::PyEnumerate;
+///
+/// Python::with_gil(|py| -> PyResult<()> {
+///     let letters = py.eval("['a', 'b']", None, None)?;
+///     for item in PyEnumerate::new(py, letters, 1)? {
+///         let (index, letter) = item?;
+///         println!("{}: {}", index, letter);
+///     }
+///     Ok(())
+/// })
+/// .unwrap();
+/// ```
+#[repr(transparent)]
+pub struct PyEnumerate(PyAny);
+
+pyobject_native_type_core!(PyEnumerate, ffi::PyEnum_Type);
+
+impl PyEnumerate {
+    /// Creates an `enumerate` object over `iterable`, counting from `start`.
+    ///
+    /// This is equivalent to Python's `enumerate(iterable, start)`.
+    pub fn new<'p>(py: Python<'p>, iterable: &PyAny, start: usize) -> PyResult<&'p PyEnumerate> {
+        let enumerate = py.get_type::<PyEnumerate>().call1((iterable, start))?;
+        Ok(enumerate.downcast()?)
+    }
+}
+
+impl<'p> Iterator for &'p PyEnumerate {
+    type Item = PyResult<(usize, &'p PyAny)>;
+
+    /// Retrieves the next index and item.
+    ///
+    /// Returns `None` when the underlying iterator is exhausted, and `Some(Err(..))` if it raises
+    /// an exception.
+    fn next(&mut self) -> Option<Self::Item> {
+        let py = self.py();
+        match unsafe { py.from_owned_ptr_or_opt::<PyAny>(ffi::PyIter_Next(self.as_ptr())) } {
+            Some(pair) => Some(pair.extract()),
+            None => {
+                if PyErr::occurred(py) {
+                    Some(Err(PyErr::fetch(py)))
+                } else {
+                    None
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::types::PyEnumerate;
+    use crate::{PyResult, PyTryFrom, Python, ToPyObject};
+
+    #[test]
+    fn test_enumerate() {
+        Python::with_gil(|py| {
+            let list = vec!["a", "b"].to_object(py);
+            let enumerate = PyEnumerate::new(py, list.as_ref(py), 5).unwrap();
+            let items: Vec<(usize, String)> = enumerate
+                .map(|item| {
+                    let (index, obj) = item?;
+                    Ok((index, obj.extract()?))
+                })
+                .collect::<PyResult<_>>()
+                .unwrap();
+            assert_eq!(items, vec![(5, "a".to_owned()), (6, "b".to_owned())]);
+
+            let from_python = py.eval("enumerate([])", None, None).unwrap();
+            assert!(<PyEnumerate as PyTryFrom>::try_from(from_python).is_ok());
+            assert!(<PyEnumerate as PyTryFrom>::try_from(list.as_ref(py)).is_err());
+        });
+    }
+
+    #[test]
+    fn test_enumerate_errors() {
+        Python::with_gil(|py| {
+            let not_iterable = 1.to_object(py);
+            assert!(PyEnumerate::new(py, not_iterable.as_ref(py), 0).is_err());
+
+            let failing = py.eval("(1 // x for x in [1, 0])", None, None).unwrap();
+            let mut enumerate = PyEnumerate::new(py, failing, 0).unwrap();
+            assert_eq!(enumerate.next().unwrap().unwrap().0, 0);
+            assert!(enumerate
+                .next()
+                .unwrap()
+                .unwrap_err()
+                .is_instance::<crate::exceptions::PyZeroDivisionError>(py));
+            assert!(enumerate.next().is_none());
+        });
+    }
+}
@@ -1,6 +1,10 @@
 use crate::derive_utils::PyFunctionArguments;
 use crate::exceptions::PyValueError;
 use crate::prelude::*;
+#[cfg(not(any(Py_LIMITED_API, PyPy)))]
+use crate::types::PyCode;
+#[cfg(not(Py_LIMITED_API))]
+use crate::types::{PyDict, PyString};
 use crate::{
     class::methods::{self, PyMethodDef},
     ffi, AsPyPointer,
@@ -74,3 +78,31 @@ pub struct PyFunction(PyAny);
 
 #[cfg(not(Py_LIMITED_API))]
 pyobject_native_type_core!(PyFunction, ffi::PyFunction_Type, #checkfunction=ffi::PyFunction_Check);
+
+#[cfg(not(Py_LIMITED_API))]
+impl PyFunction {
+    /// Returns the code object of the function (the `__code__` attribute).
+    #[cfg(not(PyPy))]
+    #[cfg_attr(docsrs, doc(cfg(not(PyPy))))]
+    pub fn code(&self) -> PyResult<&PyCode> {
+        Ok(self.getattr("__code__")?.downcast()?)
+    }
+
+    /// Returns the global namespace of the function (the `__globals__` attribute).
+    pub fn globals(&self) -> PyResult<&PyDict> {
+        Ok(self.getattr("__globals__")?.downcast()?)
+    }
+
+    /// Returns the name of the function (the `__name__` attribute).
+    pub fn name(&self) -> PyResult<&PyString> {
+        Ok(self.getattr("__name__")?.downcast()?)
+    }
+
+    /// Returns the qualified name of the function (the `__qualname__` attribute).
+    ///
+    /// Unlike the name, this includes the classes and functions the function is defined in, e.g.
+    /// `MyClass.method`.
+    pub fn qualname(&self) -> PyResult<&PyString> {
+        Ok(self.getattr("__qualname__")?.downcast()?)
+    }
+}
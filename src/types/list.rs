@@ -2,12 +2,15 @@
 //
 // based on Daniel Grunwald's https://github.com/dgrunwald/rust-cpython
 
+use crate::class::basic::CompareOp;
 use crate::err::{self, PyResult};
 use crate::ffi::{self, Py_ssize_t};
 use crate::{
     AsPyPointer, IntoPy, IntoPyPointer, PyAny, PyNativeType, PyObject, Python, ToBorrowedObject,
     ToPyObject,
 };
+use std::collections::{LinkedList, VecDeque};
+use std::ops::Index;
 
 /// Represents a Python `list`.
 #[repr(transparent)]
@@ -64,6 +67,24 @@ impl PyList {
         }
     }
 
+    /// Gets the item at the specified index, without checking that the index is in range.
+    ///
+    /// This uses the `PyList_GET_ITEM` macro, which is faster than [`PyList::get_item`] in tight
+    /// loops.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `index < self.len()`. This is only checked in debug builds.
+    #[cfg(not(any(Py_LIMITED_API, PyPy)))]
+    #[cfg_attr(docsrs, doc(cfg(not(any(Py_LIMITED_API, PyPy)))))]
+    pub unsafe fn get_item_unchecked(&self, index: usize) -> &PyAny {
+        debug_assert!(index < self.len());
+        let ptr = ffi::PyList_GET_ITEM(self.as_ptr(), index as Py_ssize_t);
+        // PyList_GET_ITEM return borrowed ptr; must make owned for safety (see #890).
+        ffi::Py_INCREF(ptr);
+        self.py().from_owned_ptr(ptr)
+    }
+
     /// Gets the item at the specified index.
     ///
     /// Panics if the index is out of range.
@@ -113,6 +134,118 @@ impl PyList {
         })
     }
 
+    /// Takes the slice `self[low:high]` and returns it as a new list.
+    ///
+    /// Indices are clamped to the bounds of the list as in Python, but negative indices are not
+    /// counted from the end.
+    pub fn get_slice(&self, low: isize, high: isize) -> PyResult<&PyList> {
+        unsafe {
+            self.py().from_owned_ptr_or_err(ffi::PyList_GetSlice(
+                self.as_ptr(),
+                low as Py_ssize_t,
+                high as Py_ssize_t,
+            ))
+        }
+    }
+
+    /// Replaces the slice `self[low:high]` with the contents of `items`.
+    ///
+    /// This is equivalent to the Python statement `self[low:high] = items`, so `items` may have a
+    /// different length to the slice it replaces.
+    pub fn set_slice(&self, low: isize, high: isize, items: &PyList) -> PyResult<()> {
+        unsafe {
+            err::error_on_minusone(
+                self.py(),
+                ffi::PyList_SetSlice(
+                    self.as_ptr(),
+                    low as Py_ssize_t,
+                    high as Py_ssize_t,
+                    items.as_ptr(),
+                ),
+            )
+        }
+    }
+
+    /// Copies the list's items into a `Vec`, leaving the list unchanged.
+    ///
+    /// Unlike collecting [`iter()`](#method.iter), the items are returned as owned `PyObject`s,
+    /// which do not depend on the lifetime of the GIL.
+    pub fn to_vec(&self) -> Vec<PyObject> {
+        let py = self.py();
+        (0..self.len())
+            .map(|index| unsafe {
+                PyObject::from_borrowed_ptr(
+                    py,
+                    ffi::PyList_GetItem(self.as_ptr(), index as Py_ssize_t),
+                )
+            })
+            .collect()
+    }
+
+    /// Removes all items from the list and returns them as a `Vec`.
+    ///
+    /// This is equivalent to calling [`to_vec()`](#method.to_vec) followed by the Python statement
+    /// `del self[:]`.
+    pub fn drain(&self) -> Vec<PyObject> {
+        let items = self.to_vec();
+        let result = unsafe {
+            ffi::PyList_SetSlice(
+                self.as_ptr(),
+                0,
+                items.len() as Py_ssize_t,
+                std::ptr::null_mut(),
+            )
+        };
+        // Removing items from a list cannot fail, and no destructors run because `items` still
+        // holds a reference to each of them.
+        debug_assert_eq!(result, 0);
+        items
+    }
+
+    /// Removes consecutive items for which `same` returns `true`, keeping the first item of each
+    /// run.
+    ///
+    /// `same` is called with each item and the item kept before it, in that order, and each
+    /// run of duplicates is removed with a single slice deletion. If `same` returns an error,
+    /// the runs found so far have been removed and the error is returned.
+    pub fn dedup_by<F>(&self, mut same: F) -> PyResult<()>
+    where
+        F: FnMut(&PyAny, &PyAny) -> PyResult<bool>,
+    {
+        let mut index = 1;
+        // `same` may mutate the list, so its length is checked on every iteration.
+        while index < self.len() {
+            let kept = self.get_item(index as isize - 1);
+            let mut end = index;
+            while end < self.len() && same(self.get_item(end as isize), kept)? {
+                end += 1;
+            }
+            if end > index {
+                unsafe {
+                    err::error_on_minusone(
+                        self.py(),
+                        ffi::PyList_SetSlice(
+                            self.as_ptr(),
+                            index as Py_ssize_t,
+                            end as Py_ssize_t,
+                            std::ptr::null_mut(),
+                        ),
+                    )?;
+                }
+            }
+            index += 1;
+        }
+        Ok(())
+    }
+
+    /// Removes consecutive items which compare equal using Python's `==` operator, keeping the
+    /// first item of each run.
+    ///
+    /// To remove every duplicate, sort the list first.
+    pub fn dedup(&self) -> PyResult<()> {
+        self.dedup_by(|item, kept| item.rich_compare(kept, CompareOp::Eq)?.is_true())
+    }
+
     /// Returns an iterator over this list's items.
     pub fn iter(&self) -> PyListIterator {
         PyListIterator {
@@ -132,6 +265,23 @@ impl PyList {
     }
 }
 
+/// Indexing a list with `list[i]` is equivalent to [`PyList::get_item`].
+///
+/// Panics if the index is out of range. To replace an item, use [`PyList::set_item`].
+impl Index<usize> for PyList {
+    type Output = PyAny;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        assert!(
+            index < self.len(),
+            "list index {} out of range for list of length {}",
+            index,
+            self.len()
+        );
+        self.get_item(index as isize)
+    }
+}
+
 /// Used by `PyList::iter()`.
 pub struct PyListIterator<'a> {
     list: &'a PyList,
@@ -192,14 +342,59 @@ where
     T: IntoPy<PyObject>,
 {
     fn into_py(self, py: Python) -> PyObject {
-        unsafe {
-            let ptr = ffi::PyList_New(self.len() as Py_ssize_t);
-            for (i, e) in self.into_iter().enumerate() {
-                let obj = e.into_py(py).into_ptr();
-                ffi::PyList_SetItem(ptr, i as Py_ssize_t, obj);
-            }
-            PyObject::from_owned_ptr(py, ptr)
+        list_into_py(py, self)
+    }
+}
+
+impl<T> ToPyObject for LinkedList<T>
+where
+    T: ToPyObject,
+{
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        PyList::new(py, self).into()
+    }
+}
+
+impl<T> IntoPy<PyObject> for LinkedList<T>
+where
+    T: IntoPy<PyObject>,
+{
+    fn into_py(self, py: Python) -> PyObject {
+        list_into_py(py, self)
+    }
+}
+
+impl<T> ToPyObject for VecDeque<T>
+where
+    T: ToPyObject,
+{
+    fn to_object(&self, py: Python<'_>) -> PyObject {
+        PyList::new(py, self).into()
+    }
+}
+
+impl<T> IntoPy<PyObject> for VecDeque<T>
+where
+    T: IntoPy<PyObject>,
+{
+    fn into_py(self, py: Python) -> PyObject {
+        list_into_py(py, self)
+    }
+}
+
+fn list_into_py<T, U>(py: Python, elements: impl IntoIterator<Item = T, IntoIter = U>) -> PyObject
+where
+    T: IntoPy<PyObject>,
+    U: ExactSizeIterator<Item = T>,
+{
+    let elements_iter = elements.into_iter();
+    unsafe {
+        let ptr = ffi::PyList_New(elements_iter.len() as Py_ssize_t);
+        for (i, e) in elements_iter.enumerate() {
+            let obj = e.into_py(py).into_ptr();
+            ffi::PyList_SetItem(ptr, i as Py_ssize_t, obj);
         }
+        PyObject::from_owned_ptr(py, ptr)
     }
 }
 
@@ -207,7 +402,8 @@ where
 mod test {
     use crate::types::PyList;
     use crate::Python;
-    use crate::{IntoPy, PyObject, PyTryFrom, ToPyObject};
+    use crate::{AsPyPointer, IntoPy, PyObject, PyTryFrom, ToPyObject};
+    use std::collections::{LinkedList, VecDeque};
 
     #[test]
     fn test_new() {
@@ -244,6 +440,34 @@ mod test {
         assert_eq!(7, list.get_item(3).extract::<i32>().unwrap());
     }
 
+    #[test]
+    #[cfg(not(any(Py_LIMITED_API, PyPy)))]
+    fn test_get_item_unchecked() {
+        Python::with_gil(|py| {
+            let list = PyList::new(py, vec![2, 3, 5, 7]);
+            let obj = unsafe { list.get_item_unchecked(3) };
+            assert_eq!(7, obj.extract::<i32>().unwrap());
+        });
+    }
+
+    #[test]
+    fn test_index() {
+        Python::with_gil(|py| {
+            let list = PyList::new(py, vec![2, 3, 5, 7]);
+            assert_eq!(2, list[0].extract::<i32>().unwrap());
+            assert_eq!(7, list[3].extract::<i32>().unwrap());
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "list index 4 out of range for list of length 4")]
+    fn test_index_out_of_range() {
+        Python::with_gil(|py| {
+            let list = PyList::new(py, vec![2, 3, 5, 7]);
+            let _ = &list[4];
+        });
+    }
+
     #[test]
     fn test_get_parked_item() {
         let gil = Python::acquire_gil();
@@ -422,6 +646,109 @@ mod test {
         assert_eq!(2, list.get_item(3).extract::<i32>().unwrap());
     }
 
+    #[test]
+    fn test_get_slice() {
+        Python::with_gil(|py| {
+            let list = PyList::new(py, vec![2, 3, 5, 7]);
+            let slice = list.get_slice(1, 3).unwrap();
+            assert_eq!(slice.extract::<Vec<i32>>().unwrap(), vec![3, 5]);
+            let slice = list.get_slice(2, 100).unwrap();
+            assert_eq!(slice.extract::<Vec<i32>>().unwrap(), vec![5, 7]);
+            assert!(list.get_slice(3, 1).unwrap().is_empty());
+            // The slice is a copy
+            slice.set_item(0, 11).unwrap();
+            assert_eq!(list.get_item(2).extract::<i32>().unwrap(), 5);
+        });
+    }
+
+    #[test]
+    fn test_set_slice() {
+        Python::with_gil(|py| {
+            let list = PyList::new(py, vec![2, 3, 5, 7]);
+            list.set_slice(1, 3, PyList::new(py, vec![11])).unwrap();
+            assert_eq!(list.extract::<Vec<i32>>().unwrap(), vec![2, 11, 7]);
+            list.set_slice(0, 0, PyList::new(py, vec![13, 17])).unwrap();
+            assert_eq!(list.extract::<Vec<i32>>().unwrap(), vec![13, 17, 2, 11, 7]);
+            list.set_slice(1, 4, PyList::empty(py)).unwrap();
+            assert_eq!(list.extract::<Vec<i32>>().unwrap(), vec![13, 7]);
+        });
+    }
+
+    #[test]
+    fn test_to_vec() {
+        Python::with_gil(|py| {
+            let list = PyList::new(py, vec![2, 3, 5]);
+            let items = list.to_vec();
+            assert_eq!(items.len(), 3);
+            assert_eq!(items[1].extract::<i32>(py).unwrap(), 3);
+            assert_eq!(list.len(), 3);
+            assert!(PyList::empty(py).to_vec().is_empty());
+        });
+    }
+
+    #[test]
+    fn test_dedup() {
+        Python::with_gil(|py| {
+            let list = PyList::new(py, vec![1, 1, 2, 3, 3, 3, 1, 2, 2]);
+            list.dedup().unwrap();
+            assert_eq!(list.extract::<Vec<i32>>().unwrap(), vec![1, 2, 3, 1, 2]);
+
+            // `==` is used, so equal values of different types are duplicates
+            let list = py.eval("[1, 1.0, True, 'a', 'a']", None, None).unwrap();
+            let list = <PyList as PyTryFrom>::try_from(list).unwrap();
+            list.dedup().unwrap();
+            assert_eq!(list.len(), 2);
+            assert_eq!(list.get_item(0).extract::<i32>().unwrap(), 1);
+
+            let empty = PyList::empty(py);
+            empty.dedup().unwrap();
+            assert!(empty.is_empty());
+        });
+    }
+
+    #[test]
+    fn test_dedup_by() {
+        Python::with_gil(|py| {
+            let list = PyList::new(
+                py,
+                vec!["apple", "avocado", "banana", "cherry", "cranberry"],
+            );
+            list.dedup_by(|item, kept| {
+                let item: &str = item.extract()?;
+                let kept: &str = kept.extract()?;
+                Ok(item[..1] == kept[..1])
+            })
+            .unwrap();
+            assert_eq!(
+                list.extract::<Vec<&str>>().unwrap(),
+                vec!["apple", "banana", "cherry"]
+            );
+
+            let list = PyList::new(py, vec![1, 1, 2]);
+            let err = list
+                .dedup_by(|item, _| Err(crate::exceptions::PyValueError::new_err(item.to_string())))
+                .unwrap_err();
+            assert!(err.is_instance::<crate::exceptions::PyValueError>(py));
+            assert_eq!(list.len(), 3);
+        });
+    }
+
+    #[test]
+    fn test_drain() {
+        Python::with_gil(|py| {
+            let obj = py.eval("object()", None, None).unwrap();
+            let cnt = obj.get_refcnt();
+            let list = PyList::new(py, vec![obj.to_object(py), 7.to_object(py)]);
+            let items = list.drain();
+            assert!(list.is_empty());
+            assert_eq!(items[0].as_ptr(), obj.as_ptr());
+            assert_eq!(items[1].extract::<i32>(py).unwrap(), 7);
+            assert_eq!(obj.get_refcnt(), cnt + 1);
+            drop(items);
+            assert_eq!(obj.get_refcnt(), cnt);
+        });
+    }
+
     #[test]
     fn test_array_into_py() {
         let gil = Python::acquire_gil();
@@ -431,4 +758,24 @@ mod test {
         assert_eq!(1, list.get_item(0).extract::<i32>().unwrap());
         assert_eq!(2, list.get_item(1).extract::<i32>().unwrap());
     }
+
+    #[test]
+    fn test_linked_list_and_vec_deque_to_list() {
+        Python::with_gil(|py| {
+            let linked_list: LinkedList<i32> = vec![1, 2, 3].into_iter().collect();
+            let vec_deque: VecDeque<i32> = vec![4, 5].into_iter().collect();
+
+            let list = linked_list.to_object(py);
+            let list = <PyList as PyTryFrom>::try_from(list.as_ref(py)).unwrap();
+            assert_eq!(list.extract::<Vec<i32>>().unwrap(), [1, 2, 3]);
+            let list: PyObject = linked_list.into_py(py);
+            assert_eq!(list.extract::<Vec<i32>>(py).unwrap(), [1, 2, 3]);
+
+            let list = vec_deque.to_object(py);
+            let list = <PyList as PyTryFrom>::try_from(list.as_ref(py)).unwrap();
+            assert_eq!(list.extract::<Vec<i32>>().unwrap(), [4, 5]);
+            let list: PyObject = vec_deque.into_py(py);
+            assert_eq!(list.extract::<Vec<i32>>(py).unwrap(), [4, 5]);
+        });
+    }
 }
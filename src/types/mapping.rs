@@ -0,0 +1,215 @@
+// Copyright (c) 2017-present PyO3 Project and Contributors
+
+use crate::err::{PyDowncastError, PyErr, PyResult};
+use crate::ffi;
+use crate::instance::PyNativeType;
+use crate::types::{PyAny, PySequence};
+use crate::{AsPyPointer, PyTryFrom, ToBorrowedObject};
+
+/// Represents a reference to a Python object supporting the mapping protocol.
+///
+/// # Examples
+///
+/// ```
+/// use pyo3::prelude::*;
+/// use pyo3::types::PyMapping;
+///
+/// Python::with_gil(|py| -> PyResult<()> {
+///     let counts: &PyMapping = py.eval("{'a': 1, 'b': 2}", None, None)?.downcast()?;
+///     counts.set_item("c", 3)?;
+///     assert_eq!(counts.len()?, 3);
+///     let keys: Vec<&str> = counts.keys()?.extract()?;
+///     assert_eq!(keys, ["a", "b", "c"]);
+///     Ok(())
+/// })
+/// .unwrap();
+/// ```
+#[repr(transparent)]
+pub struct PyMapping(PyAny);
+pyobject_native_type_named!(PyMapping);
+pyobject_native_type_extract!(PyMapping);
+
+impl PyMapping {
+    /// Returns the number of objects in the mapping.
+    ///
+    /// This is equivalent to the Python expression `len(self)`.
+    #[inline]
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> PyResult<usize> {
+        let v = unsafe { ffi::PyMapping_Size(self.as_ptr()) };
+        if v == -1 {
+            Err(PyErr::fetch(self.py()))
+        } else {
+            Ok(v as usize)
+        }
+    }
+
+    /// Returns whether the mapping is empty.
+    #[inline]
+    pub fn is_empty(&self) -> PyResult<bool> {
+        self.len().map(|l| l == 0)
+    }
+
+    /// Gets the item in self with key `key`.
+    ///
+    /// This is equivalent to the Python expression `self[key]`, and raises `KeyError` if the key
+    /// doesn't exist.
+    #[inline]
+    pub fn get_item<K>(&self, key: K) -> PyResult<&PyAny>
+    where
+        K: ToBorrowedObject,
+    {
+        PyAny::get_item(self, key)
+    }
+
+    /// Sets the item in self with key `key`.
+    ///
+    /// This is equivalent to the Python expression `self[key] = value`.
+    #[inline]
+    pub fn set_item<K, V>(&self, key: K, value: V) -> PyResult<()>
+    where
+        K: ToBorrowedObject,
+        V: ToBorrowedObject,
+    {
+        PyAny::set_item(self, key, value)
+    }
+
+    /// Deletes the item with key `key`.
+    ///
+    /// This is equivalent to the Python statement `del self[key]`.
+    #[inline]
+    pub fn del_item<K>(&self, key: K) -> PyResult<()>
+    where
+        K: ToBorrowedObject,
+    {
+        PyAny::del_item(self, key)
+    }
+
+    /// Returns a list containing all keys in the mapping.
+    ///
+    /// This is equivalent to the Python expression `list(self.keys())`.
+    #[inline]
+    pub fn keys(&self) -> PyResult<&PySequence> {
+        self.to_sequence(unsafe { ffi::PyMapping_Keys(self.as_ptr()) })
+    }
+
+    /// Returns a list containing all values in the mapping.
+    ///
+    /// This is equivalent to the Python expression `list(self.values())`.
+    #[inline]
+    pub fn values(&self) -> PyResult<&PySequence> {
+        self.to_sequence(unsafe { ffi::PyMapping_Values(self.as_ptr()) })
+    }
+
+    /// Returns a list of all `(key, value)` tuples in the mapping.
+    ///
+    /// This is equivalent to the Python expression `list(self.items())`.
+    #[inline]
+    pub fn items(&self) -> PyResult<&PySequence> {
+        self.to_sequence(unsafe { ffi::PyMapping_Items(self.as_ptr()) })
+    }
+
+    fn to_sequence(&self, ptr: *mut ffi::PyObject) -> PyResult<&PySequence> {
+        let obj: &PyAny = unsafe { self.py().from_owned_ptr_or_err(ptr)? };
+        // Before Python 3.7 these return the result of e.g. `self.keys()` for types other than
+        // `dict`, which may be a view rather than a list.
+        match <PySequence as PyTryFrom>::try_from(obj) {
+            Ok(seq) => Ok(seq),
+            Err(_) => unsafe {
+                self.py()
+                    .from_owned_ptr_or_err(ffi::PySequence_List(obj.as_ptr()))
+            },
+        }
+    }
+}
+
+impl<'v> PyTryFrom<'v> for PyMapping {
+    fn try_from<V: Into<&'v PyAny>>(value: V) -> Result<&'v PyMapping, PyDowncastError<'v>> {
+        let value = value.into();
+        unsafe {
+            if ffi::PyMapping_Check(value.as_ptr()) != 0 {
+                Ok(<PyMapping as PyTryFrom>::try_from_unchecked(value))
+            } else {
+                Err(PyDowncastError::new(value, "Mapping"))
+            }
+        }
+    }
+
+    fn try_from_exact<V: Into<&'v PyAny>>(value: V) -> Result<&'v PyMapping, PyDowncastError<'v>> {
+        <PyMapping as PyTryFrom>::try_from(value)
+    }
+
+    #[inline]
+    unsafe fn try_from_unchecked<V: Into<&'v PyAny>>(value: V) -> &'v PyMapping {
+        let ptr = value.into() as *const _ as *const PyMapping;
+        &*ptr
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::exceptions::PyKeyError;
+    use crate::types::PyMapping;
+    use crate::{PyTryFrom, Python, ToPyObject};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_mapping() {
+        Python::with_gil(|py| {
+            let mut v = HashMap::new();
+            v.insert(7, 32);
+            let obj = v.to_object(py);
+            let mapping = <PyMapping as PyTryFrom>::try_from(obj.as_ref(py)).unwrap();
+            assert_eq!(mapping.len().unwrap(), 1);
+            assert!(!mapping.is_empty().unwrap());
+            assert_eq!(mapping.get_item(7).unwrap().extract::<i32>().unwrap(), 32);
+            assert!(mapping
+                .get_item(8)
+                .unwrap_err()
+                .is_instance::<PyKeyError>(py));
+
+            mapping.set_item(8, 42).unwrap();
+            assert_eq!(mapping.len().unwrap(), 2);
+            mapping.del_item(7).unwrap();
+            assert!(mapping.del_item(7).is_err());
+            assert_eq!(mapping.keys().unwrap().extract::<Vec<i32>>().unwrap(), [8]);
+            assert_eq!(
+                mapping.values().unwrap().extract::<Vec<i32>>().unwrap(),
+                [42]
+            );
+            assert_eq!(
+                mapping
+                    .items()
+                    .unwrap()
+                    .extract::<Vec<(i32, i32)>>()
+                    .unwrap(),
+                [(8, 42)]
+            );
+
+            let not_mapping = 5.to_object(py);
+            assert!(<PyMapping as PyTryFrom>::try_from(not_mapping.as_ref(py)).is_err());
+        });
+    }
+
+    #[test]
+    fn test_mapping_from_python_class() {
+        Python::with_gil(|py| {
+            let obj = py
+                .eval("__import__('collections').UserDict(a=1)", None, None)
+                .unwrap();
+            let mapping: &PyMapping = obj.downcast().unwrap();
+            assert_eq!(
+                mapping.keys().unwrap().extract::<Vec<&str>>().unwrap(),
+                ["a"]
+            );
+            assert_eq!(
+                mapping
+                    .items()
+                    .unwrap()
+                    .extract::<Vec<(&str, i32)>>()
+                    .unwrap(),
+                [("a", 1)]
+            );
+        });
+    }
+}
@@ -0,0 +1,177 @@
+// Copyright (c) 2017-present PyO3 Project and Contributors
+use crate::types::{PyBytes, PyTuple};
+use crate::{ffi, AsPyPointer, PyAny, PyNativeType, PyResult, Python};
+use std::os::raw::c_int;
+use std::slice;
+
+/// Represents a Python `memoryview` object.
+///
+/// A `memoryview` gives access to the memory of an object supporting the buffer protocol, such
+/// as `bytes`, `bytearray` or a numpy array, without copying it. Slicing a `memoryview` creates
+/// a new view of the same memory.
+///
+/// # Examples
+///
+/// ```
+/// use pyo3::prelude::*;
+/// use pyo3::types::{PyByteArray, PyMemoryView};
+///
+/// Python::with_gil(|py| -> PyResult<()> {
+///     let data = PyByteArray::new(py, &[1, 2, 3, 4, 5, 6]);
+///     let view = PyMemoryView::from_buffer(py, data)?.cast("B", Some(&[2, 3]))?;
+///     assert_eq!(view.shape(), [2, 3]);
+///     assert_eq!(view.strides(), [3, 1]);
+///     assert_eq!(view.tobytes()?.as_bytes(), [1, 2, 3, 4, 5, 6]);
+///     Ok(())
+/// })
+/// .unwrap();
+/// ```
+#[repr(transparent)]
+pub struct PyMemoryView(PyAny);
+
+pyobject_native_type_core!(PyMemoryView, ffi::PyMemoryView_Type, #checkfunction=ffi::PyMemoryView_Check);
+
+impl PyMemoryView {
+    /// Creates a `memoryview` of an object supporting the buffer protocol.
+    ///
+    /// This is equivalent to Python's `memoryview(obj)`, and raises `TypeError` if `obj` doesn't
+    /// support the buffer protocol.
+    pub fn from_buffer<'p>(py: Python<'p>, obj: &PyAny) -> PyResult<&'p PyMemoryView> {
+        unsafe { py.from_owned_ptr_or_err(ffi::PyMemoryView_FromObject(obj.as_ptr())) }
+    }
+
+    /// Casts the view to a new item format and shape, without copying the underlying memory.
+    ///
+    /// This is equivalent to Python's `self.cast(format, shape)`, or to `self.cast(format)` if
+    /// `shape` is `None`, which casts the view to a single dimension. An empty `shape` casts it to
+    /// zero dimensions, i.e. a single item. The view must be C-contiguous.
+    pub fn cast(&self, format: &str, shape: Option<&[usize]>) -> PyResult<&PyMemoryView> {
+        let py = self.py();
+        let cast = match shape {
+            Some(shape) => self.call_method1("cast", (format, PyTuple::new(py, shape)))?,
+            None => self.call_method1("cast", (format,))?,
+        };
+        Ok(cast.downcast()?)
+    }
+
+    /// Copies the memory of the view into a new `bytes` object.
+    ///
+    /// This is equivalent to Python's `self.tobytes()`, and raises `ValueError` if the view has
+    /// been released.
+    pub fn tobytes(&self) -> PyResult<&PyBytes> {
+        Ok(self.call_method0("tobytes")?.downcast()?)
+    }
+
+    /// Returns the number of items in each dimension of the view.
+    ///
+    /// This is empty for a zero-dimensional view, which holds a single item.
+    pub fn shape(&self) -> &[usize] {
+        let view = self.buffer();
+        unsafe { dimensions(view.shape as *const usize, view.ndim) }
+    }
+
+    /// Returns the number of bytes to skip to get to the next item in each dimension of the view.
+    ///
+    /// This is empty for a zero-dimensional view, which holds a single item.
+    pub fn strides(&self) -> &[isize] {
+        let view = self.buffer();
+        unsafe { dimensions(view.strides as *const isize, view.ndim) }
+    }
+
+    /// Returns the size in bytes of a single item of the view.
+    pub fn itemsize(&self) -> usize {
+        self.buffer().itemsize as usize
+    }
+
+    /// Returns whether the memory of the view is contiguous, in either C or Fortran order.
+    pub fn is_contiguous(&self) -> bool {
+        unsafe { ffi::PyBuffer_IsContiguous(self.buffer(), b'A' as _) != 0 }
+    }
+
+    fn buffer(&self) -> &ffi::Py_buffer {
+        // The shape and strides of a memoryview are stored in the object itself, so they
+        // remain valid for the lifetime of `self` even if the view is released.
+        unsafe { &*ffi::PyMemoryView_GET_BUFFER(self.as_ptr()) }
+    }
+}
+
+/// Returns a per-dimension array of a buffer, which CPython leaves null for zero dimensions.
+unsafe fn dimensions<'a, T>(ptr: *const T, ndim: c_int) -> &'a [T] {
+    if ndim == 0 || ptr.is_null() {
+        &[]
+    } else {
+        slice::from_raw_parts(ptr, ndim as usize)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::types::{PyByteArray, PyBytes, PyMemoryView};
+    use crate::{PyTryFrom, Python, ToPyObject};
+
+    #[test]
+    fn test_memoryview() {
+        Python::with_gil(|py| {
+            let bytes = PyBytes::new(py, b"abcdef");
+            let view = PyMemoryView::from_buffer(py, bytes).unwrap();
+            assert_eq!(view.shape(), [6]);
+            assert_eq!(view.strides(), [1]);
+            assert_eq!(view.itemsize(), 1);
+            assert!(view.is_contiguous());
+            assert_eq!(view.tobytes().unwrap().as_bytes(), b"abcdef");
+
+            let every_other = view.get_item(py.eval("slice(None, None, 2)", None, None).unwrap());
+            let every_other: &PyMemoryView = every_other.unwrap().extract().unwrap();
+            assert_eq!(every_other.shape(), [3]);
+            assert_eq!(every_other.strides(), [2]);
+            assert!(!every_other.is_contiguous());
+            assert_eq!(every_other.tobytes().unwrap().as_bytes(), b"ace");
+
+            let not_buffer = 1.to_object(py);
+            assert!(PyMemoryView::from_buffer(py, not_buffer.as_ref(py)).is_err());
+            assert!(<PyMemoryView as PyTryFrom>::try_from(bytes.as_ref()).is_err());
+        });
+    }
+
+    #[test]
+    fn test_memoryview_cast() {
+        Python::with_gil(|py| {
+            let data = PyByteArray::new(py, &[1, 0, 2, 0, 3, 0, 4, 0]);
+            let view = PyMemoryView::from_buffer(py, data).unwrap();
+            let matrix = view.cast("H", Some(&[2, 2])).unwrap();
+            assert_eq!(matrix.shape(), [2, 2]);
+            assert_eq!(matrix.strides(), [4, 2]);
+            assert_eq!(matrix.itemsize(), 2);
+
+            // The view shares memory with the bytearray
+            unsafe { data.as_bytes_mut()[2] = 5 };
+            let flat = matrix.cast("B", None).unwrap();
+            assert_eq!(flat.shape(), [8]);
+            assert_eq!(flat.tobytes().unwrap().as_bytes(), [1, 0, 5, 0, 3, 0, 4, 0]);
+
+            let odd = PyByteArray::new(py, &[1, 2, 3]);
+            let odd = PyMemoryView::from_buffer(py, odd).unwrap();
+            assert!(odd.cast("H", None).is_err());
+        });
+    }
+
+    #[test]
+    fn test_memoryview_zero_dimensional() {
+        Python::with_gil(|py| {
+            let bytes = PyBytes::new(py, b"abcd");
+            let view = PyMemoryView::from_buffer(py, bytes).unwrap();
+            let scalar = view.cast("i", Some(&[])).unwrap();
+            assert!(scalar.shape().is_empty());
+            assert!(scalar.strides().is_empty());
+            assert_eq!(scalar.itemsize(), 4);
+            assert_eq!(scalar.tobytes().unwrap().as_bytes(), b"abcd");
+
+            let ctypes_int = py
+                .eval("__import__('ctypes').c_int(1)", None, None)
+                .unwrap();
+            let view = PyMemoryView::from_buffer(py, ctypes_int).unwrap();
+            assert!(view.shape().is_empty());
+            assert!(view.strides().is_empty());
+        });
+    }
+}
@@ -6,26 +6,42 @@ pub use self::any::PyAny;
 pub use self::boolobject::PyBool;
 pub use self::bytearray::PyByteArray;
 pub use self::bytes::PyBytes;
+#[cfg(not(any(Py_LIMITED_API, PyPy)))]
+pub use self::code::PyCode;
 pub use self::complex::PyComplex;
+pub use self::coroutine::PyCoroutine;
 #[cfg(not(Py_LIMITED_API))]
 #[cfg_attr(docsrs, doc(cfg(not(Py_LIMITED_API))))]
 pub use self::datetime::{
     PyDate, PyDateAccess, PyDateTime, PyDelta, PyDeltaAccess, PyTime, PyTimeAccess, PyTzInfo,
 };
-pub use self::dict::{IntoPyDict, PyDict};
+pub use self::dict::{IntoPyDict, PyDict, PyTypedDict, PyTypedDictIterator};
+#[cfg(not(PyPy))]
+pub use self::enumerate::PyEnumerate;
 pub use self::floatob::PyFloat;
 pub use self::function::{PyCFunction, PyFunction};
 pub use self::iterator::PyIterator;
 pub use self::list::PyList;
+pub use self::mapping::PyMapping;
+#[cfg(not(any(Py_LIMITED_API, PyPy)))]
+pub use self::memoryview::PyMemoryView;
 pub use self::module::PyModule;
-pub use self::num::PyLong;
+pub use self::namedtuple::PyNamedTuple;
+pub use self::none::PyNone;
 pub use self::num::PyLong as PyInt;
+pub use self::num::{ByteOrder, PyLong};
+pub use self::range::PyRange;
 pub use self::sequence::PySequence;
 pub use self::set::{PyFrozenSet, PySet};
 pub use self::slice::{PySlice, PySliceIndices};
 pub use self::string::{PyString, PyString as PyUnicode};
+pub use self::traceback::PyTraceback;
 pub use self::tuple::PyTuple;
 pub use self::typeobject::PyType;
+#[cfg(not(PyPy))]
+pub use self::weakref::PyWeakRef;
+#[cfg(not(PyPy))]
+pub use self::zip::PyZip;
 
 // Implementations core to all native types
 #[macro_export]
@@ -213,20 +229,41 @@ mod any;
 mod boolobject;
 mod bytearray;
 mod bytes;
+#[cfg(not(any(Py_LIMITED_API, PyPy)))]
+#[cfg_attr(docsrs, doc(cfg(not(any(Py_LIMITED_API, PyPy)))))]
+mod code;
 mod complex;
+mod coroutine;
 #[cfg(not(Py_LIMITED_API))]
 #[cfg_attr(docsrs, doc(cfg(not(Py_LIMITED_API))))]
 mod datetime;
 mod dict;
+#[cfg(not(PyPy))]
+#[cfg_attr(docsrs, doc(cfg(not(PyPy))))]
+mod enumerate;
 mod floatob;
 mod function;
 mod iterator;
 mod list;
+mod mapping;
+#[cfg(not(any(Py_LIMITED_API, PyPy)))]
+#[cfg_attr(docsrs, doc(cfg(not(any(Py_LIMITED_API, PyPy)))))]
+mod memoryview;
 mod module;
+mod namedtuple;
+mod none;
 mod num;
+mod range;
 mod sequence;
 mod set;
 mod slice;
 mod string;
+mod traceback;
 mod tuple;
 mod typeobject;
+#[cfg(not(PyPy))]
+#[cfg_attr(docsrs, doc(cfg(not(PyPy))))]
+mod weakref;
+#[cfg(not(PyPy))]
+#[cfg_attr(docsrs, doc(cfg(not(PyPy))))]
+mod zip;
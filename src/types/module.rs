@@ -13,6 +13,8 @@ use crate::types::{PyAny, PyDict, PyList};
 use crate::types::{PyCFunction, PyTuple};
 use crate::{AsPyPointer, IntoPy, Py, PyObject, Python};
 use std::ffi::{CStr, CString};
+#[cfg(not(all(windows, PyPy)))]
+use std::path::Path;
 use std::str;
 
 /// Represents a Python [`module`][1] object.
@@ -193,8 +195,39 @@ impl PyModule {
         }
     }
 
+    /// Returns the directory containing the module's file, as given by the `__file__` attribute.
+    ///
+    /// Returns `None` if the module has no `__file__` attribute, as is the case for built-in
+    /// modules. This is useful for extension modules which need to locate data files installed
+    /// alongside them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pyo3::prelude::*;
+    ///
+    /// #[pymodule]
+    /// fn my_module(_py: Python, module: &PyModule) -> PyResult<()> {
+    ///     if let Some(dir) = module.package_dir() {
+    ///         let data = std::fs::read_to_string(dir.join("data.txt"))?;
+    ///         module.add("DATA", data)?;
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(not(all(windows, PyPy)))]
+    #[cfg_attr(docsrs, doc(cfg(not(all(windows, PyPy)))))]
+    pub fn package_dir(&self) -> Option<&Path> {
+        self.filename()
+            .ok()
+            .and_then(|filename| Path::new(filename).parent())
+    }
+
     /// Adds an attribute to the module.
     ///
+    /// The name is also appended to the module's `__all__` list (see [`PyModule::index`]), so that
+    /// it is imported by `from my_module import *`.
+    ///
     /// For adding classes, functions or modules, prefer to use [`PyModule::add_class`],
     /// [`PyModule::add_function`] or [`PyModule::add_submodule`] instead, respectively.
     ///
@@ -235,6 +268,8 @@ impl PyModule {
 
     /// Adds a new class to the module.
     ///
+    /// Like [`PyModule::add`], this appends the class name to the module's `__all__` list.
+    ///
     /// Notice that this method does not take an argument.
     /// Instead, this method is *generic*, and requires us to use the
     /// "turbofish" syntax to specify the class we want to add.
@@ -431,4 +466,20 @@ mod test {
             assert_eq!(builtins.name().unwrap(), "builtins");
         })
     }
+
+    #[test]
+    #[cfg(not(all(windows, PyPy)))]
+    fn module_filename_and_package_dir() {
+        Python::with_gil(|py| {
+            let json = PyModule::import(py, "json").unwrap();
+            let filename = std::path::Path::new(json.filename().unwrap());
+            assert_eq!(json.package_dir(), filename.parent());
+            assert!(json.package_dir().unwrap().ends_with("json"));
+
+            let builtins = PyModule::import(py, "builtins").unwrap();
+            assert!(builtins.filename().is_err());
+            assert!(builtins.package_dir().is_none());
+            assert!(!py.error_occurred());
+        })
+    }
 }
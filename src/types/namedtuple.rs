@@ -0,0 +1,93 @@
+// Copyright (c) 2017-present PyO3 Project and Contributors
+
+use crate::err::PyResult;
+use crate::types::{PyModule, PyTuple, PyType};
+use crate::{Py, PyAny, PyObject, Python};
+
+/// Helpers for working with Python `collections.namedtuple` types.
+///
+/// Named tuple types are created dynamically at runtime, so unlike the other types in this module
+/// there is no single type object for `PyNamedTuple` to wrap. Instances of named tuple types are
+/// regular `tuple` subclasses and can be accessed as [`PyTuple`](crate::types::PyTuple).
+///
+/// # Examples
+///
+/// ```
+/// use pyo3::prelude::*;
+/// use pyo3::types::PyNamedTuple;
+///
+/// Python::with_gil(|py| -> PyResult<()> {
+///     let point = PyNamedTuple::new_type(py, "Point", &["x", "y"])?;
+///     let p = PyNamedTuple::new(py, point.as_ref(py), vec![1.to_object(py), 2.to_object(py)])?;
+///     assert_eq!(p.as_ref(py).getattr("y")?.extract::<i32>()?, 2);
+///     Ok(())
+/// })
+/// .unwrap();
+/// ```
+pub struct PyNamedTuple {
+    _private: (),
+}
+
+impl PyNamedTuple {
+    /// Creates a new named tuple type with the given name and field names.
+    ///
+    /// Equivalent to Python's `collections.namedtuple(name, fields)`.
+    pub fn new_type(py: Python, name: &str, fields: &[&str]) -> PyResult<Py<PyType>> {
+        let namedtuple = PyModule::import(py, "collections")?.getattr("namedtuple")?;
+        let ty: &PyType = namedtuple.call1((name, fields.to_vec()))?.downcast()?;
+        Ok(ty.into())
+    }
+
+    /// Creates a new instance of the named tuple type `ty` from the given field values.
+    ///
+    /// Equivalent to Python's `ty(*values)`.
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(
+        py: Python,
+        ty: &PyType,
+        values: impl IntoIterator<Item = PyObject>,
+    ) -> PyResult<Py<PyAny>> {
+        let values: Vec<PyObject> = values.into_iter().collect();
+        let args = PyTuple::new(py, values);
+        Ok(ty.call1(args)?.into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::types::{PyNamedTuple, PyTuple};
+    use crate::{Python, ToPyObject};
+
+    #[test]
+    fn test_new_type() {
+        Python::with_gil(|py| {
+            let point = PyNamedTuple::new_type(py, "Point", &["x", "y"]).unwrap();
+            let point = point.as_ref(py);
+            assert_eq!(point.name().unwrap(), "Point");
+            assert!(point.is_subclass::<PyTuple>().unwrap());
+            let fields: Vec<String> = point.getattr("_fields").unwrap().extract().unwrap();
+            assert_eq!(fields, vec!["x", "y"]);
+        });
+    }
+
+    #[test]
+    fn test_new() {
+        Python::with_gil(|py| {
+            let point = PyNamedTuple::new_type(py, "Point", &["x", "y"]).unwrap();
+            let p = PyNamedTuple::new(py, point.as_ref(py), vec![1.to_object(py), 2.to_object(py)])
+                .unwrap();
+            let p = p.as_ref(py);
+            assert_eq!(p.getattr("x").unwrap().extract::<i32>().unwrap(), 1);
+            assert_eq!(p.getattr("y").unwrap().extract::<i32>().unwrap(), 2);
+            assert_eq!(p.extract::<(i32, i32)>().unwrap(), (1, 2));
+        });
+    }
+
+    #[test]
+    fn test_new_wrong_arity() {
+        Python::with_gil(|py| {
+            let point = PyNamedTuple::new_type(py, "Point", &["x", "y"]).unwrap();
+            assert!(PyNamedTuple::new(py, point.as_ref(py), vec![1.to_object(py)]).is_err());
+        });
+    }
+}
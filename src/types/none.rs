@@ -0,0 +1,69 @@
+// Copyright (c) 2017-present PyO3 Project and Contributors
+use crate::err::PyDowncastError;
+use crate::{ffi, AsPyPointer, FromPyObject, PyAny, PyResult, Python};
+
+/// Represents the Python `None` object.
+///
+/// `None` is converted to and from the Rust unit type `()`.
+#[repr(transparent)]
+pub struct PyNone(PyAny);
+
+pyobject_native_type_core!(PyNone, *ffi::Py_TYPE(ffi::Py_None()));
+
+impl PyNone {
+    /// Returns the `None` singleton.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pyo3::prelude::*;
+    /// use pyo3::types::PyNone;
+    ///
+    /// Python::with_gil(|py| {
+    ///     let none = PyNone::get(py);
+    ///     assert!(none.is_none());
+    ///     assert_eq!(none.to_string(), "None");
+    /// });
+    /// ```
+    #[inline]
+    pub fn get(py: Python) -> &PyNone {
+        unsafe { py.from_borrowed_ptr(ffi::Py_None()) }
+    }
+}
+
+/// Extracts `()` from Python `None`, raising `TypeError` for any other object.
+impl<'source> FromPyObject<'source> for () {
+    fn extract(obj: &'source PyAny) -> PyResult<Self> {
+        if obj.as_ptr() == unsafe { ffi::Py_None() } {
+            Ok(())
+        } else {
+            Err(PyDowncastError::new(obj, "NoneType").into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::types::{PyAny, PyNone};
+    use crate::{AsPyPointer, PyTryFrom, Python, ToPyObject};
+
+    #[test]
+    fn test_none_is_singleton() {
+        Python::with_gil(|py| {
+            assert_eq!(PyNone::get(py).as_ptr(), py.None().as_ptr());
+            assert!(<PyNone as PyTryFrom>::try_from(py.None().as_ref(py)).is_ok());
+            let one: &PyAny = 1.to_object(py).into_ref(py);
+            assert!(<PyNone as PyTryFrom>::try_from(one).is_err());
+        })
+    }
+
+    #[test]
+    fn test_unit_conversion() {
+        Python::with_gil(|py| {
+            assert!(().to_object(py).is_none(py));
+            assert!(py.None().extract::<()>(py).is_ok());
+            let err = 1.to_object(py).extract::<()>(py).unwrap_err();
+            assert!(err.is_instance::<crate::exceptions::PyTypeError>(py));
+        })
+    }
+}
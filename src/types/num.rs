@@ -3,11 +3,13 @@
 // based on Daniel Grunwald's https://github.com/dgrunwald/rust-cpython
 
 use crate::{
-    exceptions, ffi, AsPyPointer, FromPyObject, IntoPy, PyAny, PyErr, PyNativeType, PyObject,
-    PyResult, Python, ToPyObject,
+    exceptions, ffi, AsPyPointer, FromPyObject, IntoPy, IntoPyPointer, PyAny, PyErr, PyNativeType,
+    PyObject, PyResult, Python, ToPyObject,
 };
 use std::convert::TryFrom;
 use std::i64;
+#[cfg(not(any(Py_LIMITED_API, PyPy)))]
+use std::os::raw::c_int;
 use std::os::raw::c_long;
 
 fn err_if_invalid_value<T: PartialEq>(
@@ -57,6 +59,122 @@ pub struct PyLong(PyAny);
 
 pyobject_native_type_core!(PyLong, ffi::PyLong_Type, #checkfunction=ffi::PyLong_Check);
 
+impl PyLong {
+    /// Creates a new Python `int` from an `i128`.
+    ///
+    /// This is equivalent to `value.into_py(py)`, but returns a `&PyLong`.
+    pub fn from_i128(py: Python, value: i128) -> &PyLong {
+        unsafe { py.from_owned_ptr(value.into_py(py).into_ptr()) }
+    }
+
+    /// Creates a new Python `int` from a `u128`.
+    ///
+    /// This is equivalent to `value.into_py(py)`, but returns a `&PyLong`.
+    pub fn from_u128(py: Python, value: u128) -> &PyLong {
+        unsafe { py.from_owned_ptr(value.into_py(py).into_ptr()) }
+    }
+
+    /// Creates a new Python `int` from its representation as an array of bytes.
+    ///
+    /// If `signed` is true, the bytes are interpreted as a two's complement integer.
+    /// An empty slice gives `0`.
+    ///
+    /// This is equivalent to Python's `int.from_bytes(bytes, byteorder, signed=signed)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pyo3::prelude::*;
+    /// use pyo3::types::{ByteOrder, PyLong};
+    ///
+    /// Python::with_gil(|py| {
+    ///     let int = PyLong::from_bytes(py, &[0xff, 0x00], ByteOrder::Big, false);
+    ///     assert_eq!(int.extract::<u32>().unwrap(), 0xff00);
+    ///     let int = PyLong::from_bytes(py, &[0xff, 0xff], ByteOrder::Little, true);
+    ///     assert_eq!(int.extract::<i32>().unwrap(), -1);
+    /// });
+    /// ```
+    #[cfg(not(any(Py_LIMITED_API, PyPy)))]
+    pub fn from_bytes<'p>(
+        py: Python<'p>,
+        bytes: &[u8],
+        byteorder: ByteOrder,
+        signed: bool,
+    ) -> &'p PyLong {
+        unsafe {
+            py.from_owned_ptr(ffi::_PyLong_FromByteArray(
+                bytes.as_ptr(),
+                bytes.len(),
+                byteorder.is_little() as c_int,
+                signed as c_int,
+            ))
+        }
+    }
+
+    /// Returns the representation of this integer as an array of `length` bytes.
+    ///
+    /// If `signed` is true, the integer is represented using two's complement. Raises
+    /// `OverflowError` if it does not fit in `length` bytes, or if it is negative and `signed` is
+    /// false.
+    ///
+    /// This is equivalent to Python's `self.to_bytes(length, byteorder, signed=signed)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pyo3::prelude::*;
+    /// use pyo3::types::{ByteOrder, PyLong};
+    ///
+    /// Python::with_gil(|py| -> PyResult<()> {
+    ///     let int = PyLong::from_i128(py, -2);
+    ///     assert_eq!(int.to_bytes(2, ByteOrder::Big, true)?, vec![0xff, 0xfe]);
+    ///     assert!(int.to_bytes(2, ByteOrder::Big, false).is_err());
+    ///     Ok(())
+    /// })
+    /// .unwrap();
+    /// ```
+    #[cfg(not(any(Py_LIMITED_API, PyPy)))]
+    pub fn to_bytes(&self, length: usize, byteorder: ByteOrder, signed: bool) -> PyResult<Vec<u8>> {
+        let mut buffer = vec![0; length];
+        crate::err::error_on_minusone(self.py(), unsafe {
+            ffi::_PyLong_AsByteArray(
+                self.as_ptr() as *mut ffi::PyLongObject,
+                buffer.as_mut_ptr(),
+                buffer.len(),
+                byteorder.is_little() as c_int,
+                signed as c_int,
+            )
+        })?;
+        Ok(buffer)
+    }
+}
+
+/// The order of bytes in the representation of an integer, used by [`PyLong::from_bytes`] and
+/// [`PyLong::to_bytes`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteOrder {
+    /// The most significant byte comes first.
+    Big,
+    /// The least significant byte comes first.
+    Little,
+}
+
+impl ByteOrder {
+    /// Returns the native byte order of the target platform.
+    pub fn native() -> Self {
+        if cfg!(target_endian = "little") {
+            ByteOrder::Little
+        } else {
+            ByteOrder::Big
+        }
+    }
+
+    #[cfg(not(any(Py_LIMITED_API, PyPy)))]
+    fn is_little(self) -> bool {
+        self == ByteOrder::Little
+    }
+}
+
 macro_rules! int_fits_c_long {
     ($rust_type:ty) => {
         impl ToPyObject for $rust_type {
@@ -338,6 +456,17 @@ mod test_128bit_intergers {
         })
     }
 
+    #[test]
+    fn test_pylong_from_128bit() {
+        Python::with_gil(|py| {
+            let obj = PyLong::from_i128(py, std::i128::MIN);
+            assert_eq!(obj.extract::<i128>().unwrap(), std::i128::MIN);
+            let obj = PyLong::from_u128(py, std::u128::MAX);
+            assert_eq!(obj.extract::<u128>().unwrap(), std::u128::MAX);
+            crate::py_run!(py, obj, "assert obj == (1 << 128) - 1");
+        })
+    }
+
     #[test]
     fn test_i128_overflow() {
         Python::with_gil(|py| {
@@ -598,6 +727,51 @@ mod test {
     use crate::Python;
     use crate::ToPyObject;
 
+    #[test]
+    #[cfg(not(any(Py_LIMITED_API, PyPy)))]
+    fn test_pylong_bytes_roundtrip() {
+        use super::{ByteOrder, PyLong};
+        Python::with_gil(|py| {
+            let bytes = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09];
+            let int = PyLong::from_bytes(py, &bytes, ByteOrder::Big, false);
+            crate::py_run!(py, int, "assert int == 0x010203040506070809");
+            assert_eq!(int.to_bytes(9, ByteOrder::Big, false).unwrap(), bytes);
+            let mut reversed = bytes.to_vec();
+            reversed.reverse();
+            assert_eq!(int.to_bytes(9, ByteOrder::Little, false).unwrap(), reversed);
+            // Leading zeroes pad the result
+            assert_eq!(
+                int.to_bytes(10, ByteOrder::Big, false).unwrap()[..2],
+                [0x00, 0x01]
+            );
+
+            let int = PyLong::from_bytes(py, &[0x80], ByteOrder::Little, true);
+            assert_eq!(int.extract::<i8>().unwrap(), -128);
+            assert_eq!(
+                int.to_bytes(2, ByteOrder::Little, true).unwrap(),
+                vec![0x80, 0xff]
+            );
+
+            let zero = PyLong::from_bytes(py, &[], ByteOrder::native(), true);
+            assert_eq!(zero.extract::<i32>().unwrap(), 0);
+        });
+    }
+
+    #[test]
+    #[cfg(not(any(Py_LIMITED_API, PyPy)))]
+    fn test_pylong_to_bytes_overflow() {
+        use super::{ByteOrder, PyLong};
+        use crate::exceptions::PyOverflowError;
+        Python::with_gil(|py| {
+            let int = PyLong::from_i128(py, 256);
+            let err = int.to_bytes(1, ByteOrder::Big, false).unwrap_err();
+            assert!(err.is_instance::<PyOverflowError>(py));
+            let int = PyLong::from_i128(py, -1);
+            let err = int.to_bytes(1, ByteOrder::Big, false).unwrap_err();
+            assert!(err.is_instance::<PyOverflowError>(py));
+        });
+    }
+
     #[test]
     fn test_u32_max() {
         let gil = Python::acquire_gil();
@@ -0,0 +1,143 @@
+// Copyright (c) 2017-present PyO3 Project and Contributors
+use crate::conversion::ToBorrowedObject;
+use crate::exceptions::PyValueError;
+use crate::{ffi, AsPyPointer, FromPyObject, PyAny, PyErr, PyNativeType, PyResult, Python};
+use std::ops::Range;
+
+/// Represents a Python `range` object.
+///
+/// # Examples
+///
+/// ```
+/// use pyo3::prelude::*;
+/// use pyo3::types::PyRange;
+///
+/// Python::with_gil(|py| -> PyResult<()> {
+///     let range = PyRange::new(py, 0, 10, 3)?;
+///     assert_eq!(range.len()?, 4);
+///     assert!(range.contains(9)?);
+///     assert!(!range.contains(10)?);
+///     Ok(())
+/// })
+/// .unwrap();
+/// ```
+#[repr(transparent)]
+pub struct PyRange(PyAny);
+
+pyobject_native_type_core!(PyRange, ffi::PyRange_Type, #checkfunction=ffi::PyRange_Check);
+
+impl PyRange {
+    /// Creates a new `range` object.
+    ///
+    /// This is equivalent to Python's `range(start, stop, step)`, and raises `ValueError` if
+    /// `step` is zero.
+    pub fn new(py: Python, start: isize, stop: isize, step: isize) -> PyResult<&PyRange> {
+        let range = py.get_type::<PyRange>().call1((start, stop, step))?;
+        Ok(range.downcast()?)
+    }
+
+    /// Gets the `start` attribute of the range.
+    pub fn start(&self) -> PyResult<isize> {
+        self.getattr("start")?.extract()
+    }
+
+    /// Gets the `stop` attribute of the range.
+    pub fn stop(&self) -> PyResult<isize> {
+        self.getattr("stop")?.extract()
+    }
+
+    /// Gets the `step` attribute of the range.
+    pub fn step(&self) -> PyResult<isize> {
+        self.getattr("step")?.extract()
+    }
+
+    /// Returns the number of integers in the range.
+    ///
+    /// This is equivalent to Python's `len(range)`, and raises `OverflowError` if the length
+    /// doesn't fit into a `usize`.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> PyResult<usize> {
+        self.as_ref().len()
+    }
+
+    /// Determines if the range contains `value`.
+    ///
+    /// This is equivalent to the Python expression `value in range`.
+    pub fn contains<V>(&self, value: V) -> PyResult<bool>
+    where
+        V: ToBorrowedObject,
+    {
+        let r = value.with_borrowed_ptr(self.py(), |ptr| unsafe {
+            ffi::PySequence_Contains(self.as_ptr(), ptr)
+        });
+        match r {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(PyErr::fetch(self.py())),
+        }
+    }
+}
+
+/// Extracts a `range` object with a step of 1.
+///
+/// Raises `ValueError` if the step of the range is not 1, as `Range` can't represent it.
+impl<'source> FromPyObject<'source> for Range<isize> {
+    fn extract(obj: &'source PyAny) -> PyResult<Self> {
+        let range: &PyRange = obj.downcast()?;
+        let step = range.step()?;
+        if step != 1 {
+            return Err(PyValueError::new_err(format!(
+                "cannot convert a range with step {} to a Rust Range",
+                step
+            )));
+        }
+        Ok(range.start()?..range.stop()?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::types::PyRange;
+    use crate::{PyTryFrom, Python, ToPyObject};
+    use std::ops::Range;
+
+    #[test]
+    fn test_range() {
+        Python::with_gil(|py| {
+            let range = PyRange::new(py, 10, 0, -2).unwrap();
+            assert_eq!(range.start().unwrap(), 10);
+            assert_eq!(range.stop().unwrap(), 0);
+            assert_eq!(range.step().unwrap(), -2);
+            assert_eq!(range.len().unwrap(), 5);
+            assert!(range.contains(4).unwrap());
+            assert!(!range.contains(3).unwrap());
+            assert!(!range.contains("a").unwrap());
+
+            assert!(PyRange::new(py, 0, 1, 0)
+                .unwrap_err()
+                .is_instance::<crate::exceptions::PyValueError>(py));
+
+            let from_python = py.eval("range(3)", None, None).unwrap();
+            assert!(<PyRange as PyTryFrom>::try_from(from_python).is_ok());
+            let list = vec![0, 1, 2].to_object(py);
+            assert!(<PyRange as PyTryFrom>::try_from(list.as_ref(py)).is_err());
+        });
+    }
+
+    #[test]
+    fn test_extract_range() {
+        Python::with_gil(|py| {
+            let range = py.eval("range(-1, 5)", None, None).unwrap();
+            assert_eq!(range.extract::<Range<isize>>().unwrap(), -1..5);
+
+            let stepped = py.eval("range(0, 5, 2)", None, None).unwrap();
+            assert!(stepped
+                .extract::<Range<isize>>()
+                .unwrap_err()
+                .is_instance::<crate::exceptions::PyValueError>(py));
+
+            let list = vec![0, 1].to_object(py);
+            assert!(list.extract::<Range<isize>>(py).is_err());
+        });
+    }
+}
@@ -6,6 +6,7 @@ use crate::instance::PyNativeType;
 use crate::types::{PyAny, PyList, PyTuple};
 use crate::AsPyPointer;
 use crate::{FromPyObject, PyTryFrom, ToBorrowedObject};
+use std::collections::{LinkedList, VecDeque};
 
 /// Represents a reference to a Python object supporting the sequence protocol.
 #[repr(transparent)]
@@ -293,6 +294,24 @@ where
     }
 }
 
+impl<'a, T> FromPyObject<'a> for LinkedList<T>
+where
+    T: FromPyObject<'a>,
+{
+    fn extract(obj: &'a PyAny) -> PyResult<Self> {
+        Ok(extract_sequence(obj)?.into_iter().collect())
+    }
+}
+
+impl<'a, T> FromPyObject<'a> for VecDeque<T>
+where
+    T: FromPyObject<'a>,
+{
+    fn extract(obj: &'a PyAny) -> PyResult<Self> {
+        Ok(extract_sequence(obj)?.into())
+    }
+}
+
 fn extract_sequence<'s, T>(obj: &'s PyAny) -> PyResult<Vec<T>>
 where
     T: FromPyObject<'s>,
@@ -334,6 +353,7 @@ mod test {
     use crate::AsPyPointer;
     use crate::Python;
     use crate::{PyObject, PyTryFrom, ToPyObject};
+    use std::collections::{LinkedList, VecDeque};
 
     fn get_object() -> PyObject {
         // Convenience function for getting a single unique object
@@ -626,6 +646,21 @@ mod test {
         assert!(v == [1, 2]);
     }
 
+    #[test]
+    fn test_extract_to_linked_list_and_vec_deque() {
+        Python::with_gil(|py| {
+            let obj = py.eval("[1, 2, 3]", None, None).unwrap();
+            let linked_list: LinkedList<i32> = obj.extract().unwrap();
+            assert!(linked_list.into_iter().eq(vec![1, 2, 3]));
+            let vec_deque: VecDeque<i32> = obj.extract().unwrap();
+            assert_eq!(vec_deque, [1, 2, 3]);
+
+            let obj = py.eval("(1, 'two')", None, None).unwrap();
+            assert!(obj.extract::<LinkedList<i32>>().is_err());
+            assert!(obj.extract::<VecDeque<i32>>().is_err());
+        });
+    }
+
     #[test]
     fn test_extract_range_to_vec() {
         let gil = Python::acquire_gil();
@@ -66,6 +66,30 @@ impl PyString {
         Ok(unsafe { std::str::from_utf8_unchecked(utf8_slice) })
     }
 
+    /// Checks whether this string is equal to the Rust string `s`.
+    ///
+    /// Short ASCII strings are compared with `PyUnicode_CompareWithASCIIString`, without
+    /// allocating. Other strings are compared using [`PyString::to_str`]. Strings containing
+    /// unpaired surrogates are never equal to `s`.
+    pub fn eq_str(&self, s: &str) -> bool {
+        const MAX_ASCII_LEN: usize = 64;
+
+        if s.len() < MAX_ASCII_LEN && s.is_ascii() && !s.contains('\0') {
+            // CompareWithASCIIString needs a nul-terminated string
+            let mut buf = [0u8; MAX_ASCII_LEN];
+            buf[..s.len()].copy_from_slice(s.as_bytes());
+            unsafe {
+                ffi::PyUnicode_CompareWithASCIIString(self.as_ptr(), buf.as_ptr() as *const c_char)
+                    == 0
+            }
+        } else {
+            match self.to_str() {
+                Ok(this) => this == s,
+                Err(_) => false,
+            }
+        }
+    }
+
     /// Converts the `PyString` into a Rust string.
     ///
     /// Unpaired surrogates invalid UTF-8 sequences are
@@ -246,6 +270,34 @@ mod test {
         })
     }
 
+    #[test]
+    fn test_eq_str() {
+        Python::with_gil(|py| {
+            let s = PyString::new(py, "hello");
+            assert!(s.eq_str("hello"));
+            assert!(!s.eq_str("hell"));
+            assert!(!s.eq_str("hello!"));
+            assert!(!s.eq_str("hello\0"));
+            assert!(!s.eq_str("héllo"));
+
+            let long = "a".repeat(100);
+            assert!(PyString::new(py, &long).eq_str(&long));
+            assert!(!s.eq_str(&long));
+
+            let s = PyString::new(py, "ascii 🐈");
+            assert!(s.eq_str("ascii 🐈"));
+            assert!(!s.eq_str("ascii"));
+
+            let s = PyString::new(py, "nul\0byte");
+            assert!(s.eq_str("nul\0byte"));
+            assert!(!s.eq_str("nul"));
+
+            let obj = py.eval(r#"'\ud800'"#, None, None).unwrap();
+            let surrogate = <PyString as PyTryFrom>::try_from(obj).unwrap();
+            assert!(!surrogate.eq_str("\u{fffd}"));
+        })
+    }
+
     #[test]
     fn test_to_str_surrogate() {
         Python::with_gil(|py| {
@@ -0,0 +1,112 @@
+// Copyright (c) 2017-present PyO3 Project and Contributors
+use crate::{ffi, PyAny, PyNativeType, PyResult};
+
+/// Represents a Python traceback object.
+///
+/// A traceback is a linked list of frames, starting at the frame which caught the exception and
+/// following [`next`](PyTraceback::next) towards the frame where the exception was raised. It
+/// can be obtained from an exception with [`PyErr::traceback`](crate::PyErr::traceback).
+///
+/// # Examples
+///
+/// ```
+/// use pyo3::prelude::*;
+///
+/// Python::with_gil(|py| {
+///     let err = py
+///         .run("def f():\n    raise ValueError('oops')\nf()", None, None)
+///         .unwrap_err();
+///     let mut traceback = err.traceback(py).unwrap();
+///     while let Some(next) = traceback.next() {
+///         traceback = next;
+///     }
+///     assert_eq!(traceback.filename().unwrap(), "<string>");
+///     assert_eq!(traceback.name().unwrap(), "f");
+///     assert_eq!(traceback.lineno().unwrap(), 2);
+/// });
+/// ```
+#[repr(transparent)]
+pub struct PyTraceback(PyAny);
+
+pyobject_native_type_core!(
+    PyTraceback,
+    ffi::PyTraceBack_Type,
+    #checkfunction=ffi::PyTraceBack_Check
+);
+
+impl PyTraceback {
+    /// Returns the name of the file containing the code of this frame.
+    pub fn filename(&self) -> PyResult<&str> {
+        self.code()?.getattr("co_filename")?.extract()
+    }
+
+    /// Returns the name of the function (or module) executing in this frame.
+    pub fn name(&self) -> PyResult<&str> {
+        self.code()?.getattr("co_name")?.extract()
+    }
+
+    /// Returns the line number at which the exception passed through this frame.
+    pub fn lineno(&self) -> PyResult<usize> {
+        self.getattr("tb_lineno")?.extract()
+    }
+
+    /// Returns the next traceback entry, one frame closer to where the exception was raised.
+    ///
+    /// Returns `None` if this is the frame where the exception was raised.
+    pub fn next(&self) -> Option<&PyTraceback> {
+        self.getattr("tb_next").ok()?.downcast().ok()
+    }
+
+    /// Formats the traceback entries, starting at this one, as Python would print them, using
+    /// `traceback.format_tb()`.
+    pub fn format(&self) -> PyResult<String> {
+        let lines: Vec<&str> = self
+            .py()
+            .import("traceback")?
+            .call_method1("format_tb", (self,))?
+            .extract()?;
+        Ok(lines.concat())
+    }
+
+    fn code(&self) -> PyResult<&PyAny> {
+        self.getattr("tb_frame")?.getattr("f_code")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::types::PyTraceback;
+    use crate::{PyTryFrom, Python, ToPyObject};
+
+    #[test]
+    fn test_traceback() {
+        Python::with_gil(|py| {
+            let err = py
+                .run(
+                    "def inner():\n    raise ValueError('oops')\ndef outer():\n    inner()\nouter()",
+                    None,
+                    None,
+                )
+                .unwrap_err();
+            let traceback = err.traceback(py).unwrap();
+            assert_eq!(traceback.name().unwrap(), "<module>");
+            assert_eq!(traceback.lineno().unwrap(), 5);
+
+            let outer = traceback.next().unwrap();
+            assert_eq!(outer.name().unwrap(), "outer");
+            assert_eq!(outer.lineno().unwrap(), 4);
+
+            let inner = outer.next().unwrap();
+            assert_eq!(inner.filename().unwrap(), "<string>");
+            assert_eq!(inner.name().unwrap(), "inner");
+            assert!(inner.next().is_none());
+
+            let formatted = traceback.format().unwrap();
+            assert!(formatted.contains("in outer"));
+            assert!(formatted.ends_with("in inner\n"));
+
+            let not_traceback = 1.to_object(py);
+            assert!(<PyTraceback as PyTryFrom>::try_from(not_traceback.as_ref(py)).is_err());
+        });
+    }
+}
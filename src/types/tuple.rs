@@ -77,6 +77,22 @@ impl PyTuple {
         }
     }
 
+    /// Gets the tuple item at the specified index, without checking that the index is in range.
+    ///
+    /// This uses the `PyTuple_GET_ITEM` macro, which is faster than [`PyTuple::get_item`] in tight
+    /// loops.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `index < self.len()`. This is only checked in debug builds.
+    #[cfg(not(any(Py_LIMITED_API, PyPy)))]
+    #[cfg_attr(docsrs, doc(cfg(not(any(Py_LIMITED_API, PyPy)))))]
+    pub unsafe fn get_item_unchecked(&self, index: usize) -> &PyAny {
+        debug_assert!(index < self.len());
+        self.py()
+            .from_borrowed_ptr(ffi::PyTuple_GET_ITEM(self.as_ptr(), index as Py_ssize_t))
+    }
+
     /// Returns `self` as a slice of objects.
     #[cfg(not(Py_LIMITED_API))]
     #[cfg_attr(docsrs, doc(cfg(not(Py_LIMITED_API))))]
@@ -362,6 +378,16 @@ mod test {
         assert_eq!(3, slice[2].extract().unwrap());
     }
 
+    #[test]
+    #[cfg(not(any(Py_LIMITED_API, PyPy)))]
+    fn test_get_item_unchecked() {
+        Python::with_gil(|py| {
+            let tuple = PyTuple::new(py, &[1, 2, 3]);
+            let obj = unsafe { tuple.get_item_unchecked(2) };
+            assert_eq!(3, obj.extract::<i32>().unwrap());
+        });
+    }
+
     #[test]
     fn test_tuple_lengths_up_to_12() {
         Python::with_gil(|py| {
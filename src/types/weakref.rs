@@ -0,0 +1,118 @@
+// Copyright (c) 2017-present PyO3 Project and Contributors
+use crate::err::{PyErr, PyResult};
+use crate::{ffi, AsPyPointer, PyAny, PyNativeType, Python};
+use std::ptr;
+
+/// Represents a Python `weakref.ref` object.
+#[repr(transparent)]
+pub struct PyWeakRef(PyAny);
+
+pyobject_native_type_core!(
+    PyWeakRef,
+    ffi::_PyWeakref_RefType,
+    #module=Some("weakref"),
+    #checkfunction=ffi::PyWeakref_CheckRef
+);
+
+impl PyWeakRef {
+    /// Creates a new weak reference to `referent`.
+    ///
+    /// If `callback` is given, it is called with the weak reference object when the referent is
+    /// about to be finalized. Raises `TypeError` if `referent` does not support weak references.
+    ///
+    /// This is equivalent to Python's `weakref.ref(referent, callback)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pyo3::prelude::*;
+    /// use pyo3::types::PyWeakRef;
+    /// use pyo3::AsPyPointer;
+    ///
+    /// Python::with_gil(|py| -> PyResult<()> {
+    ///     let referent = py.eval("type('Referent', (), {})()", None, None)?;
+    ///     let weakref = PyWeakRef::new(py, referent, None)?;
+    ///     assert!(weakref.alive());
+    ///     assert_eq!(weakref.call()?.unwrap().as_ptr(), referent.as_ptr());
+    ///     Ok(())
+    /// })
+    /// .unwrap();
+    /// ```
+    pub fn new<'p>(
+        py: Python<'p>,
+        referent: &PyAny,
+        callback: Option<&PyAny>,
+    ) -> PyResult<&'p PyWeakRef> {
+        let callback = callback.map_or(ptr::null_mut(), |callback| callback.as_ptr());
+        unsafe { py.from_owned_ptr_or_err(ffi::PyWeakref_NewRef(referent.as_ptr(), callback)) }
+    }
+
+    /// Gets the referent, or `None` if it has been garbage collected.
+    ///
+    /// This is equivalent to Python's `weakref()`.
+    pub fn call(&self) -> PyResult<Option<&PyAny>> {
+        let py = self.py();
+        unsafe {
+            let obj = ffi::PyWeakref_GetObject(self.as_ptr());
+            if obj.is_null() {
+                Err(PyErr::fetch(py))
+            } else if obj == ffi::Py_None() {
+                Ok(None)
+            } else {
+                // PyWeakref_GetObject returns a borrowed reference
+                ffi::Py_INCREF(obj);
+                Ok(Some(py.from_owned_ptr(obj)))
+            }
+        }
+    }
+
+    /// Returns whether the referent is still alive.
+    pub fn alive(&self) -> bool {
+        unsafe { ffi::PyWeakref_GetObject(self.as_ptr()) != ffi::Py_None() }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::types::{PyList, PyWeakRef};
+    use crate::{AsPyPointer, PyObject, PyTryFrom, Python, ToPyObject};
+
+    #[test]
+    fn test_weakref() {
+        Python::with_gil(|py| {
+            let referent = py.eval("type('Referent', (), {})()", None, None).unwrap();
+            let weakref = PyWeakRef::new(py, referent, None).unwrap();
+            assert!(weakref.alive());
+            assert_eq!(weakref.call().unwrap().unwrap().as_ptr(), referent.as_ptr());
+            assert!(<PyWeakRef as PyTryFrom>::try_from(weakref.as_ref()).is_ok());
+            assert!(<PyWeakRef as PyTryFrom>::try_from(referent).is_err());
+        });
+    }
+
+    #[test]
+    fn test_weakref_dead() {
+        Python::with_gil(|py| {
+            let called = PyList::empty(py);
+            let callback = called.getattr("append").unwrap();
+            let weakref: PyObject = {
+                // Release the pool's references to the referent when it goes out of scope
+                let pool = unsafe { py.new_pool() };
+                let py = pool.python();
+                let referent = py.eval("type('Referent', (), {})()", None, None).unwrap();
+                PyWeakRef::new(py, referent, Some(callback)).unwrap().into()
+            };
+            let weakref: &PyWeakRef = weakref.cast_as(py).unwrap();
+            assert!(!weakref.alive());
+            assert!(weakref.call().unwrap().is_none());
+            assert_eq!(called.len(), 1);
+        });
+    }
+
+    #[test]
+    fn test_weakref_unsupported() {
+        Python::with_gil(|py| {
+            let obj = 1.to_object(py);
+            assert!(PyWeakRef::new(py, obj.as_ref(py), None).is_err());
+        });
+    }
+}
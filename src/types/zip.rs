@@ -0,0 +1,129 @@
+// Copyright (c) 2017-present PyO3 Project and Contributors
+use crate::types::PyTuple;
+use crate::{
+    ffi, AsPyPointer, FromPyObject, PyAny, PyErr, PyNativeType, PyResult, Python, ToPyObject,
+};
+
+/// Represents a Python `zip` object.
+///
+/// Iterating over a `&PyZip` yields a tuple with one item from each of the zipped iterables.
+///
+/// # Examples
+///
+/// ```
+/// use pyo3::prelude::*;
+/// use pyo3::types::PyZip;
+///
+/// Python::with_gil(|py| -> PyResult<()> {
+///     let names = py.eval("['a', 'b']", None, None)?;
+///     let values = py.eval("[1, 2, 3]", None, None)?;
+///     for item in PyZip::new(py, vec![names, values])? {
+///         let (name, value): (&str, i32) = item?.extract()?;
+///         println!("{} = {}", name, value);
+///     }
+///     Ok(())
+/// })
+/// .unwrap();
+/// ```
+#[repr(transparent)]
+pub struct PyZip(PyAny);
+
+pyobject_native_type_core!(PyZip, ffi::PyZip_Type);
+
+impl PyZip {
+    /// Creates a `zip` object over `iterables`.
+    ///
+    /// This is equivalent to Python's `zip(*iterables)`.
+    pub fn new<'p, 'a>(
+        py: Python<'p>,
+        iterables: impl IntoIterator<Item = &'a PyAny>,
+    ) -> PyResult<&'p PyZip> {
+        let iterables: Vec<&PyAny> = iterables.into_iter().collect();
+        let zip = py.get_type::<PyZip>().call1(PyTuple::new(py, iterables))?;
+        Ok(zip.downcast()?)
+    }
+
+    /// Zips two slices by converting them to Python lists, and extracts the resulting pairs.
+    ///
+    /// Like Python's `zip()`, this stops at the end of the shorter slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pyo3::prelude::*;
+    /// use pyo3::types::PyZip;
+    ///
+    /// Python::with_gil(|py| {
+    ///     let pairs = PyZip::from_slices(py, &[1, 2, 3], &["a".to_owned(), "b".to_owned()]);
+    ///     assert_eq!(pairs.unwrap(), vec![(1, "a".to_owned()), (2, "b".to_owned())]);
+    /// });
+    /// ```
+    pub fn from_slices<A, B>(py: Python, a: &[A], b: &[B]) -> PyResult<Vec<(A, B)>>
+    where
+        A: ToPyObject + for<'a> FromPyObject<'a>,
+        B: ToPyObject + for<'a> FromPyObject<'a>,
+    {
+        let a = a.to_object(py);
+        let b = b.to_object(py);
+        PyZip::new(py, vec![a.as_ref(py), b.as_ref(py)])?
+            .map(|pair| pair?.extract())
+            .collect()
+    }
+}
+
+impl<'p> Iterator for &'p PyZip {
+    type Item = PyResult<&'p PyTuple>;
+
+    /// Retrieves the next tuple of items.
+    ///
+    /// Returns `None` when any of the underlying iterators is exhausted, and `Some(Err(..))` if
+    /// one of them raises an exception.
+    fn next(&mut self) -> Option<Self::Item> {
+        let py = self.py();
+        match unsafe { py.from_owned_ptr_or_opt::<PyAny>(ffi::PyIter_Next(self.as_ptr())) } {
+            Some(items) => Some(items.downcast().map_err(PyErr::from)),
+            None => {
+                if PyErr::occurred(py) {
+                    Some(Err(PyErr::fetch(py)))
+                } else {
+                    None
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::types::PyZip;
+    use crate::{PyTryFrom, Python, ToPyObject};
+
+    #[test]
+    fn test_zip() {
+        Python::with_gil(|py| {
+            let numbers = vec![1, 2, 3].to_object(py);
+            let letters = "ab".to_object(py);
+            let zip = PyZip::new(py, vec![numbers.as_ref(py), letters.as_ref(py)]).unwrap();
+            let items: Vec<(i32, String)> =
+                zip.map(|item| item.unwrap().extract().unwrap()).collect();
+            assert_eq!(items, vec![(1, "a".to_owned()), (2, "b".to_owned())]);
+
+            let from_python = py.eval("zip()", None, None).unwrap();
+            assert!(<PyZip as PyTryFrom>::try_from(from_python).is_ok());
+            assert!(<PyZip as PyTryFrom>::try_from(numbers.as_ref(py)).is_err());
+
+            let not_iterable = 1.to_object(py);
+            assert!(PyZip::new(py, vec![not_iterable.as_ref(py)]).is_err());
+        });
+    }
+
+    #[test]
+    fn test_zip_from_slices() {
+        Python::with_gil(|py| {
+            let pairs = PyZip::from_slices(py, &[1.5, 2.5], &[true, false, true]).unwrap();
+            assert_eq!(pairs, vec![(1.5, true), (2.5, false)]);
+            let empty: Vec<(i32, i32)> = PyZip::from_slices(py, &[], &[1]).unwrap();
+            assert!(empty.is_empty());
+        });
+    }
+}
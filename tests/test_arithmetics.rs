@@ -501,6 +501,50 @@ fn rich_comparisons_python_3_type_error() {
     py_expect_exception!(py, c2, "1 >= c2", PyTypeError);
 }
 
+#[pyclass]
+struct Version {
+    major: u32,
+    minor: u32,
+}
+
+#[pymethods]
+impl Version {
+    fn __eq__(&self, other: &Self) -> bool {
+        (self.major, self.minor) == (other.major, other.minor)
+    }
+
+    fn __lt__(&self, other: PyRef<Self>) -> bool {
+        (self.major, self.minor) < (other.major, other.minor)
+    }
+}
+
+#[test]
+fn rich_comparisons_pymethods() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let v1 = PyCell::new(py, Version { major: 1, minor: 0 }).unwrap();
+    let v2 = PyCell::new(py, Version { major: 1, minor: 2 }).unwrap();
+    let v2_copy = PyCell::new(py, Version { major: 1, minor: 2 }).unwrap();
+    py_run!(py, v1 v2 v2_copy, "assert v2 == v2_copy");
+    py_run!(py, v1 v2 v2_copy, "assert not (v1 == v2)");
+    py_run!(py, v1 v2 v2_copy, "assert v1 != v2");
+    py_run!(py, v1 v2 v2_copy, "assert not (v2 != v2_copy)");
+    py_run!(py, v1 v2 v2_copy, "assert v1 < v2");
+    py_run!(py, v1 v2 v2_copy, "assert v2 > v1");
+    py_run!(py, v1 v2 v2_copy, "assert v1.__eq__(v1) is True");
+    py_run!(py, v1 v2 v2_copy, "assert v1.__le__(v1) is NotImplemented");
+
+    // Comparisons with other types return NotImplemented
+    py_run!(py, v1, "assert v1 != 1");
+    py_run!(py, v1, "assert not (v1 == 1)");
+    py_expect_exception!(py, v1, "v1 < 1", PyTypeError);
+    py_expect_exception!(py, v1, "v1 <= v1", PyTypeError);
+
+    // Like Python classes defining __eq__, instances are not hashable
+    py_expect_exception!(py, v1, "hash(v1)", PyTypeError);
+}
+
 // Checks that binary operations for which the arguments don't match the
 // required type, return NotImplemented.
 mod return_not_implemented {
@@ -1,6 +1,7 @@
 #![cfg(not(Py_LIMITED_API))]
 
 use pyo3::buffer::PyBuffer;
+use pyo3::class::buffer::{BufferInfo, PyBufferInfo};
 use pyo3::class::PyBufferProtocol;
 use pyo3::exceptions::PyBufferError;
 use pyo3::ffi;
@@ -130,3 +131,114 @@ fn test_buffer_referenced() {
 
     assert!(drop_called.load(Ordering::Relaxed));
 }
+
+#[pyclass(buffer)]
+struct BufferInfoClass {
+    data: Vec<u16>,
+}
+
+unsafe impl PyBufferInfo for BufferInfoClass {
+    fn __buffer_info__(&self) -> BufferInfo {
+        BufferInfo {
+            ptr: self.data.as_ptr() as *mut c_void,
+            len: self.data.len() * 2,
+            itemsize: 2,
+            format: "H\0",
+            readonly: true,
+            shape: None,
+        }
+    }
+}
+
+#[pymethods]
+impl BufferInfoClass {
+    fn push(&mut self, value: u16) {
+        self.data.push(value);
+    }
+}
+
+#[test]
+fn test_pyclass_buffer() {
+    Python::with_gil(|py| {
+        let instance = Py::new(
+            py,
+            BufferInfoClass {
+                data: vec![1, 2, 3],
+            },
+        )
+        .unwrap();
+        let env = [("ob", instance.clone_ref(py))].into_py_dict(py);
+        py_assert!(py, *env, "memoryview(ob).tolist() == [1, 2, 3]");
+        py_assert!(py, *env, "memoryview(ob).format == 'H'");
+        py_assert!(py, *env, "memoryview(ob).readonly");
+
+        {
+            let buf = PyBuffer::<u16>::get(instance.as_ref(py)).unwrap();
+            assert_eq!(buf.to_vec(py).unwrap(), vec![1, 2, 3]);
+            // The object can't be mutated while the buffer is exported
+            py_expect_exception!(py, *env, "ob.push(4)", PyRuntimeError);
+            buf.release(py);
+        }
+
+        instance.borrow_mut(py).push(4);
+        py_assert!(
+            py,
+            *env,
+            "bytes(ob) == b'\\x01\\x00\\x02\\x00\\x03\\x00\\x04\\x00'"
+        );
+        py_expect_exception!(py, *env, "memoryview(ob)[0] = 5", PyTypeError);
+    });
+}
+
+/// A row-major matrix of bytes, exposed as a two-dimensional buffer.
+#[pyclass(buffer)]
+struct ByteMatrix {
+    rows: usize,
+    cols: usize,
+    data: Vec<u8>,
+}
+
+unsafe impl PyBufferInfo for ByteMatrix {
+    fn __buffer_info__(&self) -> BufferInfo {
+        BufferInfo::bytes(&self.data).with_shape(vec![self.rows, self.cols])
+    }
+}
+
+#[test]
+fn test_pyclass_buffer_2d() {
+    Python::with_gil(|py| {
+        let matrix = Py::new(
+            py,
+            ByteMatrix {
+                rows: 2,
+                cols: 3,
+                data: vec![1, 2, 3, 4, 5, 6],
+            },
+        )
+        .unwrap();
+        let env = [("ob", matrix.clone_ref(py))].into_py_dict(py);
+        py_assert!(py, *env, "memoryview(ob).shape == (2, 3)");
+        py_assert!(py, *env, "memoryview(ob).strides == (3, 1)");
+        py_assert!(py, *env, "memoryview(ob)[0, 0] == 1");
+        py_assert!(py, *env, "memoryview(ob)[1, 2] == 6");
+        py_assert!(
+            py,
+            *env,
+            "memoryview(ob).tolist() == [[1, 2, 3], [4, 5, 6]]"
+        );
+        py_assert!(py, *env, "bytes(ob) == bytes([1, 2, 3, 4, 5, 6])");
+
+        let buf = PyBuffer::<u8>::get(matrix.as_ref(py)).unwrap();
+        assert_eq!(buf.dimensions(), 2);
+        assert_eq!(buf.shape(), [2, 3]);
+        assert_eq!(buf.strides(), [3, 1]);
+        assert_eq!(buf.format().to_str().unwrap(), "B");
+        assert!(buf.is_c_contiguous());
+        assert_eq!(buf.to_vec(py).unwrap(), vec![1, 2, 3, 4, 5, 6]);
+        buf.release(py);
+
+        // The shape must match the length of the buffer
+        matrix.borrow_mut(py).rows = 3;
+        py_expect_exception!(py, *env, "memoryview(ob)", PyBufferError);
+    });
+}
@@ -1,6 +1,6 @@
 use pyo3::prelude::*;
 use pyo3::types::PyType;
-use pyo3::{py_run, PyClass};
+use pyo3::{py_run, AsPyPointer, PyClass};
 
 mod common;
 
@@ -81,6 +81,18 @@ fn class_with_docstr() {
     }
 }
 
+/// This doc string is not exposed to Python
+#[pyclass(no_doc)]
+struct ClassWithoutDocs {}
+
+#[test]
+fn class_without_docstr() {
+    Python::with_gil(|py| {
+        let typeobj = py.get_type::<ClassWithoutDocs>();
+        py_assert!(py, typeobj, "typeobj.__doc__ is None");
+    });
+}
+
 #[pyclass(name = "CustomName")]
 struct EmptyClass2 {}
 
@@ -328,3 +340,211 @@ fn test_tuple_struct_class() {
         py_assert!(py, typeobj, "typeobj.__name__ == 'TupleClass'");
     });
 }
+
+#[pyclass]
+struct ClassWithData {
+    data: Vec<i32>,
+}
+
+#[test]
+fn test_map_borrow() {
+    Python::with_gil(|py| {
+        let cell = PyCell::new(py, ClassWithData { data: vec![1, 2] }).unwrap();
+        {
+            let data = cell.map_borrow(|c| &c.data);
+            assert_eq!(*data, vec![1, 2]);
+            assert!(cell.try_borrow().is_ok());
+            assert!(cell.try_borrow_mut().is_err());
+        }
+        cell.borrow_mut().data.push(3);
+
+        let guard = cell.borrow_mut();
+        assert!(cell.try_map_borrow(|c| &c.data).is_err());
+        drop(guard);
+        let data = cell.try_map_borrow(|c| c.data.as_slice()).unwrap();
+        assert_eq!(&*data, &[1, 2, 3]);
+    });
+}
+
+#[test]
+fn test_release_borrow() {
+    Python::with_gil(|py| {
+        let cell = PyCell::new(py, ClassWithData { data: vec![] }).unwrap();
+        let mut borrow = cell.borrow_mut();
+        borrow.data.push(1);
+        let released = borrow.release();
+        assert_eq!(released.as_ptr(), cell.as_ptr());
+        assert_eq!(released.borrow().data, vec![1]);
+        assert!(cell.try_borrow_mut().is_ok());
+
+        let len = cell.try_borrow_mut_scoped(|c| {
+            c.data.push(2);
+            c.data.len()
+        });
+        assert_eq!(len.unwrap(), 2);
+        assert!(cell.try_borrow_mut().is_ok());
+
+        let guard = cell.borrow();
+        assert!(cell.try_borrow_mut_scoped(|c| c.data.clear()).is_err());
+        assert_eq!(guard.data, vec![1, 2]);
+    });
+}
+
+#[test]
+fn test_try_replace_with() {
+    Python::with_gil(|py| {
+        let cell = PyCell::new(py, ClassWithData { data: vec![1] }).unwrap();
+        let updated = cell.try_replace_with(|c| {
+            if c.data.is_empty() {
+                None
+            } else {
+                Some(ClassWithData { data: vec![] })
+            }
+        });
+        assert!(updated.unwrap());
+        assert!(cell.borrow().data.is_empty());
+        assert!(!cell.try_replace_with(|_| None).unwrap());
+
+        let _guard = cell.borrow();
+        let err = cell
+            .try_replace_with(|_| Some(ClassWithData { data: vec![2] }))
+            .unwrap_err();
+        assert!(err.is_instance::<pyo3::exceptions::PyRuntimeError>(py));
+    });
+}
+
+#[pyclass(unsafe_lifetime)]
+struct BorrowedText<'a> {
+    #[pyo3(get)]
+    text: &'a str,
+}
+
+unsafe impl pyo3::pyclass::PyClassUnsafeLifetime for BorrowedText<'static> {}
+
+#[pymethods]
+impl BorrowedText<'static> {
+    fn upper(&self) -> String {
+        self.text.to_uppercase()
+    }
+
+    #[staticmethod]
+    fn kind() -> &'static str {
+        "borrowed"
+    }
+}
+
+#[test]
+fn test_unsafe_lifetime() {
+    let text = String::from("hello");
+    Python::with_gil(|py| {
+        let obj = BorrowedText { text: &text };
+        let obj: BorrowedText<'static> = unsafe { std::mem::transmute(obj) };
+        let obj = PyCell::new(py, obj).unwrap();
+        py_assert!(py, obj, "obj.text == 'hello'");
+        py_assert!(py, obj, "obj.upper() == 'HELLO'");
+        py_assert!(py, obj, "obj.kind() == 'borrowed'");
+    });
+}
+
+#[pyclass]
+struct GenericContainer {}
+
+#[pymethods]
+impl GenericContainer {
+    #[new]
+    fn new() -> Self {
+        GenericContainer {}
+    }
+}
+
+#[pyclass]
+struct CustomClassGetItem {}
+
+#[pymethods]
+impl CustomClassGetItem {
+    #[classmethod]
+    fn __class_getitem__(_cls: &PyType, key: &PyAny) -> PyResult<String> {
+        Ok(format!("CustomClassGetItem[{}]", key.repr()?))
+    }
+}
+
+#[test]
+#[cfg(Py_3_9)]
+fn class_getitem() {
+    Python::with_gil(|py| {
+        let generic = py.get_type::<GenericContainer>();
+        py_run!(
+            py,
+            generic,
+            r#"
+            import types
+            alias = generic[int]
+            assert isinstance(alias, types.GenericAlias)
+            assert alias.__origin__ is generic
+            assert alias.__args__ == (int,)
+            assert generic[int, str].__args__ == (int, str)
+
+            import typing
+            assert typing.get_args(generic[int]) == (int,)
+            assert typing.get_origin(generic[int]) is generic
+            try:
+                isinstance(generic(), generic[int])
+            except TypeError:
+                pass
+            else:
+                assert False, "isinstance() with a parameterized generic should raise TypeError"
+            "#
+        );
+
+        let custom = py.get_type::<CustomClassGetItem>();
+        py_assert!(py, custom, "custom[1] == 'CustomClassGetItem[1]'");
+    });
+}
+
+#[pyclass(str)]
+#[derive(Debug)]
+struct DebugRepr {
+    #[pyo3(get)]
+    value: i32,
+}
+
+#[pyclass(str, display)]
+struct DisplayOnly {
+    value: i32,
+}
+
+impl std::fmt::Display for DisplayOnly {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "value is {}", self.value)
+    }
+}
+
+#[pyclass(str, display)]
+#[derive(Debug)]
+struct DebugAndDisplay {}
+
+impl std::fmt::Display for DebugAndDisplay {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("displayed")
+    }
+}
+
+#[test]
+fn repr_and_str_from_formatting() {
+    Python::with_gil(|py| {
+        let debug = PyCell::new(py, DebugRepr { value: 5 }).unwrap();
+        py_assert!(py, debug, "repr(debug) == 'DebugRepr { value: 5 }'");
+        py_assert!(py, debug, "str(debug) == 'DebugRepr { value: 5 }'");
+
+        let display = PyCell::new(py, DisplayOnly { value: 5 }).unwrap();
+        py_assert!(py, display, "repr(display) == 'value is 5'");
+        py_assert!(py, display, "str(display) == 'value is 5'");
+
+        let both = PyCell::new(py, DebugAndDisplay {}).unwrap();
+        py_assert!(py, both, "repr(both) == 'DebugAndDisplay'");
+        py_assert!(py, both, "str(both) == 'displayed'");
+
+        let _guard = debug.borrow_mut();
+        py_expect_exception!(py, debug, "repr(debug)", PyRuntimeError);
+    });
+}
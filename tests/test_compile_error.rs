@@ -16,6 +16,7 @@ fn test_compile_errors() {
     tests_rust_1_48(&t);
     tests_rust_1_49(&t);
     tests_rust_1_52(&t);
+    tests_rust_1_78(&t);
 
     #[rustversion::since(1.45)]
     fn tests_rust_1_45(t: &trybuild::TestCases) {
@@ -36,11 +37,14 @@ fn test_compile_errors() {
     fn tests_rust_1_49(t: &trybuild::TestCases) {
         t.compile_fail("tests/ui/deprecations.rs");
         t.compile_fail("tests/ui/invalid_frompy_derive.rs");
+        t.compile_fail("tests/ui/invalid_topy_derive.rs");
         t.compile_fail("tests/ui/invalid_pymethod_receiver.rs");
         t.compile_fail("tests/ui/pyclass_send.rs");
 
         #[cfg(Py_LIMITED_API)]
         t.compile_fail("tests/ui/abi3_nativetype_inheritance.rs");
+        #[cfg(Py_LIMITED_API)]
+        t.compile_fail("tests/ui/abi3_finalizer.rs");
     }
     #[rustversion::before(1.49)]
     fn tests_rust_1_49(_t: &trybuild::TestCases) {}
@@ -48,7 +52,15 @@ fn test_compile_errors() {
     #[rustversion::since(1.52)]
     fn tests_rust_1_52(t: &trybuild::TestCases) {
         t.compile_fail("tests/ui/invalid_result_conversion.rs");
+        t.compile_fail("tests/ui/invalid_pyclass_format.rs");
     }
     #[rustversion::before(1.52)]
     fn tests_rust_1_52(_t: &trybuild::TestCases) {}
+
+    #[rustversion::since(1.78)]
+    fn tests_rust_1_78(t: &trybuild::TestCases) {
+        t.compile_fail("tests/ui/invalid_pymethods_not_pyclass.rs");
+    }
+    #[rustversion::before(1.78)]
+    fn tests_rust_1_78(_t: &trybuild::TestCases) {}
 }
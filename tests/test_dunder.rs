@@ -413,6 +413,79 @@ fn context_manager() {
     assert!(c.exit_called);
 }
 
+#[pyclass(context_manager)]
+struct RustContextManager {
+    fail_enter: bool,
+    exit_args: Option<(Option<String>, Option<String>, bool)>,
+}
+
+impl pyo3::class::context::PyContextManager for RustContextManager {
+    fn __enter__(&mut self, py: Python) -> PyResult<PyObject> {
+        if self.fail_enter {
+            return Err(PyValueError::new_err("cannot enter"));
+        }
+        Ok(42.into_py(py))
+    }
+
+    fn __exit__(
+        &mut self,
+        exc_type: Option<&PyType>,
+        exc_value: Option<&pyo3::exceptions::PyBaseException>,
+        traceback: Option<&PyAny>,
+    ) -> PyResult<bool> {
+        self.exit_args = Some((
+            exc_type.map(|ty| ty.name().unwrap().to_string()),
+            exc_value.map(|value| value.to_string()),
+            traceback.is_some(),
+        ));
+        match exc_type {
+            Some(ty) => ty.is_subclass::<PyValueError>(),
+            None => Ok(false),
+        }
+    }
+}
+
+#[test]
+fn rust_context_manager() {
+    Python::with_gil(|py| {
+        let c = PyCell::new(
+            py,
+            RustContextManager {
+                fail_enter: false,
+                exit_args: None,
+            },
+        )
+        .unwrap();
+
+        py_run!(py, c, "with c as x: assert x == 42");
+        assert_eq!(c.borrow_mut().exit_args.take(), Some((None, None, false)));
+
+        // Returning true from __exit__ suppresses the exception
+        py_run!(py, c, "with c: raise ValueError('suppressed')");
+        assert_eq!(
+            c.borrow_mut().exit_args.take(),
+            Some((
+                Some("ValueError".to_string()),
+                Some("suppressed".to_string()),
+                true
+            ))
+        );
+
+        py_expect_exception!(py, c, "with c: raise KeyError()", PyKeyError);
+        assert_eq!(
+            c.borrow_mut().exit_args.take().unwrap().0.as_deref(),
+            Some("KeyError")
+        );
+
+        // If __enter__ raises, the body and __exit__ are not run
+        c.borrow_mut().fail_enter = true;
+        py_expect_exception!(py, c, "with c: assert False", PyValueError);
+        assert!(c.borrow().exit_args.is_none());
+
+        py_expect_exception!(py, c, "c.__exit__(None, None)", PyTypeError);
+    });
+}
+
 #[test]
 fn test_basics() {
     let gil = Python::acquire_gil();
@@ -675,3 +748,132 @@ assert c.counter.count == 3
         .map_err(|e| e.print(py))
         .unwrap();
 }
+
+/// Stores a non-negative integer on the instance it is accessed through.
+#[pyclass]
+struct NonNegative {
+    name: String,
+}
+
+#[pymethods]
+impl NonNegative {
+    #[new]
+    fn new(name: String) -> Self {
+        NonNegative { name }
+    }
+
+    #[get_descriptor]
+    fn get(
+        slf: PyRef<Self>,
+        py: Python,
+        obj: Option<&PyAny>,
+        _objtype: Option<&PyType>,
+    ) -> PyResult<PyObject> {
+        match obj {
+            Some(obj) => Ok(obj.getattr(format!("_{}", slf.name))?.into()),
+            // Accessed on the class, so return the descriptor itself
+            None => Ok(slf.into_py(py)),
+        }
+    }
+
+    #[set_descriptor]
+    fn set(&self, obj: &PyAny, value: i64) -> PyResult<()> {
+        if value < 0 {
+            return Err(PyValueError::new_err("value must be non-negative"));
+        }
+        obj.setattr(format!("_{}", self.name).as_str(), value)
+    }
+
+    #[delete_descriptor]
+    fn delete(&self, obj: &PyAny) -> PyResult<()> {
+        obj.delattr(format!("_{}", self.name).as_str())
+    }
+}
+
+/// A descriptor which learns the name it was assigned to in the owning class.
+#[pyclass]
+struct NamedField {
+    #[pyo3(get)]
+    name: Option<String>,
+}
+
+#[pymethods]
+impl NamedField {
+    #[new]
+    fn new() -> Self {
+        NamedField { name: None }
+    }
+
+    fn __set_name__(&mut self, _owner: &PyType, name: &str) {
+        self.name = Some(name.to_owned());
+    }
+
+    #[get_descriptor]
+    fn get(
+        slf: PyRef<Self>,
+        py: Python,
+        obj: Option<&PyAny>,
+        _objtype: Option<&PyType>,
+    ) -> PyResult<PyObject> {
+        match (obj, &slf.name) {
+            (Some(_), Some(name)) => Ok(format!("field {}", name).into_py(py)),
+            _ => Ok(slf.into_py(py)),
+        }
+    }
+}
+
+#[test]
+fn descriptor_set_name() {
+    Python::with_gil(|py| {
+        let named_field = py.get_type::<NamedField>();
+        let source = pyo3::indoc::indoc!(
+            r#"
+class Record:
+    title = NamedField()
+assert Record.title.name == "title"
+assert Record().title == "field title"
+assert NamedField().name is None
+"#
+        );
+        let globals = PyModule::import(py, "__main__").unwrap().dict();
+        globals.set_item("NamedField", named_field).unwrap();
+        py.run(source, Some(globals), None)
+            .map_err(|e| e.print(py))
+            .unwrap();
+    });
+}
+
+#[test]
+fn descriptor_methods() {
+    Python::with_gil(|py| {
+        let non_negative = py.get_type::<NonNegative>();
+        let source = pyo3::indoc::indoc!(
+            r#"
+class Account:
+    balance = NonNegative("balance")
+a = Account()
+a.balance = 5
+assert a.balance == 5
+assert a._balance == 5
+assert isinstance(Account.balance, NonNegative)
+try:
+    a.balance = -1
+    assert False
+except ValueError:
+    assert a.balance == 5
+try:
+    a.balance = "five"
+    assert False
+except TypeError:
+    pass
+del a.balance
+assert not hasattr(a, "_balance")
+"#
+        );
+        let globals = PyModule::import(py, "__main__").unwrap().dict();
+        globals.set_item("NonNegative", non_negative).unwrap();
+        py.run(source, Some(globals), None)
+            .map_err(|e| e.print(py))
+            .unwrap();
+    });
+}
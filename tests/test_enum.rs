@@ -0,0 +1,169 @@
+use pyo3::prelude::*;
+use pyo3::types::IntoPyDict;
+
+mod common;
+
+/// A geometric shape.
+#[pyclass]
+#[derive(Clone, Debug, PartialEq)]
+enum Shape {
+    /// A circle around the origin.
+    Circle {
+        radius: f64,
+    },
+    Rectangle {
+        width: f64,
+        height: f64,
+    },
+    Point(i32, i32),
+    Empty,
+}
+
+#[pymethods]
+impl Shape {
+    fn area(&self) -> f64 {
+        match self {
+            Shape::Circle { radius } => std::f64::consts::PI * radius * radius,
+            Shape::Rectangle { width, height } => width * height,
+            Shape::Point(..) | Shape::Empty => 0.0,
+        }
+    }
+
+    fn clear(&mut self) {
+        *self = Shape::Empty;
+    }
+}
+
+#[test]
+fn enum_variant_classes() {
+    Python::with_gil(|py| {
+        let shape = py.get_type::<Shape>();
+        let d = [("Shape", shape)].into_py_dict(py);
+        py_assert!(py, *d, "issubclass(Shape.Circle, Shape)");
+        py_assert!(py, *d, "Shape.Circle.__name__ == 'Circle'");
+        py_assert!(
+            py,
+            *d,
+            "Shape.Circle.__doc__ == 'A circle around the origin.'"
+        );
+        py_assert!(py, *d, "Shape.Rectangle(2.0, height=3.0).area() == 6.0");
+        py_assert!(py, *d, "Shape.Rectangle(2.0, 3.0).variant == 'Rectangle'");
+        py_assert!(py, *d, "Shape.Circle(radius=1.5).radius == 1.5");
+        py_assert!(py, *d, "Shape.Point(1, 2)._0 == 1");
+        py_assert!(py, *d, "Shape.Point(1, 2)._1 == 2");
+        py_assert!(py, *d, "isinstance(Shape.Empty(), Shape)");
+        py_expect_exception!(py, *d, "Shape.Point(1)", PyTypeError);
+    });
+}
+
+#[test]
+fn enum_conversions() {
+    Python::with_gil(|py| {
+        let point = Shape::Point(3, 4).into_py(py);
+        let d = [
+            ("Shape", py.get_type::<Shape>().as_ref()),
+            ("point", point.as_ref(py)),
+        ]
+        .into_py_dict(py);
+        py_assert!(py, *d, "isinstance(point, Shape.Point)");
+        py_assert!(py, *d, "not isinstance(point, Shape.Circle)");
+        py_assert!(py, *d, "point.variant == 'Point'");
+        py_assert!(py, *d, "(point._0, point._1) == (3, 4)");
+
+        assert_eq!(point.extract::<Shape>(py).unwrap(), Shape::Point(3, 4));
+        let circle = py.eval("Shape.Circle(2.0)", None, Some(d)).unwrap();
+        let cell: &PyCell<Shape> = circle.downcast().unwrap();
+        assert_eq!(*cell.borrow(), Shape::Circle { radius: 2.0 });
+    });
+}
+
+#[test]
+fn enum_variant_changed() {
+    Python::with_gil(|py| {
+        let circle = Shape::Circle { radius: 2.0 }.into_py(py);
+        let d = [("circle", circle.as_ref(py))].into_py_dict(py);
+        pyo3::py_run!(py, *d, "circle.clear()");
+        py_assert!(py, *d, "circle.variant == 'Empty'");
+        py_expect_exception!(py, *d, "circle.radius", PyAttributeError);
+        assert_eq!(circle.extract::<Shape>(py).unwrap(), Shape::Empty);
+    });
+}
+
+/// Converting by reference doesn't need the enum to implement `Clone`.
+#[pyclass]
+enum Message {
+    Text(String),
+    Quit,
+}
+
+#[test]
+fn enum_to_object() {
+    Python::with_gil(|py| {
+        let circle = Shape::Circle { radius: 2.0 };
+        let obj = circle.to_object(py);
+        let d = [
+            ("Shape", py.get_type::<Shape>().as_ref()),
+            ("obj", obj.as_ref(py)),
+        ]
+        .into_py_dict(py);
+        py_assert!(py, *d, "isinstance(obj, Shape.Circle)");
+        py_assert!(py, *d, "obj.radius == 2.0");
+        assert_eq!(obj.extract::<Shape>(py).unwrap(), circle);
+
+        let text = Message::Text("hello".to_string()).to_object(py);
+        let quit = Message::Quit.to_object(py);
+        let d = [
+            ("Message", py.get_type::<Message>().as_ref()),
+            ("text", text.as_ref(py)),
+            ("quit", quit.as_ref(py)),
+        ]
+        .into_py_dict(py);
+        py_assert!(py, *d, "isinstance(text, Message.Text)");
+        py_assert!(py, *d, "text._0 == 'hello'");
+        py_assert!(py, *d, "isinstance(quit, Message.Quit)");
+    });
+}
+
+mod visibility {
+    use pyo3::prelude::*;
+
+    #[pyclass]
+    pub enum Public {
+        A(u8),
+    }
+
+    #[pyclass]
+    pub(crate) enum Crate {
+        B(u8),
+    }
+
+    #[pyclass]
+    pub(super) enum Super {
+        C(u8),
+    }
+
+    #[pyclass]
+    pub(in crate::visibility) enum InPath {
+        D(u8),
+    }
+
+    pub fn variants(py: Python) -> Vec<PyObject> {
+        vec![
+            Public::A(1).into_py(py),
+            Crate::B(2).into_py(py),
+            Super::C(3).into_py(py),
+            InPath::D(4).into_py(py),
+        ]
+    }
+}
+
+#[test]
+fn enum_visibility() {
+    Python::with_gil(|py| {
+        let values: Vec<u8> = visibility::variants(py)
+            .iter()
+            .map(|obj| obj.getattr(py, "_0").unwrap().extract(py).unwrap())
+            .collect();
+        assert_eq!(values, vec![1, 2, 3, 4]);
+    });
+}
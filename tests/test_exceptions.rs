@@ -92,3 +92,104 @@ fn test_exception_nosegfault() {
     assert!(io_err().is_err());
     assert!(parse_int().is_err());
 }
+
+#[derive(PyError)]
+#[pyerror(base = "exceptions::PyValueError", module = "mymodule")]
+struct InvalidValue(String);
+
+#[derive(PyError)]
+#[pyerror(
+    module = "mymodule",
+    message = "format!(\"{} is out of range {}..{}\", self.value, self.min, self.max)"
+)]
+struct OutOfRange {
+    value: i32,
+    min: i32,
+    max: i32,
+}
+
+#[derive(PyError)]
+#[pyerror(module = "mymodule")]
+struct Unit;
+
+#[pyfunction]
+fn check_range(value: i32) -> Result<i32, OutOfRange> {
+    if (0..10).contains(&value) {
+        Ok(value)
+    } else {
+        Err(OutOfRange {
+            value,
+            min: 0,
+            max: 10,
+        })
+    }
+}
+
+#[test]
+fn test_derive_py_error() {
+    Python::with_gil(|py| {
+        let err = PyErr::from(InvalidValue("oops".to_string()));
+        assert!(err.is_instance::<InvalidValue>(py));
+        assert!(err.is_instance::<exceptions::PyValueError>(py));
+        assert_eq!(err.to_string(), "InvalidValue: oops");
+
+        let err = PyErr::from(Unit);
+        assert!(err.is_instance::<Unit>(py));
+        assert!(err.is_instance::<exceptions::PyException>(py));
+        assert!(!err.is_instance::<exceptions::PyValueError>(py));
+
+        let check_range = wrap_pyfunction!(check_range)(py).unwrap();
+        let out_of_range = py.get_type::<OutOfRange>();
+        py_run!(
+            py,
+            check_range out_of_range,
+            r#"
+            assert out_of_range.__module__ == "mymodule"
+            assert out_of_range.__name__ == "OutOfRange"
+            assert check_range(3) == 3
+            try:
+                check_range(12)
+            except out_of_range as e:
+                assert str(e) == "12 is out of range 0..10"
+            else:
+                assert False
+            "#
+        );
+    });
+}
+
+#[pyfunction]
+fn raise_chained(py: Python) -> PyResult<()> {
+    let cause = exceptions::PyKeyError::new_err("missing");
+    Err(exceptions::PyValueError::new_err("lookup failed").with_cause(py, cause))
+}
+
+#[test]
+fn test_exception_cause() {
+    Python::with_gil(|py| {
+        let raise_chained = wrap_pyfunction!(raise_chained)(py).unwrap();
+        py_run!(
+            py,
+            raise_chained,
+            r#"
+            try:
+                raise_chained()
+            except ValueError as e:
+                assert isinstance(e.__cause__, KeyError)
+                assert e.__cause__.args == ("missing",)
+                assert e.__suppress_context__
+            else:
+                assert False
+            "#
+        );
+
+        let err = py.run("raise ValueError('outer')", None, None).unwrap_err();
+        let instance = err.instance(py);
+        assert!(instance.cause().is_none());
+        let cause = exceptions::PyKeyError::new_err("inner");
+        instance.set_cause(Some(cause.instance(py)));
+        assert!(std::error::Error::source(instance).is_some());
+        instance.set_cause(None);
+        assert!(instance.cause().is_none());
+    });
+}
@@ -0,0 +1,246 @@
+#![cfg(not(Py_LIMITED_API))]
+
+use pyo3::class::finalize::PyFinalizerProtocol;
+use pyo3::class::{PyGCProtocol, PyTraverseError, PyVisit};
+use pyo3::prelude::*;
+use pyo3::py_run;
+use pyo3::AsPyPointer;
+use std::sync::atomic::{AtomicBool, AtomicPtr, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+mod common;
+
+struct DropCheck(Arc<AtomicBool>);
+
+impl Drop for DropCheck {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+#[pyclass(finalizer)]
+struct Finalized {
+    finalized: Arc<AtomicUsize>,
+}
+
+impl PyFinalizerProtocol for Finalized {
+    fn __del__(&mut self) {
+        self.finalized.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[test]
+fn finalizer_called_once() {
+    let finalized = Arc::new(AtomicUsize::new(0));
+    Python::with_gil(|py| {
+        let inst = Py::new(
+            py,
+            Finalized {
+                finalized: Arc::clone(&finalized),
+            },
+        )
+        .unwrap();
+        assert_eq!(finalized.load(Ordering::Relaxed), 0);
+        drop(inst);
+    });
+    assert_eq!(finalized.load(Ordering::Relaxed), 1);
+}
+
+static SUBCLASS_FINALIZED: AtomicUsize = AtomicUsize::new(0);
+
+#[pyclass(finalizer, subclass)]
+struct FinalizedBase {}
+
+#[pymethods]
+impl FinalizedBase {
+    #[new]
+    fn new() -> Self {
+        FinalizedBase {}
+    }
+}
+
+impl PyFinalizerProtocol for FinalizedBase {
+    fn __del__(&mut self) {
+        SUBCLASS_FINALIZED.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[test]
+fn finalizer_called_once_for_python_subclass() {
+    Python::with_gil(|py| {
+        let base = py.get_type::<FinalizedBase>();
+        // The subclass's dealloc runs the finalizer, and then calls the base dealloc
+        py_run!(
+            py,
+            base,
+            r#"
+class Sub(base):
+    pass
+sub = Sub()
+del sub
+"#
+        );
+    });
+    assert_eq!(SUBCLASS_FINALIZED.load(Ordering::Relaxed), 1);
+}
+
+#[allow(dead_code)]
+#[pyclass(gc, finalizer)]
+struct FinalizedCycle {
+    self_ref: Option<PyObject>,
+    finalized: Arc<AtomicUsize>,
+    dropped: DropCheck,
+}
+
+#[pyproto]
+impl PyGCProtocol for FinalizedCycle {
+    fn __traverse__(&self, visit: PyVisit) -> Result<(), PyTraverseError> {
+        if let Some(obj) = &self.self_ref {
+            visit.call(obj)?;
+        }
+        Ok(())
+    }
+
+    fn __clear__(&mut self) {
+        self.self_ref = None;
+    }
+}
+
+impl PyFinalizerProtocol for FinalizedCycle {
+    fn __del__(&mut self) {
+        // The finalizer runs before the cycle is broken
+        assert!(self.self_ref.is_some());
+        self.finalized.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[test]
+fn finalizer_called_once_by_gc() {
+    let finalized = Arc::new(AtomicUsize::new(0));
+    let dropped = Arc::new(AtomicBool::new(false));
+    Python::with_gil(|py| {
+        let inst = Py::new(
+            py,
+            FinalizedCycle {
+                self_ref: None,
+                finalized: Arc::clone(&finalized),
+                dropped: DropCheck(Arc::clone(&dropped)),
+            },
+        )
+        .unwrap();
+        inst.borrow_mut(py).self_ref = Some(inst.clone_ref(py).into_py(py));
+        drop(inst);
+        assert_eq!(finalized.load(Ordering::Relaxed), 0);
+
+        py.run("import gc; gc.collect()", None, None).unwrap();
+        assert_eq!(finalized.load(Ordering::Relaxed), 1);
+        assert!(dropped.load(Ordering::Relaxed));
+    });
+    assert_eq!(finalized.load(Ordering::Relaxed), 1);
+}
+
+static TO_RESURRECT: AtomicPtr<pyo3::ffi::PyObject> = AtomicPtr::new(std::ptr::null_mut());
+
+#[allow(dead_code)]
+#[pyclass(gc, finalizer)]
+struct Resurrecting {
+    finalized: Arc<AtomicUsize>,
+    dropped: DropCheck,
+}
+
+#[pyproto]
+impl PyGCProtocol for Resurrecting {
+    fn __traverse__(&self, _visit: PyVisit) -> Result<(), PyTraverseError> {
+        Ok(())
+    }
+
+    fn __clear__(&mut self) {}
+}
+
+impl PyFinalizerProtocol for Resurrecting {
+    fn __del__(&mut self) {
+        self.finalized.fetch_add(1, Ordering::Relaxed);
+        // Resurrect the object by taking a new reference to it
+        let obj = TO_RESURRECT.swap(std::ptr::null_mut(), Ordering::SeqCst);
+        if !obj.is_null() {
+            unsafe { pyo3::ffi::Py_INCREF(obj) };
+        }
+    }
+}
+
+#[test]
+fn finalizer_resurrection() {
+    let finalized = Arc::new(AtomicUsize::new(0));
+    let dropped = Arc::new(AtomicBool::new(false));
+    Python::with_gil(|py| {
+        let inst = Py::new(
+            py,
+            Resurrecting {
+                finalized: Arc::clone(&finalized),
+                dropped: DropCheck(Arc::clone(&dropped)),
+            },
+        )
+        .unwrap();
+        let ptr = inst.as_ptr();
+        TO_RESURRECT.store(ptr, Ordering::SeqCst);
+        drop(inst);
+        // The finalizer resurrected the object, so it must not have been deallocated
+        assert_eq!(finalized.load(Ordering::Relaxed), 1);
+        assert!(!dropped.load(Ordering::Relaxed));
+
+        // Take ownership of the reference taken by the finalizer
+        let resurrected: PyObject = unsafe { Py::from_owned_ptr(py, ptr) };
+        assert!(resurrected
+            .as_ref(py)
+            .is_instance::<Resurrecting>()
+            .unwrap());
+        py.run("import gc; gc.collect()", None, None).unwrap();
+        assert!(!dropped.load(Ordering::Relaxed));
+
+        // The object is deallocated once released again, without being finalized a second time
+        drop(resurrected);
+        assert!(dropped.load(Ordering::Relaxed));
+        assert_eq!(finalized.load(Ordering::Relaxed), 1);
+    });
+}
+
+#[pyclass(finalizer)]
+struct PanickingFinalizer {}
+
+impl PyFinalizerProtocol for PanickingFinalizer {
+    fn __del__(&mut self) {
+        panic!("finalizer failed");
+    }
+}
+
+#[test]
+fn finalizer_panic_is_unraisable() {
+    Python::with_gil(|py| {
+        let inst = Py::new(py, PanickingFinalizer {}).unwrap();
+        let sys = py.import("sys").unwrap();
+        let hook = sys.getattr("unraisablehook").unwrap();
+        // Only keep the exception type, as keeping `u.object` would resurrect the instance
+        py_run!(
+            py,
+            sys,
+            "sys.unraisablehook = lambda u, sys=sys: setattr(sys, 'unraisable_type', u.exc_type)"
+        );
+        // A pending exception is preserved across the finalizer
+        pyo3::exceptions::PyValueError::new_err("pending").restore(py);
+        drop(inst);
+        let pending = PyErr::fetch(py);
+        sys.setattr("unraisablehook", hook).unwrap();
+
+        assert!(pending.is_instance::<pyo3::exceptions::PyValueError>(py));
+        let exc_type = sys.getattr("unraisable_type").unwrap();
+        assert_eq!(
+            exc_type
+                .getattr("__name__")
+                .unwrap()
+                .extract::<&str>()
+                .unwrap(),
+            "PanicException"
+        );
+        sys.delattr("unraisable_type").unwrap();
+    });
+}
@@ -146,6 +146,87 @@ fn gc_integration2() {
     py_run!(py, inst, "import gc; assert inst in gc.get_objects()");
 }
 
+#[allow(dead_code)]
+#[pyclass(gc)]
+struct CycleA {
+    b: Option<Py<CycleB>>,
+    dropped: TestDropCall,
+}
+
+#[pyproto]
+impl PyGCProtocol for CycleA {
+    fn __traverse__(&self, visit: PyVisit) -> Result<(), PyTraverseError> {
+        if let Some(b) = &self.b {
+            visit.call(b)?;
+        }
+        Ok(())
+    }
+
+    fn __clear__(&mut self) {
+        self.b = None;
+    }
+}
+
+#[allow(dead_code)]
+#[pyclass(gc)]
+struct CycleB {
+    a: Option<Py<CycleA>>,
+    dropped: TestDropCall,
+}
+
+#[pyproto]
+impl PyGCProtocol for CycleB {
+    fn __traverse__(&self, visit: PyVisit) -> Result<(), PyTraverseError> {
+        if let Some(a) = &self.a {
+            visit.call(a)?;
+        }
+        Ok(())
+    }
+
+    fn __clear__(&mut self) {
+        self.a = None;
+    }
+}
+
+#[test]
+fn gc_collects_cycle_between_classes() {
+    let a_dropped = Arc::new(AtomicBool::new(false));
+    let b_dropped = Arc::new(AtomicBool::new(false));
+
+    Python::with_gil(|py| {
+        let a = Py::new(
+            py,
+            CycleA {
+                b: None,
+                dropped: TestDropCall {
+                    drop_called: Arc::clone(&a_dropped),
+                },
+            },
+        )
+        .unwrap();
+        let b = Py::new(
+            py,
+            CycleB {
+                a: Some(a.clone_ref(py)),
+                dropped: TestDropCall {
+                    drop_called: Arc::clone(&b_dropped),
+                },
+            },
+        )
+        .unwrap();
+        a.borrow_mut(py).b = Some(b);
+        drop(a);
+
+        // The objects only reference each other, so only the cyclic GC can free them
+        assert!(!a_dropped.load(Ordering::Relaxed));
+        assert!(!b_dropped.load(Ordering::Relaxed));
+        py.run("import gc; gc.collect()", None, None).unwrap();
+    });
+
+    assert!(a_dropped.load(Ordering::Relaxed));
+    assert!(b_dropped.load(Ordering::Relaxed));
+}
+
 #[pyclass(weakref, subclass)]
 struct WeakRefSupport {}
 
@@ -162,6 +243,26 @@ fn weakref_support() {
     );
 }
 
+#[test]
+#[cfg_attr(all(Py_LIMITED_API, not(Py_3_9)), ignore)]
+fn weakref_cleared_on_drop() {
+    Python::with_gil(|py| {
+        let inst = Py::new(py, WeakRefSupport {}).unwrap();
+        let weakref = py
+            .import("weakref")
+            .unwrap()
+            .call_method1("ref", (inst.clone_ref(py),))
+            .unwrap();
+        {
+            // The referent returned by the weakref is owned by this pool
+            let _pool = unsafe { py.new_pool() };
+            assert_eq!(weakref.call0().unwrap().as_ptr(), inst.as_ptr());
+        }
+        drop(inst);
+        assert!(weakref.call0().unwrap().is_none());
+    });
+}
+
 // If the base class has weakref support, child class also has weakref.
 #[pyclass(extends=WeakRefSupport)]
 struct InheritWeakRef {
@@ -158,3 +158,37 @@ fn tuple_struct_getter_setter() {
     py_run!(py, inst, "inst.num = 20");
     py_assert!(py, inst, "inst.num == 20");
 }
+
+#[pyclass]
+struct Point(
+    #[pyo3(get, set)] f64,
+    #[pyo3(get, set, name = "y")] f64,
+    #[pyo3(get)] String,
+);
+
+#[pyclass]
+struct RenamedField {
+    #[pyo3(get, set, name = "value")]
+    inner: i32,
+}
+
+#[test]
+fn tuple_struct_field_descriptors() {
+    Python::with_gil(|py| {
+        let point = Py::new(py, Point(1.0, 2.0, "p".to_string())).unwrap();
+        py_assert!(
+            py,
+            point,
+            "(point._0, point.y, point._2) == (1.0, 2.0, 'p')"
+        );
+        py_run!(py, point, "point._0 = 3.0; point.y = 4.0");
+        py_assert!(py, point, "(point._0, point.y) == (3.0, 4.0)");
+        py_assert!(py, point, "not hasattr(point, '_1')");
+        py_expect_exception!(py, point, "point._2 = 'q'", PyAttributeError);
+
+        let renamed = Py::new(py, RenamedField { inner: 1 }).unwrap();
+        py_run!(py, renamed, "renamed.value = 5");
+        assert_eq!(renamed.borrow(py).inner, 5);
+        py_assert!(py, renamed, "not hasattr(renamed, 'inner')");
+    });
+}
@@ -1,5 +1,5 @@
 use pyo3::prelude::*;
-use pyo3::py_run;
+use pyo3::{py_run, AsPyPointer};
 
 use pyo3::types::IntoPyDict;
 
@@ -102,6 +102,24 @@ fn mutation_fails() {
     assert_eq!(&e.to_string(), "RuntimeError: Already borrowed")
 }
 
+#[test]
+fn release_after_into_super() {
+    Python::with_gil(|py| {
+        let obj = PyCell::new(py, SubClass::new()).unwrap();
+        let mut base = obj.borrow_mut().into_super();
+        base.val1 = 20;
+        let released = base.release();
+        assert_eq!(released.as_ptr(), obj.as_ptr());
+        assert_eq!(released.borrow().val1, 20);
+
+        let mut sub = obj.try_borrow_mut().unwrap();
+        sub.val2 = 6;
+        assert_eq!(sub.as_ref().val1, 20);
+        drop(sub);
+        py_run!(py, obj, "assert obj.val1 == 20 and obj.val2 == 6");
+    });
+}
+
 #[pyclass(subclass)]
 struct BaseClassWithResult {
     _val: usize,
@@ -149,11 +167,60 @@ except Exception as e:
     );
 }
 
+#[pyclass(subclass, abstract)]
+struct AbstractBase {
+    #[pyo3(get)]
+    value: usize,
+}
+
+#[pymethods]
+impl AbstractBase {
+    #[new]
+    fn new() -> Self {
+        AbstractBase { value: 10 }
+    }
+}
+
+#[pyclass(extends=AbstractBase)]
+struct ConcreteClass {}
+
+#[pymethods]
+impl ConcreteClass {
+    #[new]
+    fn new() -> (Self, AbstractBase) {
+        (ConcreteClass {}, AbstractBase::new())
+    }
+}
+
+#[test]
+fn abstract_class() {
+    Python::with_gil(|py| {
+        let base = py.get_type::<AbstractBase>();
+        let concrete = py.get_type::<ConcreteClass>();
+        py_assert!(py, concrete, "concrete().value == 10");
+        py_run!(
+            py,
+            base,
+            r#"
+class PySub(base):
+    pass
+
+assert PySub().value == 10
+try:
+    base()
+    assert False, "abstract class was instantiated"
+except TypeError as e:
+    assert str(e) == "cannot instantiate abstract class"
+"#
+        );
+    });
+}
+
 // Subclassing builtin types is not allowed in the LIMITED API.
 #[cfg(not(Py_LIMITED_API))]
 mod inheriting_native_type {
     use super::*;
-    use pyo3::exceptions::PyException;
+    use pyo3::exceptions::{PyClassException, PyException};
     use pyo3::types::{IntoPyDict, PyDict, PySet};
 
     #[pyclass(extends=PySet)]
@@ -252,6 +319,60 @@ mod inheriting_native_type {
             )
         })
     }
+
+    #[pyclass(extends=PyException)]
+    struct HttpError {
+        #[pyo3(get)]
+        status: u16,
+        #[pyo3(get)]
+        url: String,
+    }
+
+    #[pymethods]
+    impl HttpError {
+        #[new]
+        fn new(status: u16, url: String) -> Self {
+            HttpError { status, url }
+        }
+    }
+
+    #[pyfunction]
+    fn fetch(url: String) -> PyResult<()> {
+        Err(HttpError::new_err((404, url)))
+    }
+
+    #[test]
+    fn custom_exception_with_fields() {
+        Python::with_gil(|py| {
+            let err = HttpError::new_err((404, "https://example.com"));
+            assert!(err.is_instance::<HttpError>(py));
+            assert!(err.is_instance::<PyException>(py));
+            let instance: &PyCell<HttpError> = err.instance(py).downcast().unwrap();
+            assert_eq!(instance.borrow().status, 404);
+            assert_eq!(instance.borrow().url, "https://example.com");
+            assert_eq!(err.to_string(), "HttpError: (404, 'https://example.com')");
+
+            let roundtrip = PyErr::from_instance(instance);
+            assert!(roundtrip.is_instance::<HttpError>(py));
+
+            let fetch = pyo3::wrap_pyfunction!(fetch)(py).unwrap();
+            let cls = py.get_type::<HttpError>();
+            py_run!(
+                py,
+                fetch cls,
+                r#"
+                    try:
+                        fetch("https://example.com/missing")
+                    except cls as e:
+                        assert e.status == 404
+                        assert e.url == "https://example.com/missing"
+                        assert e.args == (404, "https://example.com/missing")
+                    else:
+                        assert False
+                "#
+            );
+        })
+    }
 }
 
 #[pyclass(subclass)]
@@ -254,6 +254,21 @@ impl MethArgs {
     fn args_as_vec(&self, args: Vec<i32>) -> i32 {
         args.iter().sum()
     }
+
+    #[args(a, "/", b)]
+    fn get_pos_only(&self, a: i32, b: i32) -> i32 {
+        a + b
+    }
+
+    #[args(a, b, "/")]
+    fn get_all_pos_only(&self, a: i32, b: i32) -> i32 {
+        a + b
+    }
+
+    #[args(a, "/", b = 2, "*", c = 3)]
+    fn get_pos_only_with_defaults(&self, a: i32, b: i32, c: i32) -> i32 {
+        a + b + c
+    }
 }
 
 #[test]
@@ -408,6 +423,43 @@ fn meth_args() {
     py_expect_exception!(py, inst, "inst.get_pos_kw(1,2)", PyTypeError);
 
     py_run!(py, inst, "assert inst.args_as_vec(1,2,3) == 6");
+
+    py_run!(py, inst, "assert inst.get_pos_only(1, 2) == 3");
+    py_run!(py, inst, "assert inst.get_pos_only(1, b=2) == 3");
+    py_expect_exception!(
+        py,
+        inst,
+        "inst.get_pos_only(a=1, b=2)",
+        PyTypeError,
+        "MethArgs.get_pos_only() got some positional-only arguments passed as keyword arguments: 'a'"
+    );
+
+    py_run!(py, inst, "assert inst.get_all_pos_only(1, 2) == 3");
+    py_expect_exception!(py, inst, "inst.get_all_pos_only(1, b=2)", PyTypeError);
+
+    py_run!(py, inst, "assert inst.get_pos_only_with_defaults(1) == 6");
+    py_run!(
+        py,
+        inst,
+        "assert inst.get_pos_only_with_defaults(1, 1) == 5"
+    );
+    py_run!(
+        py,
+        inst,
+        "assert inst.get_pos_only_with_defaults(1, b=1, c=1) == 3"
+    );
+    py_expect_exception!(
+        py,
+        inst,
+        "inst.get_pos_only_with_defaults(1, 2, 3)",
+        PyTypeError
+    );
+    py_expect_exception!(
+        py,
+        inst,
+        "inst.get_pos_only_with_defaults(a=1)",
+        PyTypeError
+    );
 }
 
 #[pyclass]
@@ -819,3 +871,139 @@ issue_1506!(
         }
     }
 );
+
+/// A future which is pending once before completing, like an `await` on a slow operation.
+struct YieldOnce(bool);
+
+impl std::future::Future for YieldOnce {
+    type Output = ();
+
+    fn poll(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context,
+    ) -> std::task::Poll<()> {
+        if self.0 {
+            std::task::Poll::Ready(())
+        } else {
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            std::task::Poll::Pending
+        }
+    }
+}
+
+#[pyclass]
+struct AsyncMethods {
+    value: i32,
+}
+
+#[pymethods]
+impl AsyncMethods {
+    async fn add(slf: Py<Self>, other: i32) -> i32 {
+        YieldOnce(false).await;
+        Python::with_gil(|py| slf.borrow(py).value + other)
+    }
+
+    #[classmethod]
+    async fn class_name(cls: Py<PyType>) -> PyResult<String> {
+        YieldOnce(false).await;
+        Python::with_gil(|py| Ok(cls.as_ref(py).name()?.to_owned()))
+    }
+
+    #[staticmethod]
+    async fn fail(message: String) -> PyResult<()> {
+        Err(pyo3::exceptions::PyValueError::new_err(message))
+    }
+}
+
+#[test]
+fn async_methods() {
+    Python::with_gil(|py| {
+        let obj = PyCell::new(py, AsyncMethods { value: 1 }).unwrap();
+        py_run!(
+            py,
+            obj,
+            r#"
+            import asyncio
+
+            assert asyncio.iscoroutine(obj.add(2))
+            assert asyncio.run(obj.add(2)) == 3
+            assert asyncio.new_event_loop().run_until_complete(obj.add(3)) == 4
+            assert asyncio.run(type(obj).class_name()) == "AsyncMethods"
+
+            async def main(obj=obj):
+                import asyncio
+                return await asyncio.gather(obj.add(1), obj.add(2))
+            assert asyncio.run(main()) == [2, 3]
+
+            try:
+                asyncio.run(obj.fail("oops"))
+            except ValueError as e:
+                assert str(e) == "oops"
+            else:
+                assert False
+            "#
+        );
+    });
+}
+
+#[pyclass(subclass)]
+struct InitSubclass {}
+
+#[pymethods]
+impl InitSubclass {
+    #[args(tag = "None", kwargs = "**")]
+    fn __init_subclass__(
+        cls: &PyType,
+        tag: Option<String>,
+        kwargs: Option<&PyDict>,
+    ) -> PyResult<()> {
+        cls.setattr("tag", tag.as_deref().unwrap_or(cls.name()?))?;
+        if let Some(kwargs) = kwargs {
+            // Keyword arguments left in `kwargs` are passed on to the base class
+            kwargs.del_item("ignored").ok();
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn init_subclass() {
+    Python::with_gil(|py| {
+        let base = py.get_type::<InitSubclass>();
+        py_run!(
+            py,
+            base,
+            r#"
+            class Plain(base):
+                pass
+            assert Plain.tag == "Plain"
+
+            class Tagged(base, tag="custom", ignored=True):
+                pass
+            assert Tagged.tag == "custom"
+
+            class GrandChild(Tagged):
+                pass
+            assert GrandChild.tag == "GrandChild"
+
+            class Mixin:
+                def __init_subclass__(cls, extra=None, **kwargs):
+                    super().__init_subclass__(**kwargs)
+                    cls.extra = extra
+
+            class Both(base, Mixin, extra=1):
+                pass
+            assert (Both.tag, Both.extra) == ("Both", 1)
+
+            try:
+                class Unknown(base, unknown=1):
+                    pass
+            except TypeError:
+                pass
+            else:
+                assert False
+            "#
+        );
+    });
+}
@@ -93,6 +93,11 @@ fn test_module_with_functions() {
     py_assert!(py, *d, "module_with_functions.foo == 'bar'");
     py_assert!(py, *d, "module_with_functions.AnonClass != None");
     py_assert!(py, *d, "module_with_functions.LocatedClass != None");
+    py_assert!(
+        py,
+        *d,
+        "{'AnonClass', 'ValueClass', 'LocatedClass'} <= set(module_with_functions.__all__)"
+    );
     py_assert!(
         py,
         *d,
@@ -414,3 +419,32 @@ fn test_module_functions_with_module() {
                         == ('module_with_functions_with_module', 1, 2)"
     );
 }
+
+#[pymodule]
+fn module_with_init(_py: Python, m: &PyModule) -> PyResult<()> {
+    #[pymodule_init]
+    fn init(m: &PyModule) -> PyResult<()> {
+        let names: Vec<String> = m
+            .dict()
+            .keys()
+            .iter()
+            .map(|key| key.extract::<String>())
+            .collect::<PyResult<_>>()?;
+        let mut public: Vec<String> = names.into_iter().filter(|n| !n.starts_with('_')).collect();
+        public.sort();
+        m.setattr("__all__", public)
+    }
+
+    m.add_function(wrap_pyfunction!(double, m)?)?;
+    m.add("foo", "bar")?;
+    // Unlike `add`, `setattr` doesn't add the name to `__all__`
+    m.setattr("baz", 1)
+}
+
+#[test]
+fn test_module_with_init() {
+    Python::with_gil(|py| {
+        let m = pyo3::wrap_pymodule!(module_with_init)(py);
+        py_assert!(py, m, "m.__all__ == ['baz', 'double', 'foo']");
+    });
+}
@@ -0,0 +1,149 @@
+use pyo3::class::pickle::PyPickle;
+use pyo3::exceptions::PyTypeError;
+use pyo3::prelude::*;
+use pyo3::py_run;
+use pyo3::types::PyDict;
+
+mod common;
+
+#[pyclass(pickle, subclass, module = "test_pickle_module")]
+#[derive(Clone, Debug, PartialEq)]
+struct Record {
+    #[pyo3(get)]
+    name: String,
+    #[pyo3(get)]
+    scores: Vec<u32>,
+    #[pyo3(get)]
+    r#type: Option<String>,
+}
+
+#[pymethods]
+impl Record {
+    // Unpickling must not call the constructor, which requires an argument
+    #[new]
+    fn new(name: String) -> Self {
+        Record {
+            name,
+            scores: Vec::new(),
+            r#type: None,
+        }
+    }
+}
+
+#[pyclass(pickle, module = "test_pickle_module")]
+struct Pair(i32, f64);
+
+fn add_pickle_module(py: Python) -> PyResult<()> {
+    let module = PyModule::new(py, "test_pickle_module")?;
+    module.add_class::<Record>()?;
+    module.add_class::<Pair>()?;
+    py.import("sys")?
+        .getattr("modules")?
+        .set_item("test_pickle_module", module)
+}
+
+#[test]
+#[cfg_attr(all(Py_LIMITED_API, not(Py_3_10)), ignore)]
+fn pickle_round_trip() {
+    Python::with_gil(|py| {
+        add_pickle_module(py).unwrap();
+        let record = PyCell::new(
+            py,
+            Record {
+                name: "ferris".to_owned(),
+                scores: vec![3, 1, 4],
+                r#type: Some("crab".to_owned()),
+            },
+        )
+        .unwrap();
+        let pair = PyCell::new(py, Pair(-1, 0.5)).unwrap();
+        py_run!(
+            py,
+            record pair,
+            r#"
+            import pickle
+            assert record.__getstate__() == {"name": "ferris", "scores": [3, 1, 4], "type": "crab"}
+            for protocol in range(pickle.HIGHEST_PROTOCOL + 1):
+                copy = pickle.loads(pickle.dumps(record, protocol))
+                assert type(copy) is type(record)
+                assert copy is not record
+                assert (copy.name, copy.scores, copy.type) == ("ferris", [3, 1, 4], "crab")
+
+            assert pair.__getstate__() == {"0": -1, "1": 0.5}
+            assert pickle.loads(pickle.dumps(pair)).__getstate__() == {"0": -1, "1": 0.5}
+            "#
+        );
+    });
+}
+
+#[test]
+fn copy_python_subclass() {
+    Python::with_gil(|py| {
+        let record = py.get_type::<Record>();
+        py_run!(
+            py,
+            record,
+            r#"
+            import copy
+            class SubRecord(record):
+                pass
+
+            original = SubRecord("ferris")
+            duplicate = copy.deepcopy(original)
+            assert type(duplicate) is SubRecord
+            assert duplicate.name == "ferris"
+            "#
+        );
+    });
+}
+
+#[test]
+fn setstate() {
+    Python::with_gil(|py| {
+        let record = PyCell::new(py, Record::new("before".to_owned())).unwrap();
+        py_run!(
+            py,
+            record,
+            r#"
+            record.__setstate__({"name": "after", "scores": [1], "type": None})
+            assert (record.name, record.scores, record.type) == ("after", [1], None)
+            "#
+        );
+        py_expect_exception!(py, record, "record.__setstate__([])", PyTypeError);
+        py_expect_exception!(
+            py,
+            record,
+            "record.__setstate__({'name': 'x'})",
+            PyTypeError
+        );
+        py_expect_exception!(
+            py,
+            record,
+            "record.__setstate__({'name': 1, 'scores': [], 'type': None})",
+            PyTypeError
+        );
+        // A failed `__setstate__` leaves the instance unchanged
+        assert_eq!(record.borrow().name, "after");
+
+        let state = PyDict::new(py);
+        state.set_item("name", "rust").unwrap();
+        state.set_item("scores", vec![2u32]).unwrap();
+        state.set_item("type", py.None()).unwrap();
+        let from_state = Record::from_state(state).unwrap();
+        assert_eq!(
+            from_state,
+            Record {
+                name: "rust".to_owned(),
+                scores: vec![2],
+                r#type: None,
+            }
+        );
+
+        let err = Record::from_state(PyDict::new(py)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "TypeError: pickled state of `Record` is missing field `name`"
+        );
+        assert!(err.is_instance::<PyTypeError>(py));
+    });
+}
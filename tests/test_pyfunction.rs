@@ -111,6 +111,31 @@ fn test_functions_with_function_args() {
     }
 }
 
+#[test]
+#[cfg(not(Py_LIMITED_API))]
+fn test_pyfunction_accessors() {
+    use pyo3::AsPyPointer;
+    Python::with_gil(|py| {
+        let globals = pyo3::types::PyDict::new(py);
+        py.run(
+            "class Foo:\n    def bar(self):\n        return 1\n",
+            Some(globals),
+            None,
+        )
+        .unwrap();
+        let bar = py.eval("Foo.bar", Some(globals), None).unwrap();
+        let bar: &PyFunction = bar.downcast().unwrap();
+        assert_eq!(bar.name().unwrap().to_str().unwrap(), "bar");
+        assert_eq!(bar.qualname().unwrap().to_str().unwrap(), "Foo.bar");
+        assert_eq!(bar.globals().unwrap().as_ptr(), globals.as_ptr());
+        #[cfg(not(PyPy))]
+        assert_eq!(
+            bar.code().unwrap().as_ptr(),
+            bar.getattr("__code__").unwrap().as_ptr()
+        );
+    });
+}
+
 #[cfg(not(Py_LIMITED_API))]
 fn datetime_to_timestamp(dt: &PyAny) -> PyResult<i64> {
     let dt: &PyDateTime = dt.extract()?;
@@ -164,6 +189,103 @@ fn test_function_with_custom_conversion_error() {
     );
 }
 
+const LENGTH_OF: fn(&PyAny) -> PyResult<usize> = |obj| obj.len();
+
+#[pyfunction]
+fn function_with_const_conversion(#[pyo3(from_py_with = "LENGTH_OF")] length: usize) -> usize {
+    length
+}
+
+#[test]
+fn test_function_with_const_conversion() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let const_conv_func = wrap_pyfunction!(function_with_const_conversion)(py).unwrap();
+
+    py_assert!(py, const_conv_func, "const_conv_func([1, 2, 3]) == 3");
+    py_expect_exception!(
+        py,
+        const_conv_func,
+        "const_conv_func(1)",
+        PyTypeError,
+        "argument 'length': object of type 'int' has no len()"
+    );
+}
+
+#[pyfunction(a, "/", b = 2)]
+fn function_with_pos_only_args(a: i32, b: i32) -> i32 {
+    a * b
+}
+
+#[test]
+fn test_function_with_pos_only_args() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let f = wrap_pyfunction!(function_with_pos_only_args)(py).unwrap();
+
+    py_assert!(py, f, "f(3) == 6");
+    py_assert!(py, f, "f(3, b=3) == 9");
+    py_expect_exception!(
+        py,
+        f,
+        "f(a=3)",
+        PyTypeError,
+        "function_with_pos_only_args() got some positional-only arguments passed as keyword arguments: 'a'"
+    );
+}
+
+#[pyfunction(x, "/")]
+fn single_pos_only_arg(x: i32) -> i32 {
+    x * 2
+}
+
+#[pyfunction]
+fn only_py_arg(py: Python) -> PyObject {
+    py.None()
+}
+
+#[test]
+fn test_fast_calling_conventions() {
+    Python::with_gil(|py| {
+        // A single positional-only argument is passed with METH_O
+        let f = wrap_pyfunction!(single_pos_only_arg)(py).unwrap();
+        py_assert!(py, f, "f(21) == 42");
+        py_expect_exception!(
+            py,
+            f,
+            "f('21')",
+            PyTypeError,
+            "argument 'x': 'str' object cannot be interpreted as an integer"
+        );
+        py_expect_exception!(py, f, "f()", PyTypeError);
+        py_expect_exception!(py, f, "f(1, 2)", PyTypeError);
+        py_expect_exception!(py, f, "f(x=1)", PyTypeError);
+        #[cfg(not(PyPy))]
+        py_expect_exception!(
+            py,
+            f,
+            "f(1, 2)",
+            PyTypeError,
+            "single_pos_only_arg() takes exactly one argument (2 given)"
+        );
+
+        // A function which only takes `Python` is called with METH_NOARGS
+        let f = wrap_pyfunction!(only_py_arg)(py).unwrap();
+        py_assert!(py, f, "f() is None");
+        py_expect_exception!(py, f, "f(1)", PyTypeError);
+        #[cfg(not(PyPy))]
+        py_expect_exception!(
+            py,
+            f,
+            "f(1)",
+            PyTypeError,
+            "only_py_arg() takes no arguments (1 given)"
+        );
+    });
+}
+
 #[test]
 fn test_raw_function() {
     let gil = Python::acquire_gil();
@@ -239,3 +361,114 @@ fn test_conversion_error() {
         "argument 'option_arg': 'str' object cannot be interpreted as an integer"
     );
 }
+
+/// This doc string is not exposed to Python
+#[pyfunction]
+#[pyo3(no_doc)]
+fn function_without_docs() {}
+
+#[test]
+fn test_pyfunction_no_doc() {
+    Python::with_gil(|py| {
+        let f = wrap_pyfunction!(function_without_docs)(py).unwrap();
+        py_assert!(py, f, "f.__doc__ is None");
+        py_assert!(py, f, "f.__text_signature__ is None");
+    });
+}
+
+#[pyfunction]
+fn count_to(n: usize) -> impl Iterator<Item = usize> {
+    1..=n
+}
+
+#[pyfunction]
+fn checked_reciprocals(values: Vec<f64>) -> impl Iterator<Item = PyResult<f64>> {
+    values.into_iter().map(|v| {
+        if v == 0.0 {
+            Err(pyo3::exceptions::PyZeroDivisionError::new_err(
+                "division by zero",
+            ))
+        } else {
+            Ok(1.0 / v)
+        }
+    })
+}
+
+#[test]
+fn test_pyfunction_returning_iterator() {
+    Python::with_gil(|py| {
+        let count_to = wrap_pyfunction!(count_to)(py).unwrap();
+        py_assert!(py, count_to, "list(count_to(3)) == [1, 2, 3]");
+        py_assert!(py, count_to, "sum(count_to(100)) == 5050");
+        pyo3::py_run!(
+            py,
+            count_to,
+            r#"
+            it = count_to(2)
+            assert iter(it) is it
+            assert next(it) == 1
+            assert next(it) == 2
+            assert next(it, None) is None
+            "#
+        );
+
+        let checked_reciprocals = wrap_pyfunction!(checked_reciprocals)(py).unwrap();
+        py_assert!(
+            py,
+            checked_reciprocals,
+            "list(checked_reciprocals([1.0, 2.0])) == [1.0, 0.5]"
+        );
+        py_expect_exception!(
+            py,
+            checked_reciprocals,
+            "list(checked_reciprocals([1.0, 0.0]))",
+            PyZeroDivisionError
+        );
+    });
+}
+
+#[pyclass]
+struct Counter {
+    count: u64,
+}
+
+#[pyfunction(method_of = "Counter")]
+fn increment(counter: &mut Counter) -> u64 {
+    counter.count += 1;
+    counter.count
+}
+
+#[test]
+fn test_pyfunction_method_of() {
+    Python::with_gil(|py| {
+        let increment = wrap_pyfunction!(increment)(py).unwrap();
+        let counter = Py::new(py, Counter { count: 0 }).unwrap();
+        py_assert!(py, increment counter, "increment(counter) == 1");
+        py_assert!(py, increment counter, "increment(counter) == 2");
+        py_expect_exception!(py, increment, "increment(1)", PyTypeError);
+        py_expect_exception!(py, increment, "increment()", PyTypeError);
+
+        let err = increment.call1((1,)).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("TypeError: argument 'counter': "));
+    });
+}
+
+/// An alias which the macros can't recognise as `Python` by its name.
+type Gil<'py> = Python<'py>;
+
+#[pyfunction]
+fn python_between_arguments(a: i64, #[pyo3(python)] gil: Gil, b: i64) -> PyObject {
+    (a + b).into_py(gil)
+}
+
+#[test]
+fn test_pyfunction_python_argument() {
+    Python::with_gil(|py| {
+        let f = wrap_pyfunction!(python_between_arguments)(py).unwrap();
+        py_assert!(py, f, "f(1, 2) == 3");
+        py_assert!(py, f, "f(a=1, b=2) == 3");
+        py_expect_exception!(py, f, "f(1, 2, 3)", PyTypeError);
+    });
+}
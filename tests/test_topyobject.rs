@@ -0,0 +1,169 @@
+use pyo3::prelude::*;
+use pyo3::py_run;
+
+#[macro_use]
+mod common;
+
+#[derive(ToPyObject)]
+struct Point {
+    x: i64,
+    label: String,
+    r#type: Option<&'static str>,
+}
+
+#[derive(ToPyObject)]
+struct Renamed {
+    #[pyo3(item("first-name"))]
+    first_name: String,
+}
+
+#[derive(ToPyObject)]
+struct Meters(f64);
+
+#[derive(ToPyObject)]
+#[pyo3(transparent)]
+struct Wrapper {
+    inner: Vec<u8>,
+}
+
+#[derive(ToPyObject)]
+struct Pair<T>(T, T);
+
+#[test]
+fn test_structs() {
+    Python::with_gil(|py| {
+        let point = Point {
+            x: 1,
+            label: "origin".to_owned(),
+            r#type: None,
+        }
+        .to_object(py);
+        let renamed = Renamed {
+            first_name: "Ferris".to_owned(),
+        }
+        .to_object(py);
+        let meters = Meters(2.5).to_object(py);
+        let wrapper = Wrapper { inner: vec![1, 2] }.to_object(py);
+        let pair = Pair("a", "b").to_object(py);
+        py_run!(
+            py,
+            point renamed meters wrapper pair,
+            r#"
+            assert point == {"x": 1, "label": "origin", "type": None}
+            assert renamed == {"first-name": "Ferris"}
+            assert meters == 2.5
+            assert wrapper == [1, 2]
+            assert pair == ("a", "b")
+            "#
+        );
+    });
+}
+
+#[derive(ToPyObject)]
+enum Color {
+    Red,
+    Green,
+    #[pyo3(name = "blue")]
+    Blue,
+}
+
+#[derive(ToPyObject)]
+#[repr(u8)]
+enum Level {
+    Low = 1,
+    Medium,
+    High = 10,
+}
+
+#[derive(ToPyObject)]
+enum Shape {
+    Circle {
+        radius: f64,
+    },
+    #[pyo3(name = "rect")]
+    Rectangle(f64, f64),
+    Empty,
+}
+
+/// Fields named like the locals of the generated code.
+#[derive(ToPyObject)]
+enum Shadowing {
+    Locals { py: i32, dict: i32 },
+}
+
+#[test]
+fn test_enums() {
+    Python::with_gil(|py| {
+        let colors = vec![
+            Color::Red.to_object(py),
+            Color::Green.to_object(py),
+            Color::Blue.to_object(py),
+        ];
+        let levels = vec![
+            Level::Low.to_object(py),
+            Level::Medium.to_object(py),
+            Level::High.to_object(py),
+        ];
+        let shapes = vec![
+            Shape::Circle { radius: 1.0 }.to_object(py),
+            Shape::Rectangle(2.0, 3.0).to_object(py),
+            Shape::Empty.to_object(py),
+        ];
+        let shadowing = Shadowing::Locals { py: 1, dict: 2 }.to_object(py);
+        py_run!(
+            py,
+            colors levels shapes shadowing,
+            r#"
+            assert colors == ["Red", "Green", "blue"]
+            assert levels == [1, 2, 10]
+            assert shapes == [
+                {"type": "Circle", "radius": 1.0},
+                {"type": "rect", "0": 2.0, "1": 3.0},
+                {"type": "Empty"},
+            ]
+            assert shadowing == {"type": "Locals", "py": 1, "dict": 2}
+            "#
+        );
+    });
+}
+
+#[derive(Debug, PartialEq, FromPyObject, ToPyObject)]
+struct Config {
+    #[pyo3(item)]
+    name: String,
+    #[pyo3(item("retry-count"))]
+    retries: u32,
+}
+
+#[derive(Debug, PartialEq, FromPyObject, ToPyObject)]
+enum IntOrStr {
+    #[pyo3(name = "int")]
+    Int(i64),
+    #[pyo3(annotation = "str")]
+    Str(String),
+}
+
+#[test]
+fn test_with_from_pyobject() {
+    Python::with_gil(|py| {
+        let config = Config {
+            name: "default".to_owned(),
+            retries: 3,
+        };
+        let obj = config.to_object(py);
+        py_assert!(py, obj, "obj == {'name': 'default', 'retry-count': 3}");
+        assert_eq!(obj.extract::<Config>(py).unwrap(), config);
+
+        let obj = IntOrStr::Int(5).to_object(py);
+        py_assert!(py, obj, "obj == {'type': 'int', '0': 5}");
+        let err = py
+            .eval("[]", None, None)
+            .unwrap()
+            .extract::<IntOrStr>()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "TypeError: 'list' object cannot be converted to 'Union[int, str]'"
+        );
+    });
+}
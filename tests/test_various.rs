@@ -205,3 +205,63 @@ fn test_result_conversion() {
     let py = gil.python();
     wrap_pyfunction!(result_conversion_function)(py).unwrap();
 }
+
+#[pyclass]
+struct InMemoryImporter {
+    source: String,
+}
+
+#[pymethods]
+impl InMemoryImporter {
+    fn find_spec(
+        slf: PyRef<Self>,
+        py: Python,
+        fullname: &str,
+        _path: &PyAny,
+        _target: Option<PyObject>,
+    ) -> PyResult<PyObject> {
+        if fullname != "pyo3_in_memory_module" {
+            return Ok(py.None());
+        }
+        let spec = py
+            .import("importlib.util")?
+            .call_method1("spec_from_loader", (fullname, slf))?;
+        Ok(spec.into())
+    }
+
+    fn create_module(&self, _spec: &PyAny) -> Option<PyObject> {
+        None
+    }
+
+    fn exec_module(&self, py: Python, module: &PyModule) -> PyResult<()> {
+        py.run(&self.source, Some(module.dict()), None)
+    }
+}
+
+#[test]
+fn test_add_meta_path_finder() {
+    Python::with_gil(|py| {
+        py.add_meta_path_finder(InMemoryImporter {
+            source: "answer = 42".to_string(),
+        })
+        .unwrap();
+
+        let module = py.import("pyo3_in_memory_module").unwrap();
+        assert_eq!(
+            module.getattr("answer").unwrap().extract::<i32>().unwrap(),
+            42
+        );
+        assert!(py.import("pyo3_no_such_in_memory_module").is_err());
+
+        py_run!(
+            py,
+            module,
+            r#"
+            import sys
+            finder = sys.meta_path.pop(0)
+            assert type(finder).__name__ == "InMemoryImporter"
+            del sys.modules["pyo3_in_memory_module"]
+            "#
+        );
+    });
+}
@@ -0,0 +1,6 @@
+use pyo3::prelude::*;
+
+#[pyclass(finalizer)]
+struct Connection {}
+
+fn main() {}
@@ -12,4 +12,16 @@ fn from_py_with_string(#[pyo3("from_py_with")] param: String) {}
 #[pyfunction]
 fn from_py_with_value_not_a_string(#[pyo3(from_py_with = func)] param: String) {}
 
+#[pyfunction]
+fn python_twice(#[pyo3(python, python)] py: Python) {}
+
+#[pyfunction]
+fn python_from_py_with(#[pyo3(python, from_py_with = "func")] py: Python) {}
+
+#[pyfunction]
+fn from_py_with_python_type(#[pyo3(from_py_with = "func")] py: Python) {}
+
+#[pyfunction(args = "*")]
+fn from_py_with_args(#[pyo3(from_py_with = "func")] args: &pyo3::types::PyTuple) {}
+
 fn main() {}
@@ -10,4 +10,19 @@ fn kw_after_kwargs(py: Python, kwargs: &PyDict, a: i32) -> PyObject {
     [a.to_object(py), vararg.into()].to_object(py)
 }
 
+#[pyfunction("/", a)]
+fn pos_only_first(a: i32) -> i32 {
+    a
+}
+
+#[pyfunction(a, "*", b, "/")]
+fn pos_only_after_varargs(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+#[pyfunction(b, "/")]
+fn pos_only_not_first(a: i32, b: i32) -> i32 {
+    a + b
+}
+
 fn main() {}
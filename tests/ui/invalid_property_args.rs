@@ -25,6 +25,9 @@ impl ClassWithSetter {
 }
 
 #[pyclass]
-struct TupleGetterSetter(#[pyo3(get, set)] i32);
+struct NameWithoutGetter(#[pyo3(name = "value")] i32);
+
+#[pyclass]
+struct UnknownFieldOption(#[pyo3(get, rename = "value")] i32);
 
 fn main() {}
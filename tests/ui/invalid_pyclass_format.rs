@@ -0,0 +1,10 @@
+use pyo3::prelude::*;
+
+#[pyclass(str)]
+struct NoFormatting {}
+
+#[pyclass(display)]
+#[derive(Debug)]
+struct NoDisplay {}
+
+fn main() {}
@@ -108,4 +108,27 @@ impl MyClass {
     async fn async_method(&self) {}
 }
 
+#[pymethods]
+impl MyClass {
+    #[getter]
+    async fn async_getter(slf: Py<Self>) {}
+}
+
+#[pymethods]
+impl MyClass {
+    #[new]
+    async fn async_new() -> Self {
+        MyClass {}
+    }
+}
+
+#[pymethods]
+impl MyClass {
+    #[get_descriptor]
+    fn get(&self, _obj: Option<&PyAny>, _objtype: Option<&pyo3::types::PyType>) {}
+
+    #[get_descriptor]
+    fn get_again(&self, _obj: Option<&PyAny>, _objtype: Option<&pyo3::types::PyType>) {}
+}
+
 fn main() {}
@@ -0,0 +1,10 @@
+use pyo3::prelude::*;
+
+struct NotPyClass;
+
+#[pymethods]
+impl NotPyClass {
+    fn method(&self) {}
+}
+
+fn main() {}
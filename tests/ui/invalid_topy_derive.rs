@@ -0,0 +1,17 @@
+use pyo3::prelude::ToPyObject;
+
+#[derive(ToPyObject)]
+struct RenamedAttribute {
+    #[pyo3(attribute("x"))]
+    value: i32,
+}
+
+#[derive(ToPyObject)]
+enum RenamedVariantAttribute {
+    Value {
+        #[pyo3(attribute("x"))]
+        value: i32,
+    },
+}
+
+fn main() {}
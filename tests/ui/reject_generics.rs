@@ -5,4 +5,9 @@ struct ClassWithGenerics<A> {
     a: A,
 }
 
+#[pyclass(unsafe_lifetime)]
+struct ClassWithLifetimeAndGenerics<'a, A> {
+    a: &'a A,
+}
+
 fn main() {}